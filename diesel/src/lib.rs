@@ -43,7 +43,7 @@ pub use deadpool::{
 pub use self::{error::Error, manager::Manager};
 
 /// Type alias for using [`deadpool::managed::PoolError`] with [`diesel`].
-pub type PoolError = managed::PoolError<Error>;
+pub type PoolError = managed::PoolError<InteractError<Error>>;
 
 /// Connection which is returned by the [`Pool`].
 pub type Connection<C> = managed::sync::SyncWrapper<C, Error>;
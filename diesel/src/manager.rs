@@ -6,7 +6,7 @@ use deadpool::{
     Runtime,
 };
 
-use crate::{Connection, Error};
+use crate::{Connection, Error, InteractError};
 
 /// [`Connection`] [`Manager`] for use with [`diesel`].
 ///
@@ -49,7 +49,7 @@ where
     C: diesel::Connection + 'static,
 {
     type Type = Connection<C>;
-    type Error = Error;
+    type Error = InteractError<Error>;
 
     async fn create(&self) -> Result<Self::Type, Self::Error> {
         let database_url = self.database_url.clone();
@@ -65,6 +65,11 @@ where
                 "Mutex is poisoned. Connection is considered unusable.".into(),
             ));
         }
+        if obj.is_aborted() {
+            return Err(RecycleError::Message(
+                "Interaction has been aborted. Connection is considered unusable.".into(),
+            ));
+        }
         obj.interact(|conn| conn.execute("SELECT 1").map_err(Error::Ping))
             .await
             .map_err(|e| RecycleError::Message(format!("Panic: {:?}", e)))
@@ -5,7 +5,7 @@ use deadpool::{
     Runtime,
 };
 
-use crate::{Manager, Pool};
+use crate::{InteractError, Manager, Pool};
 
 /// Configuration object.
 ///
@@ -63,7 +63,10 @@ impl Config {
     /// See [`BuildError`] and [`rusqlite::Error`] for details.
     ///
     /// [`RedisError`]: redis::RedisError
-    pub fn create_pool(&self, runtime: Runtime) -> Result<Pool, BuildError<rusqlite::Error>> {
+    pub fn create_pool(
+        &self,
+        runtime: Runtime,
+    ) -> Result<Pool, BuildError<InteractError<rusqlite::Error>>> {
         let manager = Manager::from_config(self, runtime);
         Pool::builder(manager)
             .config(self.get_pool_config())
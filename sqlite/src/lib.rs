@@ -40,7 +40,7 @@ pub use self::config::Config;
 pub type Pool = managed::Pool<Manager>;
 
 /// Type alias for using [`deadpool::managed::PoolError`] with [`rusqlite`].
-pub type PoolError = managed::PoolError<rusqlite::Error>;
+pub type PoolError = managed::PoolError<InteractError<rusqlite::Error>>;
 
 /// Type alias for using [`deadpool::managed::Object`] with [`rusqlite`].
 pub type Connection = managed::Object<Manager>;
@@ -71,7 +71,7 @@ impl Manager {
 #[async_trait]
 impl managed::Manager for Manager {
     type Type = SyncWrapper<rusqlite::Connection, rusqlite::Error>;
-    type Error = rusqlite::Error;
+    type Error = InteractError<rusqlite::Error>;
 
     async fn create(&self) -> Result<Self::Type, Self::Error> {
         let path = self.config.path.clone();
@@ -84,6 +84,11 @@ impl managed::Manager for Manager {
                 "Mutex is poisoned. Connection is considered unusable.".into(),
             ));
         }
+        if conn.is_aborted() {
+            return Err(RecycleError::Message(
+                "Interaction has been aborted. Connection is considered unusable.".into(),
+            ));
+        }
         let recycle_count = self.recycle_count.fetch_add(1, Ordering::Relaxed);
         let n: usize = conn
             .interact(move |conn| conn.query_row("SELECT $1", [recycle_count], |row| row.get(0)))
@@ -36,6 +36,8 @@ async fn panic() {
             .await;
         assert!(matches!(result, Err(InteractError::Panic(_))))
     }
+    // The connection has been discarded instead of being returned.
+    assert_eq!(pool.status().size, 0);
     // The previous callback panicked. The pool should recover from this.
     let conn = pool.get().await.unwrap();
     let result: i64 = conn
@@ -20,6 +20,21 @@
     unused_results
 )]
 
+/// Wraps the given future into an `async-backtrace` frame pointing at the
+/// surrounding function if the `async-backtrace` feature is enabled.
+#[cfg_attr(not(any(feature = "managed", feature = "sync")), allow(unused_macros))]
+macro_rules! framed {
+    ($future:expr) => {{
+        #[cfg(feature = "async-backtrace")]
+        let future = async_backtrace::frame!($future);
+        #[cfg(not(feature = "async-backtrace"))]
+        let future = $future;
+        future
+    }};
+}
+
+#[cfg(any(feature = "managed", feature = "unmanaged"))]
+mod lock;
 mod runtime;
 
 #[cfg(feature = "managed")]
@@ -30,15 +45,21 @@ pub mod managed;
 #[cfg_attr(docsrs, doc(cfg(feature = "unmanaged")))]
 pub mod unmanaged;
 
+#[cfg(feature = "sync")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sync")))]
+pub mod sync;
+
 // For handy re-usage in integration crates.
 #[cfg(feature = "managed")]
 #[doc(hidden)]
 pub use async_trait::async_trait;
 
+use std::iter::FromIterator;
+
 pub use self::runtime::{Runtime, SpawnBlockingError};
 
 /// The current pool status.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct Status {
     /// The maximum size of the pool.
     pub max_size: usize,
@@ -50,4 +71,188 @@ pub struct Status {
     /// objects in the pool this number can become negative and stores the
     /// number of futures waiting for an object.
     pub available: isize,
+
+    /// The number of failed attempts to get an object split by cause.
+    ///
+    /// This is only tracked by managed pools and always zero for unmanaged
+    /// ones.
+    pub rejections: Rejections,
+
+    /// The number of `SyncWrapper`s dropped by this pool which are still
+    /// waiting to be dropped on a blocking thread.
+    ///
+    /// `sync::pending_drops()` returns the process-wide number. This is
+    /// only tracked by managed pools and always zero for unmanaged ones or
+    /// without the `sync` feature.
+    pub pending_blocking_drops: usize,
+}
+
+/// Approximate memory in bytes held by a pool as returned by
+/// `managed::Pool::memory_usage()`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MemoryUsage {
+    /// Memory used by the pool itself for keeping track of its objects.
+    pub bookkeeping: usize,
+
+    /// Memory used by the pooled objects including the ones which are
+    /// currently in use, as estimated by `Manager::memory_usage()`.
+    pub objects: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the memory used by the pool and its objects together.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.bookkeeping + self.objects
+    }
+}
+
+/// Number of failed attempts to get an object from a pool split by cause.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Rejections {
+    /// Attempts which timed out while waiting for a slot.
+    pub wait_timeouts: usize,
+
+    /// Attempts which timed out while creating a new object.
+    pub create_timeouts: usize,
+
+    /// Attempts which failed as the pool was exhausted and configured to
+    /// fail instead of waiting.
+    pub exhausted: usize,
+
+    /// Attempts which failed as too many tasks were waiting already.
+    pub queue_full: usize,
+
+    /// Attempts which have been cancelled, either by dropping the future
+    /// or by a cancellation signal.
+    pub cancelled: usize,
+}
+
+impl Status {
+    /// Returns the changes since the given `earlier` [`Status`] of the same
+    /// pool, e.g. to compare a canary before and after a deploy window.
+    #[must_use]
+    pub fn diff(&self, earlier: &Status) -> StatusDiff {
+        let delta = |now: usize, then: usize| now as isize - then as isize;
+        StatusDiff {
+            max_size: delta(self.max_size, earlier.max_size),
+            size: delta(self.size, earlier.size),
+            available: self.available - earlier.available,
+            rejections: self.rejections.diff(&earlier.rejections),
+            pending_blocking_drops: delta(
+                self.pending_blocking_drops,
+                earlier.pending_blocking_drops,
+            ),
+        }
+    }
+}
+
+/// Changes between two [`Status`]es of a pool as returned by
+/// [`Status::diff()`].
+///
+/// Gauges like the `size` contain the signed change, while the
+/// [`Rejections`] contain the number of rejections in between.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StatusDiff {
+    /// Change of the maximum size of the pool.
+    pub max_size: isize,
+
+    /// Change of the current size of the pool.
+    pub size: isize,
+
+    /// Change of the number of available objects.
+    pub available: isize,
+
+    /// Rejections which happened in between.
+    pub rejections: Rejections,
+
+    /// Change of the number of pending blocking drops.
+    pub pending_blocking_drops: isize,
+}
+
+impl Rejections {
+    /// Returns the number of rejections since the given `earlier` snapshot.
+    ///
+    /// Counters which are lower than in `earlier`, e.g. because it belongs to
+    /// a different pool, result in zero.
+    #[must_use]
+    pub fn diff(&self, earlier: &Rejections) -> Rejections {
+        Rejections {
+            wait_timeouts: self.wait_timeouts.saturating_sub(earlier.wait_timeouts),
+            create_timeouts: self.create_timeouts.saturating_sub(earlier.create_timeouts),
+            exhausted: self.exhausted.saturating_sub(earlier.exhausted),
+            queue_full: self.queue_full.saturating_sub(earlier.queue_full),
+            cancelled: self.cancelled.saturating_sub(earlier.cancelled),
+        }
+    }
+
+    /// Returns the number of rejections of all causes.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.wait_timeouts
+            + self.create_timeouts
+            + self.exhausted
+            + self.queue_full
+            + self.cancelled
+    }
+}
+
+/// Combined [`Status`] of several pools.
+///
+/// This can be collected from any iterator of [`Status`]es, e.g. the ones of
+/// pools of different types:
+///
+/// ```rust
+/// use deadpool::{
+///     managed::{self, NoopManager},
+///     unmanaged, AggregateStatus,
+/// };
+///
+/// let db_pool = managed::Pool::<NoopManager>::builder(NoopManager::new())
+///     .max_size(4)
+///     .build()
+///     .unwrap();
+/// let cache_pool = unmanaged::Pool::from(vec![1, 2]);
+///
+/// let status: AggregateStatus = [db_pool.status(), cache_pool.status()]
+///     .iter()
+///     .copied()
+///     .collect();
+/// assert_eq!(status.total.max_size, 6);
+/// assert_eq!(status.pools.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AggregateStatus {
+    /// Sum of all the [`Status`]es.
+    pub total: Status,
+
+    /// [`Status`] of every single pool in the order they were provided.
+    pub pools: Vec<Status>,
+}
+
+impl FromIterator<Status> for AggregateStatus {
+    fn from_iter<I: IntoIterator<Item = Status>>(iter: I) -> Self {
+        let mut aggregate = Self::default();
+        for status in iter {
+            let total = &mut aggregate.total;
+            total.max_size += status.max_size;
+            total.size += status.size;
+            total.available += status.available;
+            let (t, r) = (&mut total.rejections, status.rejections);
+            t.wait_timeouts += r.wait_timeouts;
+            t.create_timeouts += r.create_timeouts;
+            t.exhausted += r.exhausted;
+            t.queue_full += r.queue_full;
+            t.cancelled += r.cancelled;
+            total.pending_blocking_drops += status.pending_blocking_drops;
+            aggregate.pools.push(status);
+        }
+        aggregate
+    }
+}
+
+/// Combines the given [`Status`]es of several pools into one
+/// [`AggregateStatus`].
+pub fn aggregate_status(statuses: impl IntoIterator<Item = Status>) -> AggregateStatus {
+    statuses.into_iter().collect()
 }
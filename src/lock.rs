@@ -0,0 +1,76 @@
+//! Lock implementation used for all internal synchronization.
+//!
+//! All internal state is guarded by the [`Mutex`] of this module which
+//! delegates to a backend implementing the [`Lock`] trait. The backend is
+//! selected at compile time:
+//!
+//! - By default this is [`std::sync::Mutex`] ignoring poisoning. None of the
+//!   data guarded by these locks can be left in an inconsistent state by a
+//!   panic, so a panic in one task must not render the whole pool unusable
+//!   for all the others.
+//! - Enabling the `parking_lot` feature swaps it for `parking_lot::Mutex`
+//!   which has no notion of poisoning at all.
+//!
+//! Supporting another lock (e.g. the one of `loom`) only requires an
+//! implementation of [`Lock`] and a `cfg` selecting it as the backend.
+//!
+//! The object wrapped by a `SyncWrapper` isn't internal state and keeps
+//! using a [`std::sync::Mutex`] on purpose: a poisoned lock tells that the
+//! object might be broken, which is reported via
+//! `SyncWrapper::is_mutex_poisoned()`.
+
+use std::{fmt, ops::DerefMut, sync::PoisonError};
+
+/// Mutual exclusion lock which can be used as backend of [`Mutex`].
+pub(crate) trait Lock<'a, T: 'a> {
+    /// RAII guard releasing the lock when dropped.
+    type Guard: DerefMut<Target = T> + 'a;
+
+    /// Acquires the lock, blocking the current thread until it is available.
+    /// This must not fail, e.g. due to poisoning.
+    fn lock(&'a self) -> Self::Guard;
+}
+
+impl<'a, T: 'a> Lock<'a, T> for std::sync::Mutex<T> {
+    type Guard = std::sync::MutexGuard<'a, T>;
+
+    fn lock(&'a self) -> Self::Guard {
+        std::sync::Mutex::lock(self).unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<'a, T: 'a> Lock<'a, T> for parking_lot::Mutex<T> {
+    type Guard = parking_lot::MutexGuard<'a, T>;
+
+    fn lock(&'a self) -> Self::Guard {
+        parking_lot::Mutex::lock(self)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+type Backend<T> = parking_lot::Mutex<T>;
+
+#[cfg(not(feature = "parking_lot"))]
+type Backend<T> = std::sync::Mutex<T>;
+
+/// Lock guarding internal state using the [`Lock`] backend selected via
+/// features.
+#[derive(Default)]
+pub(crate) struct Mutex<T>(Backend<T>);
+
+impl<T> Mutex<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(Backend::new(value))
+    }
+
+    pub(crate) fn lock(&self) -> <Backend<T> as Lock<'_, T>>::Guard {
+        Lock::lock(&self.0)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Mutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
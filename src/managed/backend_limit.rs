@@ -0,0 +1,32 @@
+/// Report passed to the callback configured via
+/// [`PoolBuilder::on_backend_limit()`] when the maximum size of a [`Pool`]
+/// exceeds the limit reported via [`Manager::size_limit()`].
+///
+/// [`Manager::size_limit()`]: super::Manager::size_limit
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::on_backend_limit()`]: super::PoolBuilder::on_backend_limit
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BackendLimitExceeded {
+    /// Maximum size the [`Pool`] has been configured with.
+    ///
+    /// [`Pool`]: super::Pool
+    pub max_size: usize,
+
+    /// Limit reported by the [`Manager`].
+    ///
+    /// [`Manager`]: super::Manager
+    pub limit: usize,
+
+    /// Indicates whether the [`Pool`] has been shrunk to the `limit` as
+    /// configured via [`PoolConfig::clamp_to_backend_limit`].
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`PoolConfig::clamp_to_backend_limit`]: super::PoolConfig::clamp_to_backend_limit
+    pub clamped: bool,
+}
+
+/// Callback receiving [`BackendLimitExceeded`] reports as configured via
+/// [`PoolBuilder::on_backend_limit()`].
+///
+/// [`PoolBuilder::on_backend_limit()`]: super::PoolBuilder::on_backend_limit
+pub(crate) type OnBackendLimit = Box<dyn Fn(BackendLimitExceeded) + Send + Sync>;
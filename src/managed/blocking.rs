@@ -0,0 +1,213 @@
+//! Blocking facade of a [`Pool`] for use from synchronous code.
+//!
+//! A [`BlockingPool`] owns a [`Pool`] together with its own multi-threaded
+//! Tokio runtime, so background tasks like the reaper and the IO of the
+//! backend keep running while no thread is blocked on the pool. All of its
+//! methods block the calling thread and neither require nor expect a Tokio
+//! context on it. This makes it suitable for embedding in foreign runtimes,
+//! e.g. as a Python extension built with PyO3:
+//!
+//! ```rust
+//! use deadpool::managed::{blocking::BlockingPool, Pool, ValueManager};
+//!
+//! let manager = ValueManager::new(Vec::<u8>::new);
+//! let pool = BlockingPool::new(Pool::builder(manager).max_size(8)).unwrap();
+//! let len = pool.with(|buf| {
+//!     buf.extend_from_slice(b"42");
+//!     buf.len()
+//! });
+//! assert_eq!(len.unwrap(), 2);
+//! ```
+//!
+//! [`Pool`]: super::Pool
+
+use std::{
+    fmt,
+    future::Future,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use tokio::runtime::{Builder, Handle, Runtime as TokioRuntime};
+
+use crate::{Runtime, Status};
+
+use super::{BuildError, Manager, Object, Pool, PoolBuilder, PoolError, ShutdownReport};
+
+/// Thread-safe blocking facade of a [`Pool`] owning its own runtime.
+///
+/// See the [module documentation](self) for details.
+///
+/// [`Pool`]: super::Pool
+pub struct BlockingPool<M: Manager> {
+    pool: Pool<M>,
+    runtime: Arc<TokioRuntime>,
+}
+
+impl<M> fmt::Debug for BlockingPool<M>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingPool")
+            .field("pool", &self.pool)
+            .field("runtime", &self.runtime)
+            .finish()
+    }
+}
+
+impl<M: Manager> Clone for BlockingPool<M> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+impl<M: Manager> BlockingPool<M> {
+    /// Creates a new Tokio runtime and builds the [`Pool`] of the given
+    /// `builder` using it as its [`Runtime`] via
+    /// [`PoolBuilder::build_warm()`], so its [`PoolConfig::min_idle`]
+    /// [`Object`]s are created right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::Config`] if the runtime can't be created and
+    /// any error of [`PoolBuilder::build_warm()`] otherwise.
+    ///
+    /// [`PoolConfig::min_idle`]: super::PoolConfig::min_idle
+    ///
+    /// [`Pool`]: super::Pool
+    pub fn new(builder: PoolBuilder<M>) -> Result<Self, BuildError<M::Error>> {
+        let runtime = Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| BuildError::Config(format!("Failed to create runtime: {}", e)))?;
+        let pool = runtime.block_on(builder.runtime(Runtime::Tokio1).build_warm())?;
+        Ok(Self {
+            pool,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Retrieves an [`Object`] blocking the current thread until one is
+    /// available.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub fn get(&self) -> Result<BlockingObject<M>, PoolError<M::Error>> {
+        let obj = self.runtime.block_on(self.pool.get())?;
+        Ok(BlockingObject {
+            obj: Some(obj),
+            handle: self.runtime.handle().clone(),
+        })
+    }
+
+    /// Retrieves an [`Object`], calls `f` with it and returns it to the
+    /// [`Pool`] again afterwards.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    ///
+    /// [`Pool`]: super::Pool
+    pub fn with<F, R>(&self, f: F) -> Result<R, PoolError<M::Error>>
+    where
+        F: FnOnce(&mut M::Type) -> R,
+    {
+        let mut obj = self.get()?;
+        Ok(f(&mut obj))
+    }
+
+    /// Runs the given `future` to completion on the runtime of this
+    /// [`BlockingPool`] blocking the current thread.
+    ///
+    /// This is meant for interacting with asynchronous [`Object`]s, e.g.
+    /// via [`BlockingPool::pool()`] or a [`BlockingObject`]:
+    ///
+    /// ```rust
+    /// use deadpool::managed::{blocking::BlockingPool, NoopManager, Pool};
+    ///
+    /// let pool = BlockingPool::new(Pool::builder(NoopManager::new())).unwrap();
+    /// let obj = pool.block_on(pool.pool().get()).unwrap();
+    /// assert_eq!(*obj, 0);
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Closes the [`Pool`], see [`Pool::close()`] for details.
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Pool::close()`]: super::Pool::close
+    pub fn close(&self) -> ShutdownReport {
+        let _guard = self.runtime.enter();
+        self.pool.close()
+    }
+
+    /// Retrieves the [`Status`] of the [`Pool`].
+    ///
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub fn status(&self) -> Status {
+        self.pool.status()
+    }
+
+    /// Returns the wrapped [`Pool`].
+    ///
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub fn pool(&self) -> &Pool<M> {
+        &self.pool
+    }
+}
+
+/// [`Object`] retrieved from a [`BlockingPool`].
+///
+/// It is returned to the [`Pool`] within the runtime of its [`BlockingPool`]
+/// when dropped, so this doesn't need a Tokio context either.
+///
+/// [`Pool`]: super::Pool
+#[must_use]
+pub struct BlockingObject<M: Manager> {
+    /// Only [`None`] while being dropped.
+    obj: Option<Object<M>>,
+    handle: Handle,
+}
+
+// Implemented manually to leave out the runtime handle.
+impl<M> fmt::Debug for BlockingObject<M>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingObject")
+            .field("obj", &self.obj)
+            .finish()
+    }
+}
+
+impl<M: Manager> Deref for BlockingObject<M> {
+    type Target = M::Type;
+
+    fn deref(&self) -> &M::Type {
+        self.obj.as_ref().unwrap()
+    }
+}
+
+impl<M: Manager> DerefMut for BlockingObject<M> {
+    fn deref_mut(&mut self) -> &mut M::Type {
+        self.obj.as_mut().unwrap()
+    }
+}
+
+impl<M: Manager> Drop for BlockingObject<M> {
+    fn drop(&mut self) {
+        let _guard = self.handle.enter();
+        drop(self.obj.take());
+    }
+}
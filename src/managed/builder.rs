@@ -1,12 +1,29 @@
-use std::{fmt, marker::PhantomData, time::Duration};
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::Runtime;
 
 use super::{
+    backend_limit::OnBackendLimit,
+    discard::OnDiscard,
+    eviction::{EvictionPolicy, LeastRecentlyUsed},
     hooks::{self, Hooks},
-    Manager, Object, Pool, PoolConfig, Timeouts,
+    intercept::Interceptor,
+    layer::Layer,
+    random::{RandomSource, SystemRandom},
+    task_panic::OnTaskPanic,
+    BackendLimitExceeded, BackgroundTask, CapacityLimiter, CreateLimit, CreateRetry, DiscardReason,
+    ErrorRateLimit, Manager, Object, OnExhausted, Pool, PoolConfig, PoolError, Quarantine,
+    QueueMode, RecycleExperiment, Reset, TimeoutCooldown, Timeouts, WaitTimeoutScaling,
 };
 
+#[cfg(all(unix, feature = "host_limit"))]
+use super::HostLimiter;
+
 /// Possible errors returned when [`PoolBuilder::build()`] fails to build a
 /// [`Pool`].
 #[derive(Debug)]
@@ -20,6 +37,10 @@ pub enum BuildError<E> {
 
     /// [`Runtime`] is required.
     NoRuntimeSpecified(String),
+
+    /// Creating the [`PoolConfig::min_idle`] [`Object`]s failed while
+    /// building the [`Pool`] via [`PoolBuilder::build_warm()`].
+    WarmUp(PoolError<E>),
 }
 
 impl<E> From<E> for BuildError<E> {
@@ -28,7 +49,7 @@ impl<E> From<E> for BuildError<E> {
     }
 }
 
-impl<E: std::fmt::Display> fmt::Display for BuildError<E> {
+impl<E: fmt::Display> fmt::Display for BuildError<E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Config(msg) => {
@@ -40,6 +61,7 @@ impl<E: std::fmt::Display> fmt::Display for BuildError<E> {
                 "Error occurred while building the pool: NoRuntimeSpecified: {}",
                 msg
             ),
+            Self::WarmUp(e) => write!(f, "Error occurred while building the pool: WarmUp: {}", e),
         }
     }
 }
@@ -49,10 +71,17 @@ impl<E: std::error::Error + 'static> std::error::Error for BuildError<E> {
         match self {
             Self::Config(_) | Self::NoRuntimeSpecified(_) => None,
             Self::Backend(e) => Some(e),
+            Self::WarmUp(e) => Some(e),
         }
     }
 }
 
+/// Callback invoked by [`PoolBuilder::build()`].
+type OnBuild = Box<dyn FnOnce(&PoolConfig) + Send + Sync>;
+
+/// Function returning the deadline of the current request.
+pub(crate) type DeadlineExtractor = Box<dyn Fn() -> Option<Instant> + Send + Sync>;
+
 /// Builder for [`Pool`]s.
 ///
 /// Instances of this are created by calling the [`Pool::builder()`] method.
@@ -66,6 +95,19 @@ where
     pub(crate) config: PoolConfig,
     pub(crate) runtime: Option<Runtime>,
     pub(crate) hooks: Hooks<M>,
+    pub(crate) interceptors: Vec<Box<dyn Interceptor<M::Error>>>,
+    pub(crate) reset: Option<fn(&mut M::Type)>,
+    pub(crate) eviction_policy: Box<dyn EvictionPolicy>,
+    pub(crate) random: Box<dyn RandomSource>,
+    pub(crate) deadline: Option<DeadlineExtractor>,
+    pub(crate) seed: Vec<M::Type>,
+    pub(crate) on_discard: Option<OnDiscard<M::Type>>,
+    pub(crate) on_backend_limit: Option<OnBackendLimit>,
+    pub(crate) on_task_panic: Option<OnTaskPanic>,
+    pub(crate) capacity_limiter: Option<CapacityLimiter>,
+    #[cfg(all(unix, feature = "host_limit"))]
+    pub(crate) host_limiter: Option<HostLimiter>,
+    on_build: Option<OnBuild>,
     _wrapper: PhantomData<fn() -> W>,
 }
 
@@ -76,13 +118,27 @@ where
     W: From<Object<M>>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("PoolBuilder")
+        let mut f = f.debug_struct("PoolBuilder");
+        let _ = f
             .field("manager", &self.manager)
             .field("config", &self.config)
             .field("runtime", &self.runtime)
             .field("hooks", &self.hooks)
-            .field("_wrapper", &self._wrapper)
-            .finish()
+            .field("interceptors", &self.interceptors)
+            .field("reset", &self.reset.is_some())
+            .field("eviction_policy", &self.eviction_policy)
+            .field("random", &self.random)
+            .field("deadline", &self.deadline.is_some())
+            .field("seed", &self.seed.len())
+            .field("on_discard", &self.on_discard.is_some())
+            .field("on_backend_limit", &self.on_backend_limit.is_some())
+            .field("on_task_panic", &self.on_task_panic.is_some())
+            .field("capacity_limiter", &self.capacity_limiter)
+            .field("on_build", &self.on_build.is_some())
+            .field("_wrapper", &self._wrapper);
+        #[cfg(all(unix, feature = "host_limit"))]
+        let _ = f.field("host_limiter", &self.host_limiter);
+        f.finish()
     }
 }
 
@@ -97,16 +153,32 @@ where
             config: PoolConfig::default(),
             runtime: None,
             hooks: Hooks::default(),
-            _wrapper: PhantomData::default(),
+            interceptors: Vec::new(),
+            reset: None,
+            eviction_policy: Box::new(LeastRecentlyUsed),
+            random: Box::new(SystemRandom),
+            deadline: None,
+            seed: Vec::new(),
+            on_discard: None,
+            on_backend_limit: None,
+            on_task_panic: None,
+            capacity_limiter: None,
+            #[cfg(all(unix, feature = "host_limit"))]
+            host_limiter: None,
+            on_build: None,
+            _wrapper: PhantomData,
         }
     }
 
     /// Builds the [`Pool`].
     ///
+    /// This doesn't create any [`PoolConfig::min_idle`] [`Object`]s as it
+    /// can't wait for them. Use [`PoolBuilder::build_warm()`] for that.
+    ///
     /// # Errors
     ///
     /// See [`BuildError`] for details.
-    pub fn build(self) -> Result<Pool<M, W>, BuildError<M::Error>> {
+    pub fn build(mut self) -> Result<Pool<M, W>, BuildError<M::Error>> {
         // Return an error if a timeout is configured without runtime.
         let t = &self.config.timeouts;
         if (t.wait.is_some() || t.create.is_some() || t.recycle.is_some()) && self.runtime.is_none()
@@ -115,7 +187,57 @@ where
                 "Timeouts require a runtime".to_string(),
             ));
         }
-        Ok(Pool::from_builder(self))
+        if self.seed.len() > self.config.max_size {
+            return Err(BuildError::Config(format!(
+                "Seeded {} objects but the maximum size is {}",
+                self.seed.len(),
+                self.config.max_size
+            )));
+        }
+        if self.config.min_idle > self.config.max_size {
+            return Err(BuildError::Config(format!(
+                "The minimum of {} idle objects exceeds the maximum size of {}",
+                self.config.min_idle, self.config.max_size
+            )));
+        }
+        if self.config.max_concurrent_creates == Some(0) {
+            return Err(BuildError::Config(
+                "The maximum of concurrent creates must not be zero".to_string(),
+            ));
+        }
+        if self.deadline.is_some() && self.runtime.is_none() {
+            return Err(BuildError::NoRuntimeSpecified(
+                "Deadlines require a runtime".to_string(),
+            ));
+        }
+        if self.config.create_retry.is_some() && self.runtime.is_none() {
+            return Err(BuildError::NoRuntimeSpecified(
+                "Retrying creations requires a runtime".to_string(),
+            ));
+        }
+        let on_build = self.on_build.take();
+        let pool = Pool::from_builder(self)?;
+        if let Some(on_build) = on_build {
+            on_build(&pool.inner.config);
+        }
+        Ok(pool)
+    }
+
+    /// Builds the [`Pool`] like [`PoolBuilder::build()`] and creates its
+    /// [`PoolConfig::min_idle`] [`Object`]s right away via
+    /// [`Pool::warm_up()`], so the first requests after a cold start don't
+    /// pay for establishing them.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolBuilder::build()`]. Failing to create the [`Object`]s is
+    /// reported as [`BuildError::WarmUp`].
+    pub async fn build_warm(self) -> Result<Pool<M, W>, BuildError<M::Error>> {
+        let pool = self.build()?;
+        pool.warm_up(pool.inner.config.min_idle)
+            .await
+            .map_err(BuildError::WarmUp)?;
+        Ok(pool)
     }
 
     /// Sets a [`PoolConfig`] to build the [`Pool`] with.
@@ -154,6 +276,120 @@ where
         self
     }
 
+    /// Sets the [`PoolConfig::cooperative_yield`].
+    pub fn cooperative_yield(mut self, value: bool) -> Self {
+        self.config.cooperative_yield = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::on_exhausted`].
+    pub fn on_exhausted(mut self, value: OnExhausted) -> Self {
+        self.config.on_exhausted = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_waiting`].
+    pub fn max_waiting(mut self, value: Option<usize>) -> Self {
+        self.config.max_waiting = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::wait_timeout_scaling`].
+    pub fn wait_timeout_scaling(mut self, value: WaitTimeoutScaling) -> Self {
+        self.config.wait_timeout_scaling = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_error_rate`].
+    pub fn max_error_rate(mut self, value: Option<ErrorRateLimit>) -> Self {
+        self.config.max_error_rate = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_concurrent_creates`].
+    pub fn max_concurrent_creates(mut self, value: Option<usize>) -> Self {
+        self.config.max_concurrent_creates = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::thread_affinity`].
+    pub fn thread_affinity(mut self, value: bool) -> Self {
+        self.config.thread_affinity = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::timeout_cooldown`].
+    pub fn timeout_cooldown(mut self, value: Option<TimeoutCooldown>) -> Self {
+        self.config.timeout_cooldown = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::min_idle`].
+    pub fn min_idle(mut self, value: usize) -> Self {
+        self.config.min_idle = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::backfill_interval`].
+    pub fn backfill_interval(mut self, value: Option<Duration>) -> Self {
+        self.config.backfill_interval = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::standby`].
+    pub fn standby(mut self, value: bool) -> Self {
+        self.config.standby = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::clamp_to_backend_limit`].
+    pub fn clamp_to_backend_limit(mut self, value: bool) -> Self {
+        self.config.clamp_to_backend_limit = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::queue_mode`].
+    pub fn queue_mode(mut self, value: QueueMode) -> Self {
+        self.config.queue_mode = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::create_retry`].
+    pub fn create_retry(mut self, value: Option<CreateRetry>) -> Self {
+        self.config.create_retry = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::create_limit`].
+    pub fn create_limit(mut self, value: Option<CreateLimit>) -> Self {
+        self.config.create_limit = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::recycle_experiment`].
+    pub fn recycle_experiment(mut self, value: Option<RecycleExperiment>) -> Self {
+        self.config.recycle_experiment = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::quarantine`].
+    pub fn quarantine(mut self, value: Option<Quarantine>) -> Self {
+        self.config.quarantine = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_lifetime`].
+    pub fn max_lifetime(mut self, value: Option<Duration>) -> Self {
+        self.config.max_lifetime = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::idle_timeout`].
+    pub fn idle_timeout(mut self, value: Option<Duration>) -> Self {
+        self.config.idle_timeout = value;
+        self
+    }
+
     /// Attaches a `post_create` hook.
     ///
     /// The given `hook` will be called each time right after a new [`Object`]
@@ -163,15 +399,217 @@ where
         self
     }
 
+    /// Attaches an [`Interceptor`] called around every attempt to get an
+    /// [`Object`].
+    ///
+    /// [`Interceptor::before_get()`] is called in the order the interceptors
+    /// have been attached and [`Interceptor::after_get()`] in reverse order.
+    pub fn interceptor(mut self, interceptor: impl Interceptor<M::Error> + 'static) -> Self {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
+    /// Attaches a `pre_recycle` hook.
+    ///
+    /// The given `hook` will be called each time right before an [`Object`]
+    /// is recycled. If it fails, the [`Object`] is dropped instead.
+    pub fn pre_recycle(mut self, hook: impl hooks::PreRecycle<M> + 'static) -> Self {
+        self.hooks.pre_recycle.push(Box::new(hook));
+        self
+    }
+
     /// Attaches a `post_recycle` hook.
     ///
     /// The given `hook` will be called each time right after an [`Object`] has
-    /// been recycled.
+    /// been recycled. If it fails, the [`Object`] is discarded and replaced.
     pub fn post_recycle(mut self, hook: impl hooks::PostRecycle<M> + 'static) -> Self {
         self.hooks.post_recycle.push(Box::new(hook));
         self
     }
 
+    /// Attaches a `post_idle` hook.
+    ///
+    /// The given `hook` will be called each time right after an [`Object`] has
+    /// been recycled which had been idle for longer than the given
+    /// `threshold`. It runs before any `post_recycle` hooks. If it fails, the
+    /// [`Object`] is discarded and replaced.
+    pub fn post_idle(
+        mut self,
+        threshold: Duration,
+        hook: impl hooks::PostIdle<M> + 'static,
+    ) -> Self {
+        self.hooks.post_idle.push((threshold, Box::new(hook)));
+        self
+    }
+
+    /// Attaches an `on_park` hook.
+    ///
+    /// The given `hook` will be called each time right before an [`Object`]
+    /// is stored as idle object. Together with [`PoolBuilder::on_unpark()`]
+    /// this allows zeroizing or sealing sensitive session material while the
+    /// [`Object`] is idle. Idle objects handed to
+    /// [`PoolBuilder::on_discard()`] are still parked.
+    pub fn on_park(mut self, hook: impl Fn(&mut M::Type) + Send + Sync + 'static) -> Self {
+        self.hooks.on_park.push(Box::new(hook));
+        self
+    }
+
+    /// Attaches an `on_unpark` hook.
+    ///
+    /// The given `hook` will be called each time right after an idle
+    /// [`Object`] has been taken out of the [`Pool`] and before it is
+    /// recycled. If it fails, the [`Object`] is discarded just like if
+    /// [`Manager::recycle()`] had failed.
+    pub fn on_unpark<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut M::Type) -> Result<(), hooks::HookError<M::Error>> + Send + Sync + 'static,
+    {
+        self.hooks.on_unpark.push(Box::new(hook));
+        self
+    }
+
+    /// Sets a callback which is invoked with the effective [`PoolConfig`]
+    /// once the [`Pool`] has been built successfully.
+    ///
+    /// This is useful for logging exactly how each [`Pool`] has been
+    /// configured on startup.
+    pub fn on_build(mut self, f: impl FnOnce(&PoolConfig) + Send + Sync + 'static) -> Self {
+        self.on_build = Some(Box::new(f));
+        self
+    }
+
+    /// Makes the [`Pool`] call [`Reset::reset()`] on every [`Object`] when
+    /// it's returned to the [`Pool`].
+    ///
+    /// This happens right before the [`Object`] is stored as idle object, so
+    /// [`Manager::recycle()`] can rely on it being in a clean state.
+    pub fn reset_on_return(mut self) -> Self
+    where
+        M::Type: Reset,
+    {
+        self.reset = Some(<M::Type as Reset>::reset);
+        self
+    }
+
+    /// Sets the [`EvictionPolicy`] deciding which idle [`Object`]s to drop
+    /// first.
+    ///
+    /// See the [`eviction`] module for details.
+    ///
+    /// [`eviction`]: super::eviction
+    pub fn eviction_policy(mut self, policy: impl EvictionPolicy + 'static) -> Self {
+        self.eviction_policy = Box::new(policy);
+        self
+    }
+
+    /// Sets the [`RandomSource`] used for all random decisions of the
+    /// [`Pool`].
+    ///
+    /// See the [`random`] module for details.
+    ///
+    /// [`random`]: super::random
+    pub fn random(mut self, random: impl RandomSource + 'static) -> Self {
+        self.random = Box::new(random);
+        self
+    }
+
+    /// Sets a function returning the deadline of the current request, e.g.
+    /// by reading a task-local variable.
+    ///
+    /// Whenever a task starts waiting for a slot, the wait timeout is capped
+    /// so it doesn't exceed the returned deadline. This makes deadline
+    /// propagation work even through layers which can't pass [`Timeouts`]
+    /// explicitly. Returning [`None`] leaves the wait timeout unchanged.
+    ///
+    /// This requires a [`Runtime`] to be configured.
+    pub fn deadline(mut self, f: impl Fn() -> Option<Instant> + Send + Sync + 'static) -> Self {
+        self.deadline = Some(Box::new(f));
+        self
+    }
+
+    /// Seeds the [`Pool`] with already established objects.
+    ///
+    /// The objects are added as idle [`Object`]s and handed out before the
+    /// [`Manager`] is asked to create any new ones. This is useful for
+    /// processes which inherit their connections, e.g. via socket activation
+    /// or file descriptor passing, and must not reconnect on startup.
+    ///
+    /// [`PoolBuilder::build()`] fails if more objects are given than the
+    /// [`PoolConfig::max_size`] allows.
+    pub fn seed(mut self, objects: impl IntoIterator<Item = M::Type>) -> Self {
+        self.seed.extend(objects);
+        self
+    }
+
+    /// Sets a callback receiving all the objects discarded by the [`Pool`]
+    /// instead of silently dropping them.
+    ///
+    /// This allows closing them gracefully, e.g. using a protocol-level
+    /// teardown, or collecting statistics before disposal. The callback is
+    /// invoked synchronously and should hand off any expensive work, e.g.
+    /// by sending the object into a channel.
+    pub fn on_discard(
+        mut self,
+        f: impl Fn(M::Type, DiscardReason) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_discard = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a callback which is invoked if the maximum size of the [`Pool`]
+    /// exceeds the limit reported via [`Manager::size_limit()`] after the
+    /// first [`Object`] has been created.
+    ///
+    /// This is the place to emit a warning about the misconfiguration.
+    pub fn on_backend_limit(
+        mut self,
+        f: impl Fn(BackendLimitExceeded) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_backend_limit = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a callback which is invoked with the panic message whenever a
+    /// [`BackgroundTask`] of the [`Pool`] panics.
+    ///
+    /// This covers the reaper spawned via [`Pool::spawn_reaper()`], the
+    /// gradual backfill and the blocking drops of `SyncWrapper`s discarded
+    /// by the [`Pool`]. Without it such panics only reach the default
+    /// handler of the [`Runtime`], so this is the place to forward them to
+    /// a crash reporter.
+    pub fn on_task_panic(
+        mut self,
+        f: impl Fn(BackgroundTask, String) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_task_panic = Some(Arc::new(f));
+        self
+    }
+
+    /// Attaches the [`Pool`] to the given [`CapacityLimiter`] shared with
+    /// other [`Pool`]s.
+    ///
+    /// Waiting for a slot of the [`CapacityLimiter`] counts towards the
+    /// create timeout. Every seeded object takes a slot, so building the
+    /// [`Pool`] fails with a [`BuildError::Config`] if there aren't enough
+    /// free ones.
+    pub fn capacity_limiter(mut self, limiter: CapacityLimiter) -> Self {
+        self.capacity_limiter = Some(limiter);
+        self
+    }
+
+    /// Attaches the [`Pool`] to the given [`HostLimiter`] shared with other
+    /// processes on the same host.
+    ///
+    /// Waiting for a slot of the [`HostLimiter`] counts towards the create
+    /// timeout. Every seeded object takes a slot, so building the [`Pool`]
+    /// fails with a [`BuildError::Config`] if there aren't enough free ones.
+    #[cfg(all(unix, feature = "host_limit"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "host_limit"))))]
+    pub fn host_limiter(mut self, limiter: HostLimiter) -> Self {
+        self.host_limiter = Some(limiter);
+        self
+    }
+
     /// Sets the [`Runtime`].
     ///
     /// # Important
@@ -191,3 +629,37 @@ where
         self
     }
 }
+
+impl<M: Manager + 'static> PoolBuilder<M> {
+    /// Wraps the [`Manager`] of the [`Pool`] with the given [`Layer`].
+    ///
+    /// All the hooks and settings configured so far are kept. See the
+    /// [`layer`] module for details.
+    ///
+    /// [`layer`]: super::layer
+    pub fn wrap_manager<L: Layer<M>>(self, layer: L) -> PoolBuilder<L::Manager>
+    where
+        L::Manager: 'static,
+    {
+        PoolBuilder {
+            manager: layer.layer(self.manager),
+            config: self.config,
+            runtime: self.runtime,
+            hooks: self.hooks.adapt(),
+            interceptors: self.interceptors,
+            reset: self.reset,
+            eviction_policy: self.eviction_policy,
+            random: self.random,
+            deadline: self.deadline,
+            seed: self.seed,
+            on_discard: self.on_discard,
+            on_backend_limit: self.on_backend_limit,
+            on_task_panic: self.on_task_panic,
+            capacity_limiter: self.capacity_limiter,
+            #[cfg(all(unix, feature = "host_limit"))]
+            host_limiter: self.host_limiter,
+            on_build: self.on_build,
+            _wrapper: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, TryAcquireError};
+
+use super::{Manager, Object, ObjectState, Permit, Pool, PoolError};
+
+/// Clonable view of the slots of a [`Pool`].
+///
+/// This allows external systems (e.g. job schedulers) to inspect the capacity
+/// of a [`Pool`] and reserve slots ahead of time. Reserved slots are handed
+/// out as [`Permit`]s which can later be exchanged for [`Object`]s.
+///
+/// Instances of this are created by calling the [`Pool::capacity()`] method.
+pub struct CapacitySemaphore<M: Manager, W: From<Object<M>> = Object<M>> {
+    pool: Pool<M, W>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `W` type parameter.
+impl<M, W> fmt::Debug for CapacitySemaphore<M, W>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+    W: From<Object<M>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapacitySemaphore")
+            .field("pool", &self.pool)
+            .finish()
+    }
+}
+
+impl<M: Manager, W: From<Object<M>>> Clone for CapacitySemaphore<M, W> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<M: Manager, W: From<Object<M>>> CapacitySemaphore<M, W> {
+    pub(crate) fn new(pool: Pool<M, W>) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the number of slots which can currently be acquired without
+    /// waiting.
+    #[must_use]
+    pub fn available_permits(&self) -> usize {
+        self.pool.inner.semaphore.available_permits()
+    }
+
+    /// Returns the total number of slots of the [`Pool`].
+    #[must_use]
+    pub fn max_permits(&self) -> usize {
+        self.pool.inner.max_size.load(Ordering::Relaxed)
+    }
+
+    /// Acquires a single slot waiting for it to become available.
+    ///
+    /// This is the same as calling [`Pool::acquire_permit()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn acquire(&self) -> Result<Permit<M, W>, PoolError<M::Error>> {
+        self.pool.acquire_permit().await
+    }
+
+    /// Tries to acquire a single slot without waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Exhausted`] if there is no slot available right
+    /// now and [`PoolError::Closed`] if the [`Pool`] has been closed.
+    pub fn try_acquire(&self) -> Result<Permit<M, W>, PoolError<M::Error>> {
+        self.try_acquire_many(1)
+            .map(|mut permits| permits.pop().unwrap())
+    }
+
+    /// Atomically acquires `n` slots waiting for all of them to become
+    /// available.
+    ///
+    /// This doesn't apply any timeouts, not even the wait timeout of the
+    /// [`Pool`] handle this [`CapacitySemaphore`] was created from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Closed`] if the [`Pool`] has been closed.
+    pub async fn acquire_many(&self, n: u32) -> Result<Vec<Permit<M, W>>, PoolError<M::Error>> {
+        self.pool
+            .inner
+            .semaphore
+            .acquire_many(n)
+            .await
+            .map_err(|_| PoolError::Closed)?
+            .forget();
+        Ok(self.permits(n))
+    }
+
+    /// Tries to atomically acquire `n` slots without waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Exhausted`] if there are not enough slots
+    /// available right now and [`PoolError::Closed`] if the [`Pool`] has
+    /// been closed.
+    pub fn try_acquire_many(&self, n: u32) -> Result<Vec<Permit<M, W>>, PoolError<M::Error>> {
+        self.pool
+            .inner
+            .semaphore
+            .try_acquire_many(n)
+            .map_err(|e| match e {
+                TryAcquireError::Closed => PoolError::Closed,
+                TryAcquireError::NoPermits => PoolError::Exhausted {
+                    retry_after: self.pool.inner.return_rate.retry_after(),
+                },
+            })?
+            .forget();
+        Ok(self.permits(n))
+    }
+
+    /// Creates [`Permit`]s for `n` slots which have already been removed
+    /// from the semaphore.
+    fn permits(&self, n: u32) -> Vec<Permit<M, W>> {
+        (0..n)
+            .map(|_| {
+                let _ = self.pool.inner.available.fetch_sub(1, Ordering::Relaxed);
+                Permit {
+                    obj: Object {
+                        obj: None,
+                        state: ObjectState::Receiving,
+                        burst: false,
+                        memory: 0,
+                        broken: AtomicBool::new(false),
+                        tag: None,
+                        timeouts: self.pool.timeouts,
+                        pool: Arc::downgrade(&self.pool.inner),
+                    },
+                    info: Default::default(),
+                    timeouts: self.pool.timeouts,
+                    _wrapper: PhantomData,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Limit on the total number of objects shared by multiple [`Pool`]s.
+///
+/// Every [`Pool`] attached to the same [`CapacityLimiter`] via
+/// [`PoolBuilder::capacity_limiter()`] needs to take one of its slots for
+/// every object it creates and gives it back once that object is dropped.
+/// This ensures that all those [`Pool`]s together never exceed a global
+/// limit (e.g. the `max_connections` of a database divided by the number of
+/// replicas of a service) while each of them keeps its own
+/// [`PoolConfig::max_size`].
+///
+/// Idle objects keep their slots, so an idle [`Pool`] might starve the
+/// others. Use [`Pool::evict_idle()`] to give slots back if necessary.
+///
+/// [`PoolBuilder::capacity_limiter()`]: super::PoolBuilder::capacity_limiter
+/// [`PoolConfig::max_size`]: super::PoolConfig::max_size
+#[derive(Clone, Debug)]
+pub struct CapacityLimiter {
+    semaphore: Arc<Semaphore>,
+    max: usize,
+}
+
+impl CapacityLimiter {
+    /// Creates a new [`CapacityLimiter`] allowing at most `max` objects
+    /// across all the attached [`Pool`]s.
+    #[must_use]
+    pub fn new(max: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max)),
+            max,
+        }
+    }
+
+    /// Returns the total number of objects allowed by this
+    /// [`CapacityLimiter`].
+    #[must_use]
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the number of objects which can currently be created by any
+    /// of the attached [`Pool`]s.
+    #[must_use]
+    pub fn available(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    /// Waits for a slot to become available.
+    pub(crate) async fn acquire<E>(&self) -> Result<OwnedSemaphorePermit, PoolError<E>> {
+        self.semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| PoolError::Closed)
+    }
+
+    /// Takes a slot if one is available right now.
+    pub(crate) fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.semaphore.clone().try_acquire_owned().ok()
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+
+/// Background task accompanying a pooled object, e.g. the connection task
+/// of a database driver.
+///
+/// It is stored in the [`Resources`] of its [`Object`] and aborted right
+/// before the object is dropped by the [`Pool`], so companion tasks can't
+/// outlive their objects. [`Manager`]s register them via
+/// [`Manager::companion()`].
+///
+/// When an [`Object`] is taken out of its [`Pool`] via [`Object::take()`],
+/// the companion task is detached and keeps running alongside the taken
+/// object.
+///
+/// [`Manager`]: super::Manager
+/// [`Manager::companion()`]: super::Manager::companion
+/// [`Object`]: super::Object
+/// [`Object::take()`]: super::Object::take
+/// [`Pool`]: super::Pool
+/// [`Resources`]: super::Resources
+#[must_use]
+pub struct Companion {
+    /// Only [`None`] after aborting or detaching.
+    abort: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl fmt::Debug for Companion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Companion")
+            .field("running", &self.abort.is_some())
+            .finish()
+    }
+}
+
+impl Companion {
+    /// Creates a new [`Companion`] calling the given `abort` function to stop
+    /// the task.
+    pub fn new<F>(abort: F) -> Self
+    where
+        F: FnOnce() + Send + Sync + 'static,
+    {
+        Self {
+            abort: Some(Box::new(abort)),
+        }
+    }
+
+    /// Creates a new [`Companion`] aborting the given Tokio task.
+    #[cfg(feature = "rt_tokio_1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rt_tokio_1")))]
+    pub fn from_tokio<T: Send + 'static>(handle: tokio::task::JoinHandle<T>) -> Self {
+        Self::new(move || handle.abort())
+    }
+
+    /// Aborts the task right away.
+    pub fn abort(self) {
+        drop(self);
+    }
+
+    /// Lets the task keep running without being aborted.
+    pub fn detach(mut self) {
+        self.abort = None;
+    }
+}
+
+impl Drop for Companion {
+    fn drop(&mut self) {
+        if let Some(abort) = self.abort.take() {
+            abort();
+        }
+    }
+}
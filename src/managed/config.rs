@@ -1,4 +1,6 @@
-use std::time::Duration;
+use std::{convert::TryFrom, time::Duration};
+
+use super::random::RandomSource;
 
 /// [`Pool`] configuration.
 ///
@@ -16,6 +18,214 @@ pub struct PoolConfig {
     /// [`Pool`]: super::Pool
     #[cfg_attr(feature = "serde", serde(default))]
     pub timeouts: Timeouts,
+
+    /// Yield to the executor once before acquiring a slot whenever other
+    /// tasks are already waiting for an [`Object`].
+    ///
+    /// This keeps a few hot tasks which get and return [`Object`]s in a tight
+    /// loop from monopolizing freshly returned [`Object`]s while the waiting
+    /// tasks didn't get a chance to run yet.
+    ///
+    /// [`Object`]: super::Object
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cooperative_yield: bool,
+
+    /// Behavior of the [`Pool`] when all of its slots are in use.
+    ///
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub on_exhausted: OnExhausted,
+
+    /// Maximum number of tasks waiting for a slot of the [`Pool`].
+    ///
+    /// Once reached, further attempts to get an [`Object`] fail right away
+    /// with a [`PoolError::QueueFull`] instead of joining the queue, so
+    /// callers can shed load early.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`PoolError::QueueFull`]: super::PoolError::QueueFull
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_waiting: Option<usize>,
+
+    /// Order in which idle [`Object`]s are handed out.
+    ///
+    /// [`Object`]: super::Object
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub queue_mode: QueueMode,
+
+    /// Policy for shortening the [`Timeouts::wait`] while many tasks are
+    /// already waiting for an [`Object`].
+    ///
+    /// [`Object`]: super::Object
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wait_timeout_scaling: WaitTimeoutScaling,
+
+    /// Discard [`Object`]s whose errors reported via
+    /// [`Object::report_error()`] exceed this limit.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Object::report_error()`]: super::Object::report_error
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_error_rate: Option<ErrorRateLimit>,
+
+    /// Maximum number of [`Object`]s being created at the same time.
+    ///
+    /// This is independent of the [`PoolConfig::max_size`] and smooths the
+    /// load on the backend (e.g. TCP and TLS handshakes or authentication)
+    /// during cold starts of large [`Pool`]s. Waiting for a creation slot
+    /// counts towards the [`Timeouts::create`]. A limit of zero is rejected
+    /// when building the [`Pool`] as no [`Object`] could ever be created.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_concurrent_creates: Option<usize>,
+
+    /// Prefer handing out idle [`Object`]s to tasks running on the same
+    /// thread that returned them.
+    ///
+    /// With multi-threaded executors this keeps very hot [`Object`]s with a
+    /// lot of local state on the same worker thread and reduces cross-core
+    /// cache traffic. If no [`Object`] has been returned on the current
+    /// thread, the one idle for the longest time is used as usual.
+    ///
+    /// [`Object`]: super::Object
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub thread_affinity: bool,
+
+    /// Maximum lifetime of [`Object`]s.
+    ///
+    /// Idle [`Object`]s older than this are dropped when they would be handed
+    /// out next or by the reaper spawned via [`Pool::spawn_reaper()`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool::spawn_reaper()`]: super::Pool::spawn_reaper
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_lifetime: Option<Duration>,
+
+    /// Maximum time [`Object`]s may stay idle in the [`Pool`].
+    ///
+    /// [`Object`]s idle for longer than this are dropped when they would be
+    /// handed out next or by the reaper spawned via
+    /// [`Pool::spawn_reaper()`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`Pool::spawn_reaper()`]: super::Pool::spawn_reaper
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub idle_timeout: Option<Duration>,
+
+    /// Minimum number of idle [`Object`]s to keep in the [`Pool`].
+    ///
+    /// The reaper spawned via [`Pool::spawn_reaper()`] creates them right
+    /// away and tops them up again on every run, e.g. after [`Object`]s have
+    /// been dropped because of errors. [`PoolBuilder::build_warm()`] and
+    /// `BlockingPool::new()` create them while building the [`Pool`], and
+    /// [`Pool::warm_up()`] creates them at any time without a reaper. This
+    /// must not exceed the [`PoolConfig::max_size`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`Pool::spawn_reaper()`]: super::Pool::spawn_reaper
+    /// [`Pool::warm_up()`]: super::Pool::warm_up
+    /// [`PoolBuilder::build_warm()`]: super::PoolBuilder::build_warm
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_idle: usize,
+
+    /// Create the missing [`PoolConfig::min_idle`] [`Object`]s one at a
+    /// time with this interval in between once burst [`Object`]s have been
+    /// dropped.
+    ///
+    /// After a spike handled via [`OnExhausted::CreateBurst`] the reaper
+    /// spawned via [`Pool::spawn_reaper()`] would otherwise create all of
+    /// them at once, causing a synchronized wave of connection attempts
+    /// against a backend which is likely still under pressure.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool::spawn_reaper()`]: super::Pool::spawn_reaper
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub backfill_interval: Option<Duration>,
+
+    /// Start the [`Pool`] as a warm standby which rejects all attempts to
+    /// get an [`Object`] with a [`PoolError::Standby`] until
+    /// [`Pool::activate()`] is called.
+    ///
+    /// The reaper spawned via [`Pool::spawn_reaper()`] keeps the
+    /// [`PoolConfig::min_idle`] [`Object`]s ready meanwhile and validates
+    /// them on every run by recycling them, replacing the ones which fail.
+    /// This way a standby replica can take over traffic instantly on
+    /// failover.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`Pool::activate()`]: super::Pool::activate
+    /// [`Pool::spawn_reaper()`]: super::Pool::spawn_reaper
+    /// [`PoolError::Standby`]: super::PoolError::Standby
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub standby: bool,
+
+    /// Shrink the [`Pool`] to the limit reported via
+    /// [`Manager::size_limit()`] if its [`PoolConfig::max_size`] exceeds it.
+    ///
+    /// Otherwise the [`Pool`] keeps its size and only reports the excess
+    /// via [`PoolBuilder::on_backend_limit()`].
+    ///
+    /// [`Manager::size_limit()`]: super::Manager::size_limit
+    /// [`Pool`]: super::Pool
+    /// [`PoolBuilder::on_backend_limit()`]: super::PoolBuilder::on_backend_limit
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub clamp_to_backend_limit: bool,
+
+    /// Fail fast for a while once too many attempts to get an [`Object`]
+    /// timed out or too many [`Object`]s failed fatally to be recycled.
+    ///
+    /// This prevents the storm of retries and connection attempts which
+    /// usually follows a short outage of the backend.
+    ///
+    /// [`Object`]: super::Object
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub timeout_cooldown: Option<TimeoutCooldown>,
+
+    /// Retry failed [`Manager::create()`] calls inside the [`Pool`] before
+    /// surfacing the error to the caller.
+    ///
+    /// This requires a [`Runtime`] for sleeping between the attempts. The
+    /// [`Timeouts::create`] covers all attempts together.
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    /// [`Pool`]: super::Pool
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub create_retry: Option<CreateRetry>,
+
+    /// Hard cap on the number of [`Manager::create()`] calls, e.g. for
+    /// backends billing every connection establishment.
+    ///
+    /// See [`CreateLimit`] for details.
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub create_limit: Option<CreateLimit>,
+
+    /// Compare recycling every [`Object`] on checkout with trusting
+    /// [`Object`]s returned recently for a sample of the checkouts.
+    ///
+    /// See [`RecycleExperiment`] for details.
+    ///
+    /// [`Object`]: super::Object
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recycle_experiment: Option<RecycleExperiment>,
+
+    /// Park [`Object`]s failing [`Manager::recycle()`] for a while and
+    /// retry them later instead of dropping them right away.
+    ///
+    /// See [`Quarantine`] for details.
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    /// [`Object`]: super::Object
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub quarantine: Option<Quarantine>,
 }
 
 impl PoolConfig {
@@ -26,8 +236,39 @@ impl PoolConfig {
         Self {
             max_size,
             timeouts: Timeouts::default(),
+            cooperative_yield: false,
+            on_exhausted: OnExhausted::default(),
+            max_waiting: None,
+            queue_mode: QueueMode::default(),
+            wait_timeout_scaling: WaitTimeoutScaling::default(),
+            max_error_rate: None,
+            max_concurrent_creates: None,
+            thread_affinity: false,
+            timeout_cooldown: None,
+            create_retry: None,
+            create_limit: None,
+            recycle_experiment: None,
+            quarantine: None,
+            max_lifetime: None,
+            idle_timeout: None,
+            min_idle: 0,
+            backfill_interval: None,
+            standby: false,
+            clamp_to_backend_limit: false,
         }
     }
+
+    /// Indicates whether an idle object with the given [`Metrics`] exceeds
+    /// the [`PoolConfig::max_lifetime`] or the [`PoolConfig::idle_timeout`].
+    ///
+    /// [`Metrics`]: super::Metrics
+    pub(crate) fn is_expired(&self, metrics: &super::Metrics) -> bool {
+        let exceeds = |limit: Option<Duration>, value: Duration| match limit {
+            Some(limit) => value > limit,
+            None => false,
+        };
+        exceeds(self.max_lifetime, metrics.age()) || exceeds(self.idle_timeout, metrics.idle())
+    }
 }
 
 impl Default for PoolConfig {
@@ -38,6 +279,338 @@ impl Default for PoolConfig {
     }
 }
 
+/// Behavior of a [`Pool`] when all of its slots are in use and the
+/// [`PoolConfig::max_size`] has been reached.
+///
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum OnExhausted {
+    /// Wait for a slot to become available. This honors the configured
+    /// [`Timeouts::wait`].
+    Wait,
+
+    /// Fail immediately with a [`PoolError::Exhausted`].
+    ///
+    /// [`PoolError::Exhausted`]: super::PoolError::Exhausted
+    Error,
+
+    /// Create a new [`Object`] exceeding the [`PoolConfig::max_size`]. Such
+    /// burst [`Object`]s are dropped instead of being returned to the
+    /// [`Pool`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    CreateBurst,
+}
+
+impl Default for OnExhausted {
+    /// Returns [`OnExhausted::Wait`].
+    fn default() -> Self {
+        Self::Wait
+    }
+}
+
+/// Order in which a [`Pool`] hands out its idle [`Object`]s.
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum QueueMode {
+    /// Hand out the [`Object`] which has been idle for the longest time
+    /// first. This spreads the load evenly across all [`Object`]s.
+    ///
+    /// [`Object`]: super::Object
+    Fifo,
+
+    /// Hand out the most recently returned [`Object`] first. This keeps a
+    /// few hot [`Object`]s in use and lets the others expire via the
+    /// [`PoolConfig::idle_timeout`].
+    ///
+    /// [`Object`]: super::Object
+    Lifo,
+}
+
+impl Default for QueueMode {
+    /// Returns [`QueueMode::Fifo`].
+    fn default() -> Self {
+        Self::Fifo
+    }
+}
+
+/// Policy for adapting the [`Timeouts::wait`] to the number of tasks already
+/// waiting for an [`Object`] when a task starts waiting.
+///
+/// This makes a [`Pool`] fail faster under overload without every caller
+/// having to compute its own adaptive timeout. The scaled timeout is never
+/// longer than the configured one.
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum WaitTimeoutScaling {
+    /// Always use the configured [`Timeouts::wait`].
+    Fixed,
+
+    /// Shorten the [`Timeouts::wait`] by `step` for every task already
+    /// waiting, but never below `min`.
+    Linear {
+        /// Reduction per waiting task.
+        step: Duration,
+
+        /// Lower bound of the scaled timeout.
+        min: Duration,
+    },
+
+    /// Use `timeout` instead of the [`Timeouts::wait`] as soon as at least
+    /// `depth` tasks are already waiting.
+    Step {
+        /// Number of waiting tasks from which on `timeout` is used.
+        depth: usize,
+
+        /// Timeout used once `depth` has been reached.
+        timeout: Duration,
+    },
+}
+
+impl WaitTimeoutScaling {
+    /// Returns the effective wait timeout for the given `timeout` with
+    /// `depth` tasks already waiting.
+    pub(crate) fn apply(self, timeout: Duration, depth: usize) -> Duration {
+        match self {
+            Self::Fixed => timeout,
+            Self::Linear { step, min } => {
+                let reduction = step
+                    .checked_mul(u32::try_from(depth).unwrap_or(u32::MAX))
+                    .unwrap_or(Duration::MAX);
+                timeout.saturating_sub(reduction).max(min).min(timeout)
+            }
+            Self::Step {
+                depth: d,
+                timeout: t,
+            } if depth >= d => t.min(timeout),
+            Self::Step { .. } => timeout,
+        }
+    }
+}
+
+impl Default for WaitTimeoutScaling {
+    /// Returns [`WaitTimeoutScaling::Fixed`].
+    fn default() -> Self {
+        Self::Fixed
+    }
+}
+
+/// Limit of the error rate of an [`Object`] after which it is discarded
+/// instead of being returned to its [`Pool`].
+///
+/// See [`Metrics::error_rate()`] for how the rate is calculated.
+///
+/// [`Metrics::error_rate()`]: super::Metrics::error_rate
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct ErrorRateLimit {
+    /// Maximum ratio of reported errors to uses, e.g. `0.5` for 50%.
+    pub max_rate: f64,
+
+    /// Minimum number of uses before the limit is checked at all, so a
+    /// single error of a fresh [`Object`] doesn't discard it right away.
+    ///
+    /// [`Object`]: super::Object
+    pub min_uses: usize,
+}
+
+impl ErrorRateLimit {
+    /// Indicates whether the given [`Metrics`] exceed this limit.
+    ///
+    /// [`Metrics`]: super::Metrics
+    pub(crate) fn is_exceeded(&self, metrics: &super::Metrics) -> bool {
+        metrics.use_count() >= self.min_uses && metrics.error_rate() > self.max_rate
+    }
+}
+
+/// Condition under which a [`Pool`] enters a cooldown in which all attempts
+/// to get an [`Object`] fail right away with a [`PoolError::CoolingDown`].
+///
+/// Attempts are counted in consecutive windows of the given length. If the
+/// ratio of timed out attempts in the current window exceeds the limit, the
+/// cooldown starts and no new [`Object`]s are created until it is over.
+/// Recycle errors classified as [`RecycleErrorKind::Fatal`] are counted as
+/// timed out attempts too.
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+/// [`PoolError::CoolingDown`]: super::PoolError::CoolingDown
+/// [`RecycleErrorKind::Fatal`]: super::RecycleErrorKind::Fatal
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct TimeoutCooldown {
+    /// Length of the windows in which attempts are counted.
+    pub window: Duration,
+
+    /// Maximum ratio of timed out attempts to all attempts, e.g. `0.5` for
+    /// 50%.
+    pub max_rate: f64,
+
+    /// Minimum number of timed out attempts before the limit is checked at
+    /// all, so a single timeout of a rarely used [`Pool`] doesn't trigger
+    /// the cooldown.
+    ///
+    /// [`Pool`]: super::Pool
+    pub min_timeouts: usize,
+
+    /// Duration of the cooldown.
+    pub cooldown: Duration,
+}
+
+impl TimeoutCooldown {
+    /// Indicates whether the given numbers of `attempts` and `timeouts`
+    /// exceed this limit.
+    pub(crate) fn is_exceeded(&self, attempts: usize, timeouts: usize) -> bool {
+        timeouts >= self.min_timeouts && timeouts as f64 / attempts as f64 > self.max_rate
+    }
+}
+
+/// Policy for parking [`Object`]s which failed to be recycled instead of
+/// dropping them right away.
+///
+/// This avoids dropping and recreating expensive sessions during brief
+/// network partitions. Quarantined [`Object`]s keep counting towards the
+/// [`Status::size`] of the [`Pool`], so they are only parked if there is a
+/// free slot for them. Once the `delay` is over, they are recycled again
+/// before any idle [`Object`]. If that fails more than `max_retries` times
+/// in a row, or nobody retries them for another `max_retries` delays, they
+/// are dropped for good.
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+/// [`Status::size`]: crate::Status::size
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct Quarantine {
+    /// Time a quarantined [`Object`] waits before being retried.
+    ///
+    /// [`Object`]: super::Object
+    pub delay: Duration,
+
+    /// Maximum number of times an [`Object`] is quarantined in a row.
+    ///
+    /// [`Object`]: super::Object
+    pub max_retries: usize,
+}
+
+/// Limit of [`Manager::create()`] calls of a [`Pool`].
+///
+/// Once `max` calls have been made, every further attempt to create an
+/// [`Object`] fails right away with a [`PoolError::CreateLimitReached`]
+/// instead of calling the [`Manager`]. Retries configured via
+/// [`PoolConfig::create_retry`] count as well. Without a `per` window the
+/// limit applies to the whole lifetime of the [`Pool`], otherwise the count
+/// resets once the window starting with the first counted call is over.
+///
+/// [`Manager`]: super::Manager
+/// [`Manager::create()`]: super::Manager::create
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+/// [`PoolError::CreateLimitReached`]: super::PoolError::CreateLimitReached
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct CreateLimit {
+    /// Maximum number of [`Manager::create()`] calls.
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    pub max: usize,
+
+    /// Length of the window in which calls are counted, e.g. one hour for
+    /// an hourly limit. [`None`] means the lifetime of the [`Pool`].
+    ///
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub per: Option<Duration>,
+}
+
+/// Policy for retrying failed [`Manager::create()`] calls with an exponential
+/// backoff.
+///
+/// The backoff starts at `initial_backoff` and doubles with every failed
+/// attempt up to `max_backoff`. With `jitter` enabled, a random duration of up
+/// to half of the backoff is subtracted, so tasks failing at the same time
+/// don't all retry at the same time. The jitter is drawn from the
+/// [`RandomSource`] of the [`Pool`]. If the [`Manager::backoff_hint()`] of
+/// the error is longer, it is used instead.
+///
+/// [`Manager::backoff_hint()`]: super::Manager::backoff_hint
+/// [`Manager::create()`]: super::Manager::create
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct CreateRetry {
+    /// Maximum number of attempts including the first one.
+    pub max_attempts: usize,
+
+    /// Backoff after the first failed attempt.
+    pub initial_backoff: Duration,
+
+    /// Maximum backoff between two attempts.
+    pub max_backoff: Duration,
+
+    /// Randomize the backoff.
+    pub jitter: bool,
+}
+
+impl CreateRetry {
+    /// Returns the backoff after the given number of `failed` attempts
+    /// drawing the jitter from the given `random` source.
+    pub(crate) fn backoff(&self, failed: usize, random: &dyn RandomSource) -> Duration {
+        let factor = u32::try_from(failed.saturating_sub(1))
+            .ok()
+            .and_then(|shift| 1_u32.checked_shl(shift))
+            .unwrap_or(u32::MAX);
+        let backoff = self
+            .initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+        if !self.jitter {
+            return backoff;
+        }
+        let random = random.next_u64();
+        let max_jitter = u64::try_from(backoff.as_nanos() / 2).unwrap_or(u64::MAX);
+        backoff - Duration::from_nanos(random.checked_rem(max_jitter).unwrap_or(0))
+    }
+}
+
+/// Experiment comparing two recycling strategies on a sample of the
+/// checkouts of idle [`Object`]s.
+///
+/// The [`Pool`] always recycles [`Object`]s on checkout. For sampled
+/// checkouts it additionally records whether the [`Object`] has been idle
+/// for less than the `freshness` window and thus would have been handed out
+/// without recycling when trusting that window. A fresh [`Object`] failing to
+/// be recycled counts as a disagreement of both strategies.
+///
+/// A low disagreement rate reported by [`Pool::recycle_experiment()`]
+/// indicates that skipping the recycling of fresh [`Object`]s is safe.
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+/// [`Pool::recycle_experiment()`]: super::Pool::recycle_experiment
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct RecycleExperiment {
+    /// Fraction of the checkouts to sample, e.g. `0.1` for 10%.
+    pub sample_rate: f64,
+
+    /// Maximum idle time of an [`Object`] to be considered fresh.
+    ///
+    /// [`Object`]: super::Object
+    pub freshness: Duration,
+}
+
 /// Timeouts when getting [`Object`]s from a [`Pool`].
 ///
 /// [`Object`]: super::Object
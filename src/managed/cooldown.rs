@@ -0,0 +1,93 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::lock::Mutex;
+
+use super::TimeoutCooldown;
+
+/// Tracker of failed attempts entering a cooldown once the configured
+/// [`TimeoutCooldown`] limit is exceeded.
+#[derive(Debug, Default)]
+pub(crate) struct Cooldown {
+    state: Mutex<State>,
+    /// Set while the `state` might contain a cooldown, so checking for one
+    /// on every attempt doesn't need to take the lock.
+    active: AtomicBool,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Start of the current window or [`None`] if nothing has been recorded
+    /// yet.
+    window_start: Option<Instant>,
+    attempts: usize,
+    failures: usize,
+    /// End of the current cooldown, if any.
+    until: Option<Instant>,
+    /// Set if the current cooldown is too long to be represented and thus
+    /// never ends.
+    endless: bool,
+}
+
+impl Cooldown {
+    /// Returns the remaining time of the current cooldown or [`None`] if
+    /// there is none.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        if !self.active.load(Ordering::Relaxed) {
+            return None;
+        }
+        let state = self.state.lock();
+        if state.endless {
+            return Some(Duration::MAX);
+        }
+        let remaining = state
+            .until
+            .map(|until| until.saturating_duration_since(Instant::now()))
+            .filter(|remaining| *remaining > Duration::ZERO);
+        if remaining.is_none() {
+            self.active.store(false, Ordering::Relaxed);
+        }
+        remaining
+    }
+
+    /// Records a finished attempt and starts a cooldown if the given `limit`
+    /// is exceeded. Timed out attempts and fatal recycle errors are recorded
+    /// as `failed`.
+    pub(crate) fn record(&self, limit: &TimeoutCooldown, failed: bool) {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        // Attempts which were already running when the cooldown started
+        // must not extend it.
+        if state.endless {
+            return;
+        }
+        if let Some(until) = state.until {
+            if now < until {
+                return;
+            }
+        }
+        let expired = match state.window_start {
+            Some(start) => now.saturating_duration_since(start) >= limit.window,
+            None => true,
+        };
+        if expired {
+            state.window_start = Some(now);
+            state.attempts = 0;
+            state.failures = 0;
+        }
+        state.attempts += 1;
+        if failed {
+            state.failures += 1;
+        }
+        if limit.is_exceeded(state.attempts, state.failures) {
+            state.window_start = None;
+            match now.checked_add(limit.cooldown) {
+                Some(until) => state.until = Some(until),
+                None => state.endless = true,
+            }
+            self.active.store(true, Ordering::Relaxed);
+        }
+    }
+}
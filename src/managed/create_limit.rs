@@ -0,0 +1,53 @@
+use std::time::{Duration, Instant};
+
+use crate::lock::Mutex;
+
+use super::CreateLimit;
+
+/// Counter of [`Manager::create()`] calls enforcing the configured
+/// [`CreateLimit`].
+///
+/// [`Manager::create()`]: super::Manager::create
+#[derive(Debug, Default)]
+pub(crate) struct CreateBudget {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Start of the current window or [`None`] if nothing has been counted
+    /// yet.
+    window_start: Option<Instant>,
+    created: usize,
+}
+
+impl CreateBudget {
+    /// Counts another creation if the given `limit` allows it. Otherwise
+    /// returns the time until the current window is over or [`None`] if the
+    /// limit never resets.
+    pub(crate) fn take(&self, limit: &CreateLimit) -> Result<(), Option<Duration>> {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        if let (Some(per), Some(start)) = (limit.per, state.window_start) {
+            if now.saturating_duration_since(start) >= per {
+                state.window_start = None;
+                state.created = 0;
+            }
+        }
+        if state.created >= limit.max {
+            let retry_after = match (limit.per, state.window_start) {
+                // A window too long to be represented never resets.
+                (Some(per), Some(start)) => start
+                    .checked_add(per)
+                    .map(|end| end.saturating_duration_since(now)),
+                _ => None,
+            };
+            return Err(retry_after);
+        }
+        if state.window_start.is_none() {
+            state.window_start = Some(now);
+        }
+        state.created += 1;
+        Ok(())
+    }
+}
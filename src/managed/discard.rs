@@ -0,0 +1,61 @@
+/// Reason why an [`Object`] has been discarded by its [`Pool`].
+///
+/// See [`PoolBuilder::on_discard()`] for details.
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::on_discard()`]: super::PoolBuilder::on_discard
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiscardReason {
+    /// Idle [`Object`] has been evicted, e.g. via [`Pool::evict_idle()`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool::evict_idle()`]: super::Pool::evict_idle
+    Evicted,
+
+    /// Idle [`Object`] exceeded the [`PoolConfig::max_lifetime`] or the
+    /// [`PoolConfig::idle_timeout`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`PoolConfig::idle_timeout`]: super::PoolConfig::idle_timeout
+    /// [`PoolConfig::max_lifetime`]: super::PoolConfig::max_lifetime
+    Expired,
+
+    /// Idle [`Object`] has been removed as the [`Pool`] has been closed.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    Closed,
+
+    /// [`Manager::recycle()`] failed for this [`Object`].
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    /// [`Object`]: super::Object
+    RecycleFailed,
+
+    /// [`Object`] has been marked as broken while it was checked out, e.g.
+    /// by exceeding the [`PoolConfig::max_error_rate`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`PoolConfig::max_error_rate`]: super::PoolConfig::max_error_rate
+    Broken,
+
+    /// [`Object`] has been rejected by the predicate passed to
+    /// [`Pool::retain()`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool::retain()`]: super::Pool::retain
+    Filtered,
+
+    /// Temporary object of [`Pool::self_test()`] has been recycled
+    /// successfully and isn't needed anymore.
+    ///
+    /// [`Pool::self_test()`]: super::Pool::self_test
+    SelfTest,
+}
+
+/// Callback receiving discarded objects as configured via
+/// [`PoolBuilder::on_discard()`].
+///
+/// [`PoolBuilder::on_discard()`]: super::PoolBuilder::on_discard
+pub(crate) type OnDiscard<T> = Box<dyn Fn(T, DiscardReason) + Send + Sync>;
@@ -0,0 +1,125 @@
+//! Engine of a [`Pool`] for building alternate frontends.
+//!
+//! The [`PoolCore`] trait exposes the slot accounting and waiter handling of
+//! a [`Pool`] without the [`Pool::get()`] based frontend on top of it.
+//! Downstream crates can use it to provide different interfaces (e.g.
+//! blocking, actor-based or FFI facades) while sharing the capacity limits,
+//! timeouts, hooks and statistics of the [`Pool`]:
+//!
+//! ```rust
+//! use deadpool::managed::{engine::PoolCore, Manager, NoopManager, Object, Pool, PoolError};
+//!
+//! fn checkout<C: PoolCore>(
+//!     core: &C,
+//!     rt: &tokio::runtime::Runtime,
+//! ) -> Result<Object<C::Manager>, PoolError<<C::Manager as Manager>::Error>> {
+//!     rt.block_on(async { core.acquire(&core.timeouts()).await?.get().await })
+//! }
+//!
+//! let rt = tokio::runtime::Builder::new_current_thread()
+//!     .build()
+//!     .unwrap();
+//! let pool: Pool<NoopManager> = Pool::builder(NoopManager::new()).build().unwrap();
+//! let obj = checkout(&pool, &rt).unwrap();
+//! assert_eq!(pool.status().size, 1);
+//! # drop(obj);
+//! ```
+//!
+//! Returning an [`Object`] to the [`Pool`] works by simply dropping it.
+//!
+//! [`Object`]: super::Object
+//! [`Pool`]: super::Pool
+//! [`Pool::get()`]: super::Pool::get
+
+use std::marker::PhantomData;
+
+use async_trait::async_trait;
+
+use crate::Status;
+
+use super::{Manager, Object, Permit, Pool, PoolError, ShutdownReport, Timeouts};
+
+/// Slot accounting and waiter handling of a [`Pool`].
+///
+/// See the [module documentation](self) for details.
+///
+/// [`Pool`]: super::Pool
+#[async_trait]
+pub trait PoolCore: Send + Sync {
+    /// [`Manager`] of the [`Object`]s.
+    ///
+    /// [`Object`]: super::Object
+    type Manager: Manager;
+
+    /// Waits for a free slot honoring the given `timeouts`.
+    ///
+    /// The returned [`Permit`] is exchanged for an [`Object`] via
+    /// [`Permit::get()`] which applies the [`Timeouts::create`] and
+    /// [`Timeouts::recycle`] of the `timeouts` passed here.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    ///
+    /// [`Object`]: super::Object
+    async fn acquire(
+        &self,
+        timeouts: &Timeouts,
+    ) -> Result<Permit<Self::Manager>, PoolError<<Self::Manager as Manager>::Error>>;
+
+    /// Returns the default [`Timeouts`].
+    fn timeouts(&self) -> Timeouts;
+
+    /// Returns the current [`Status`].
+    fn status(&self) -> Status;
+
+    /// Closes the [`Pool`], see [`Pool::close()`] for details.
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Pool::close()`]: super::Pool::close
+    fn close(&self) -> ShutdownReport;
+
+    /// Indicates whether the [`Pool`] has been closed.
+    ///
+    /// [`Pool`]: super::Pool
+    fn is_closed(&self) -> bool;
+}
+
+#[async_trait]
+impl<M, W> PoolCore for Pool<M, W>
+where
+    M: Manager,
+    M::Type: Send,
+    M::Error: Send,
+    W: From<Object<M>>,
+{
+    type Manager = M;
+
+    async fn acquire(&self, timeouts: &Timeouts) -> Result<Permit<M>, PoolError<M::Error>> {
+        let (obj, info) = self
+            .intercept(self.acquire_checked(timeouts, None, None, false))
+            .await?;
+        Ok(Permit {
+            obj,
+            info,
+            timeouts: *timeouts,
+            _wrapper: PhantomData,
+        })
+    }
+
+    fn timeouts(&self) -> Timeouts {
+        Pool::timeouts(self)
+    }
+
+    fn status(&self) -> Status {
+        Pool::status(self)
+    }
+
+    fn close(&self) -> ShutdownReport {
+        Pool::close(self)
+    }
+
+    fn is_closed(&self) -> bool {
+        Pool::is_closed(self)
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use super::hooks::HookError;
 
@@ -38,6 +38,23 @@ impl<E: std::error::Error + 'static> std::error::Error for RecycleError<E> {
     }
 }
 
+/// Classification of a [`RecycleError`] returned by
+/// [`Manager::classify_recycle_error()`].
+///
+/// [`Manager::classify_recycle_error()`]: super::Manager::classify_recycle_error
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecycleErrorKind {
+    /// The error is likely caused by a momentary problem (e.g. a network
+    /// blip). Recycling is retried once before the object is discarded.
+    Transient,
+
+    /// The object is broken and is discarded right away. The error counts
+    /// as a failed attempt towards the [`PoolConfig::timeout_cooldown`].
+    ///
+    /// [`PoolConfig::timeout_cooldown`]: super::PoolConfig::timeout_cooldown
+    Fatal,
+}
+
 /// Possible steps causing the timeout in an error returned by [`Pool::get()`]
 /// method.
 ///
@@ -70,11 +87,86 @@ pub enum PoolError<E> {
     /// [`Pool`]: super::Pool
     Closed,
 
+    /// All slots of the [`Pool`] are in use and it is configured to fail
+    /// instead of waiting.
+    ///
+    /// See [`OnExhausted::Error`] for details.
+    ///
+    /// [`OnExhausted::Error`]: super::OnExhausted::Error
+    /// [`Pool`]: super::Pool
+    Exhausted {
+        /// Estimated time until a slot becomes available again based on how
+        /// often [`Object`]s have recently been returned to the [`Pool`].
+        ///
+        /// This is [`None`] if no [`Object`]s have been returned yet. It is
+        /// meant to be used for things like HTTP `Retry-After` headers.
+        ///
+        /// [`Object`]: super::Object
+        /// [`Pool`]: super::Pool
+        retry_after: Option<Duration>,
+    },
+
+    /// The [`PoolConfig::create_limit`] has been reached, so no new
+    /// [`Object`] can be created.
+    ///
+    /// [`Object`]: super::Object
+    /// [`PoolConfig::create_limit`]: super::PoolConfig::create_limit
+    CreateLimitReached {
+        /// Remaining time until the limit resets or [`None`] if it never
+        /// does.
+        retry_after: Option<Duration>,
+    },
+
+    /// Too many tasks are already waiting for a slot of the [`Pool`].
+    ///
+    /// See [`PoolConfig::max_waiting`] for details.
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`PoolConfig::max_waiting`]: super::PoolConfig::max_waiting
+    QueueFull,
+
+    /// The [`Pool`] is a warm standby which hasn't been activated yet.
+    ///
+    /// See [`PoolConfig::standby`] for details.
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`PoolConfig::standby`]: super::PoolConfig::standby
+    Standby,
+
+    /// Too many attempts to get an [`Object`] timed out recently and the
+    /// [`Pool`] fails fast until the cooldown is over.
+    ///
+    /// See [`PoolConfig::timeout_cooldown`] for details.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    /// [`PoolConfig::timeout_cooldown`]: super::PoolConfig::timeout_cooldown
+    CoolingDown {
+        /// Remaining time of the cooldown.
+        retry_after: Duration,
+    },
+
+    /// Retrieving an [`Object`] has been cancelled.
+    ///
+    /// See [`Pool::get_cancellable()`] for details.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool::get_cancellable()`]: super::Pool::get_cancellable
+    Cancelled,
+
     /// No [`Runtime`] was specified.
     ///
     /// [`Runtime`]: crate::Runtime
     NoRuntimeSpecified,
 
+    /// [`Manager::create()`] panicked. Contains the panic message.
+    ///
+    /// The [`Pool`] stays usable, so retrying is fine.
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    /// [`Pool`]: super::Pool
+    Panic(String),
+
     /// [`PostCreate`] hook reported an error.
     ///
     /// [`PostCreate`]: super::hooks::PostCreate
@@ -84,6 +176,11 @@ pub enum PoolError<E> {
     ///
     /// [`PostRecycle`]: super::hooks::PostRecycle
     PostRecycleHook(HookError<E>),
+
+    /// [`PostIdle`] hook reported an error.
+    ///
+    /// [`PostIdle`]: super::hooks::PostIdle
+    PostIdleHook(HookError<E>),
 }
 
 impl<E> From<E> for PoolError<E> {
@@ -105,9 +202,19 @@ impl<E: fmt::Display> fmt::Display for PoolError<E> {
             },
             Self::Backend(e) => write!(f, "Error occurred while creating a new object: {}", e),
             Self::Closed => write!(f, "Pool has been closed"),
+            Self::Exhausted { .. } => write!(f, "Pool is exhausted"),
+            Self::QueueFull => write!(f, "Too many tasks are waiting for an object"),
+            Self::Standby => write!(f, "Pool is in standby and hasn't been activated"),
+            Self::CreateLimitReached { .. } => write!(f, "Limit of created objects reached"),
+            Self::CoolingDown { .. } => {
+                write!(f, "Pool is cooling down after too many timeouts")
+            }
+            Self::Cancelled => write!(f, "Retrieving an object has been cancelled"),
             Self::NoRuntimeSpecified => write!(f, "No runtime specified"),
+            Self::Panic(msg) => write!(f, "Panic occurred while creating a new object: {}", msg),
             Self::PostCreateHook(msg) => writeln!(f, "`post_create` hook failed: {}", msg),
             Self::PostRecycleHook(msg) => writeln!(f, "`post_recycle` hook failed: {}", msg),
+            Self::PostIdleHook(msg) => writeln!(f, "`post_idle` hook failed: {}", msg),
         }
     }
 }
@@ -115,9 +222,18 @@ impl<E: fmt::Display> fmt::Display for PoolError<E> {
 impl<E: std::error::Error + 'static> std::error::Error for PoolError<E> {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Timeout(_) | Self::Closed | Self::NoRuntimeSpecified => None,
+            Self::Timeout(_)
+            | Self::Closed
+            | Self::Exhausted { .. }
+            | Self::QueueFull
+            | Self::Standby
+            | Self::CreateLimitReached { .. }
+            | Self::CoolingDown { .. }
+            | Self::Cancelled
+            | Self::NoRuntimeSpecified
+            | Self::Panic(_) => None,
             Self::Backend(e) => Some(e),
-            Self::PostCreateHook(e) | Self::PostRecycleHook(e) => Some(e),
+            Self::PostCreateHook(e) | Self::PostRecycleHook(e) | Self::PostIdleHook(e) => Some(e),
         }
     }
 }
@@ -0,0 +1,91 @@
+//! Strategies deciding which idle objects to drop first.
+//!
+//! Whenever a [`Pool`] has to get rid of some of its idle objects, e.g. when
+//! calling [`Pool::evict_idle()`], the configured [`EvictionPolicy`] decides
+//! which ones are dropped. It can be set via
+//! [`PoolBuilder::eviction_policy()`] and defaults to [`LeastRecentlyUsed`].
+//!
+//! Custom policies can be implemented as closures comparing the [`Metrics`]
+//! of two objects.
+//!
+//! [`Pool`]: super::Pool
+//! [`Pool::evict_idle()`]: super::Pool::evict_idle
+//! [`PoolBuilder::eviction_policy()`]: super::PoolBuilder::eviction_policy
+
+use std::{cmp::Ordering, fmt};
+
+use super::Metrics;
+
+/// Strategy deciding which idle objects to drop first.
+pub trait EvictionPolicy: Sync + Send {
+    /// Compares two idle objects by their [`Metrics`].
+    ///
+    /// Returns [`Ordering::Less`] if the object described by `a` should be
+    /// dropped before the one described by `b`.
+    fn compare(&self, a: &Metrics, b: &Metrics) -> Ordering;
+}
+
+impl fmt::Debug for dyn EvictionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:p}", self)
+    }
+}
+
+impl<F> EvictionPolicy for F
+where
+    F: Fn(&Metrics, &Metrics) -> Ordering + Sync + Send,
+{
+    fn compare(&self, a: &Metrics, b: &Metrics) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// [`EvictionPolicy`] dropping the objects created first.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Oldest;
+
+impl EvictionPolicy for Oldest {
+    fn compare(&self, a: &Metrics, b: &Metrics) -> Ordering {
+        a.created.cmp(&b.created)
+    }
+}
+
+/// [`EvictionPolicy`] dropping the objects which haven't been used for the
+/// longest time.
+///
+/// This is the default [`EvictionPolicy`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LeastRecentlyUsed;
+
+impl EvictionPolicy for LeastRecentlyUsed {
+    fn compare(&self, a: &Metrics, b: &Metrics) -> Ordering {
+        let a = a.recycled.unwrap_or(a.created);
+        let b = b.recycled.unwrap_or(b.created);
+        a.cmp(&b)
+    }
+}
+
+/// [`EvictionPolicy`] dropping the objects which were the cheapest to
+/// create first, so expensive ones survive longer under the same idle
+/// policy.
+///
+/// See [`Metrics::create_duration`] for how the cost is measured.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CheapestToRecreate;
+
+impl EvictionPolicy for CheapestToRecreate {
+    fn compare(&self, a: &Metrics, b: &Metrics) -> Ordering {
+        a.create_duration.cmp(&b.create_duration)
+    }
+}
+
+/// [`EvictionPolicy`] dropping the objects which have been recycled most
+/// often.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MostUsed;
+
+impl EvictionPolicy for MostUsed {
+    fn compare(&self, a: &Metrics, b: &Metrics) -> Ordering {
+        b.recycle_count.cmp(&a.recycle_count)
+    }
+}
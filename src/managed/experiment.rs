@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::RecycleExperiment;
+
+/// Results of the [`PoolConfig::recycle_experiment`] as returned by
+/// [`Pool::recycle_experiment()`].
+///
+/// [`Pool::recycle_experiment()`]: super::Pool::recycle_experiment
+/// [`PoolConfig::recycle_experiment`]: super::PoolConfig::recycle_experiment
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RecycleExperimentReport {
+    /// Number of sampled checkouts of idle [`Object`]s.
+    ///
+    /// [`Object`]: super::Object
+    pub sampled: usize,
+
+    /// Number of sampled checkouts whose [`Object`] was returned within the
+    /// [`RecycleExperiment::freshness`] window and wouldn't have been
+    /// verified when trusting it.
+    ///
+    /// [`Object`]: super::Object
+    pub fresh: usize,
+
+    /// Number of fresh sampled checkouts whose [`Object`] failed to be
+    /// recycled nonetheless. Trusting the freshness window would have handed
+    /// out a broken [`Object`] in these cases.
+    ///
+    /// [`Object`]: super::Object
+    pub disagreements: usize,
+}
+
+impl RecycleExperimentReport {
+    /// Returns the ratio of [`RecycleExperimentReport::disagreements`] to
+    /// [`RecycleExperimentReport::fresh`] checkouts or `0.0` if there were no
+    /// fresh checkouts yet.
+    #[must_use]
+    pub fn disagreement_rate(&self) -> f64 {
+        if self.fresh == 0 {
+            return 0.0;
+        }
+        self.disagreements as f64 / self.fresh as f64
+    }
+}
+
+/// Counters backing the [`RecycleExperimentReport`].
+#[derive(Debug, Default)]
+pub(crate) struct Experiment {
+    checkouts: AtomicUsize,
+    sampled: AtomicUsize,
+    fresh: AtomicUsize,
+    disagreements: AtomicUsize,
+}
+
+impl Experiment {
+    /// Counts a checkout of an idle object and indicates whether it is part
+    /// of the sample.
+    ///
+    /// Checkouts are sampled evenly without any randomness, e.g. every tenth
+    /// one for a [`RecycleExperiment::sample_rate`] of `0.1`.
+    pub(crate) fn sample(&self, experiment: &RecycleExperiment) -> bool {
+        let rate = experiment.sample_rate.clamp(0.0, 1.0);
+        let n = self.checkouts.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * rate).floor() > (n * rate).floor()
+    }
+
+    /// Records the outcome of a sampled checkout.
+    pub(crate) fn record(&self, fresh: bool, recycled: bool) {
+        let _ = self.sampled.fetch_add(1, Ordering::Relaxed);
+        if fresh {
+            let _ = self.fresh.fetch_add(1, Ordering::Relaxed);
+            if !recycled {
+                let _ = self.disagreements.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub(crate) fn report(&self) -> RecycleExperimentReport {
+        RecycleExperimentReport {
+            sampled: self.sampled.load(Ordering::Relaxed),
+            fresh: self.fresh.load(Ordering::Relaxed),
+            disagreements: self.disagreements.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Composition of two [`Pool`]s where one serves as fallback for the other.
+//!
+//! A [`FallbackPool`] tries to get an [`Object`] from a primary [`Pool`]
+//! first and transparently falls back to a secondary [`Pool`] if that fails.
+//! This is useful for serving requests in a degraded mode, e.g. by using a
+//! different host, different credentials or an in-memory stub.
+//!
+//! The primary [`Pool`] should usually be configured with a short wait
+//! timeout. [`Pool::with_timeouts()`] makes this easy without affecting other
+//! users of the same [`Pool`].
+//!
+//! [`Pool::with_timeouts()`]: super::Pool::with_timeouts
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use super::{Manager, Object, Pool, PoolError};
+
+/// Combination of a primary and a secondary [`Pool`].
+///
+/// See the [module documentation](self) for details.
+pub struct FallbackPool<P, S, PW = Object<P>, SW = Object<S>>
+where
+    P: Manager,
+    S: Manager,
+    PW: From<Object<P>>,
+    SW: From<Object<S>>,
+{
+    primary: Pool<P, PW>,
+    secondary: Pool<S, SW>,
+}
+
+// Implemented manually to avoid unnecessary trait bounds on the wrapper
+// type parameters.
+impl<P, S, PW, SW> fmt::Debug for FallbackPool<P, S, PW, SW>
+where
+    P: fmt::Debug + Manager,
+    P::Type: fmt::Debug,
+    S: fmt::Debug + Manager,
+    S::Type: fmt::Debug,
+    PW: From<Object<P>>,
+    SW: From<Object<S>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FallbackPool")
+            .field("primary", &self.primary)
+            .field("secondary", &self.secondary)
+            .finish()
+    }
+}
+
+impl<P, S, PW, SW> Clone for FallbackPool<P, S, PW, SW>
+where
+    P: Manager,
+    S: Manager,
+    PW: From<Object<P>>,
+    SW: From<Object<S>>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            primary: self.primary.clone(),
+            secondary: self.secondary.clone(),
+        }
+    }
+}
+
+impl<P, S, PW, SW> FallbackPool<P, S, PW, SW>
+where
+    P: Manager,
+    S: Manager,
+    PW: From<Object<P>>,
+    SW: From<Object<S>>,
+{
+    /// Creates a new [`FallbackPool`] from the given `primary` and
+    /// `secondary` [`Pool`]s.
+    pub fn new(primary: Pool<P, PW>, secondary: Pool<S, SW>) -> Self {
+        Self { primary, secondary }
+    }
+
+    /// Retrieves an [`Object`] from the primary [`Pool`] or falls back to
+    /// the secondary one if that fails.
+    ///
+    /// # Errors
+    ///
+    /// See [`FallbackError`] for details.
+    pub async fn get(&self) -> Result<FallbackObject<PW, SW>, FallbackError<P::Error, S::Error>> {
+        let primary = match self.primary.get().await {
+            Ok(obj) => return Ok(FallbackObject::Primary(obj)),
+            Err(e) => e,
+        };
+        match self.secondary.get().await {
+            Ok(obj) => Ok(FallbackObject::Secondary(obj)),
+            Err(secondary) => Err(FallbackError { primary, secondary }),
+        }
+    }
+
+    /// Returns the primary [`Pool`].
+    pub fn primary(&self) -> &Pool<P, PW> {
+        &self.primary
+    }
+
+    /// Returns the secondary [`Pool`].
+    pub fn secondary(&self) -> &Pool<S, SW> {
+        &self.secondary
+    }
+}
+
+/// [`Object`] returned by a [`FallbackPool`] telling which [`Pool`] served it.
+///
+/// If both [`Pool`]s hand out objects of the same type, this can be used just
+/// like a regular [`Object`] as it implements [`Deref`] and [`DerefMut`].
+#[derive(Debug)]
+pub enum FallbackObject<PW, SW> {
+    /// [`Object`] served by the primary [`Pool`].
+    Primary(PW),
+
+    /// [`Object`] served by the secondary [`Pool`].
+    Secondary(SW),
+}
+
+impl<PW, SW> FallbackObject<PW, SW> {
+    /// Indicates whether this [`Object`] has been served by the primary
+    /// [`Pool`].
+    pub fn is_primary(&self) -> bool {
+        matches!(self, Self::Primary(_))
+    }
+
+    /// Indicates whether this [`Object`] has been served by the secondary
+    /// [`Pool`].
+    pub fn is_secondary(&self) -> bool {
+        matches!(self, Self::Secondary(_))
+    }
+}
+
+impl<T: ?Sized, PW, SW> Deref for FallbackObject<PW, SW>
+where
+    PW: Deref<Target = T>,
+    SW: Deref<Target = T>,
+{
+    type Target = T;
+    fn deref(&self) -> &T {
+        match self {
+            Self::Primary(obj) => obj,
+            Self::Secondary(obj) => obj,
+        }
+    }
+}
+
+impl<T: ?Sized, PW, SW> DerefMut for FallbackObject<PW, SW>
+where
+    PW: DerefMut<Target = T>,
+    SW: DerefMut<Target = T>,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        match self {
+            Self::Primary(obj) => obj,
+            Self::Secondary(obj) => obj,
+        }
+    }
+}
+
+/// Error returned by [`FallbackPool::get()`] if neither [`Pool`] was able to
+/// provide an [`Object`].
+#[derive(Debug)]
+pub struct FallbackError<PE, SE> {
+    /// Error returned by the primary [`Pool`].
+    pub primary: PoolError<PE>,
+
+    /// Error returned by the secondary [`Pool`].
+    pub secondary: PoolError<SE>,
+}
+
+impl<PE: fmt::Display, SE: fmt::Display> fmt::Display for FallbackError<PE, SE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Primary pool failed: {}; Secondary pool failed: {}",
+            self.primary, self.secondary
+        )
+    }
+}
+
+impl<PE, SE> std::error::Error for FallbackError<PE, SE>
+where
+    PE: std::error::Error + 'static,
+    SE: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.secondary)
+    }
+}
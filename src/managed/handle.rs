@@ -0,0 +1,53 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::Runtime;
+
+/// Handle to a [`Pool`] passed to [`Manager::attach_pool()`].
+///
+/// It doesn't keep the [`Pool`] alive but allows background tasks started
+/// by a [`Manager`] to find out whether the [`Pool`] is still in use.
+///
+/// [`Manager`]: super::Manager
+/// [`Manager::attach_pool()`]: super::Manager::attach_pool
+/// [`Pool`]: super::Pool
+#[derive(Clone, Debug)]
+pub struct PoolHandle {
+    runtime: Option<Runtime>,
+    closed: Arc<AtomicBool>,
+}
+
+impl PoolHandle {
+    pub(crate) fn new(runtime: Option<Runtime>) -> Self {
+        Self {
+            runtime,
+            closed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the [`Runtime`] of the [`Pool`], if any.
+    ///
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub fn runtime(&self) -> Option<Runtime> {
+        self.runtime
+    }
+
+    /// Indicates whether the [`Pool`] has been closed or dropped.
+    ///
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    /// Marks the [`Pool`] as closed and returns whether it has been open
+    /// before.
+    ///
+    /// [`Pool`]: super::Pool
+    pub(crate) fn close(&self) -> bool {
+        !self.closed.swap(true, Ordering::Relaxed)
+    }
+}
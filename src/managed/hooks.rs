@@ -1,10 +1,10 @@
 //! Hooks allowing to run code when creating and/or recycling objects.
 
-use std::fmt;
+use std::{fmt, future::Future, pin::Pin, time::Duration};
 
 use async_trait::async_trait;
 
-use super::Manager;
+use super::{Manager, Metrics};
 
 /// Possible errors returned by [`Hooks`] which will abort the creation and/or
 /// recycling of objects.
@@ -50,6 +50,32 @@ impl<M: Manager> fmt::Debug for dyn PostCreate<M> {
     }
 }
 
+/// Abstraction of `pre_recycle` hooks.
+#[async_trait]
+pub trait PreRecycle<M: Manager>: Sync + Send {
+    /// The hook method which is called right before an existing [`Object`]
+    /// is recycled.
+    ///
+    /// Returning an error vetoes the [`Object`]. It is dropped and the
+    /// [`Pool`] moves on to the next idle one or creates a new one, so this
+    /// is a good place to reject objects based on their [`Metrics`], e.g.
+    /// ones which are older than some maximum age.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    async fn pre_recycle(
+        &self,
+        obj: &mut M::Type,
+        metrics: &Metrics,
+    ) -> Result<(), HookError<M::Error>>;
+}
+
+impl<M: Manager> fmt::Debug for dyn PreRecycle<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:p}", self)
+    }
+}
+
 /// Abstraction of `post_recycle` hooks.
 #[async_trait]
 pub trait PostRecycle<M: Manager>: Sync + Send {
@@ -65,12 +91,49 @@ impl<M: Manager> fmt::Debug for dyn PostRecycle<M> {
     }
 }
 
+/// Abstraction of `post_idle` hooks.
+#[async_trait]
+pub trait PostIdle<M: Manager>: Sync + Send {
+    /// The hook method which is called after recycling an existing [`Object`]
+    /// which has been idle for longer than the threshold the hook was
+    /// attached with.
+    ///
+    /// This is a good place to refresh session state which is likely to be
+    /// stale, e.g. by pinging the server or re-preparing statements.
+    ///
+    /// [`Object`]: super::Object
+    async fn post_idle(&self, obj: &mut M::Type, idle: Duration)
+        -> Result<(), HookError<M::Error>>;
+}
+
+impl<M: Manager> fmt::Debug for dyn PostIdle<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:p}", self)
+    }
+}
+
+/// Synchronous hook called right before an idle object is parked in the
+/// [`Pool`].
+///
+/// [`Pool`]: super::Pool
+pub(crate) type ParkHook<T> = Box<dyn Fn(&mut T) + Send + Sync>;
+
+/// Synchronous hook called right after an idle object has been taken out of
+/// the [`Pool`].
+///
+/// [`Pool`]: super::Pool
+pub(crate) type UnparkHook<T, E> = Box<dyn Fn(&mut T) -> Result<(), HookError<E>> + Send + Sync>;
+
 /// Collection of all the hooks that can be configured for a [`Pool`].
 ///
 /// [`Pool`]: super::Pool
 pub struct Hooks<M: Manager> {
     pub(crate) post_create: Vec<Box<dyn PostCreate<M>>>,
+    pub(crate) pre_recycle: Vec<Box<dyn PreRecycle<M>>>,
     pub(crate) post_recycle: Vec<Box<dyn PostRecycle<M>>>,
+    pub(crate) post_idle: Vec<(Duration, Box<dyn PostIdle<M>>)>,
+    pub(crate) on_park: Vec<ParkHook<M::Type>>,
+    pub(crate) on_unpark: Vec<UnparkHook<M::Type, M::Error>>,
 }
 
 // Implemented manually to avoid unnecessary trait bound on `M` type parameter.
@@ -78,7 +141,11 @@ impl<M: Manager> fmt::Debug for Hooks<M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Hooks")
             .field("post_create", &self.post_create)
+            .field("pre_recycle", &self.pre_recycle)
             .field("post_recycle", &self.post_recycle)
+            .field("post_idle", &self.post_idle)
+            .field("on_park", &self.on_park.len())
+            .field("on_unpark", &self.on_unpark.len())
             .finish()
     }
 }
@@ -88,7 +155,126 @@ impl<M: Manager> Default for Hooks<M> {
     fn default() -> Self {
         Self {
             post_create: Vec::new(),
+            pre_recycle: Vec::new(),
             post_recycle: Vec::new(),
+            post_idle: Vec::new(),
+            on_park: Vec::new(),
+            on_unpark: Vec::new(),
+        }
+    }
+}
+
+impl<M: Manager + 'static> Hooks<M> {
+    /// Converts these [`Hooks`] into ones of another [`Manager`] sharing the
+    /// same object and error types.
+    pub(crate) fn adapt<N>(self) -> Hooks<N>
+    where
+        N: Manager<Type = M::Type, Error = M::Error>,
+    {
+        Hooks {
+            post_create: self
+                .post_create
+                .into_iter()
+                .map(|hook| Box::new(Adapted(hook)) as Box<dyn PostCreate<N>>)
+                .collect(),
+            pre_recycle: self
+                .pre_recycle
+                .into_iter()
+                .map(|hook| Box::new(Adapted(hook)) as Box<dyn PreRecycle<N>>)
+                .collect(),
+            post_recycle: self
+                .post_recycle
+                .into_iter()
+                .map(|hook| Box::new(Adapted(hook)) as Box<dyn PostRecycle<N>>)
+                .collect(),
+            post_idle: self
+                .post_idle
+                .into_iter()
+                .map(|(threshold, hook)| {
+                    (threshold, Box::new(Adapted(hook)) as Box<dyn PostIdle<N>>)
+                })
+                .collect(),
+            on_park: self.on_park,
+            on_unpark: self.on_unpark,
         }
     }
 }
+
+/// Hook of one [`Manager`] used for another one sharing the same object and
+/// error types.
+///
+/// The hook traits are implemented manually for it because the returned
+/// futures merely forward to the wrapped hook, so `M::Type` isn't required
+/// to be `Send`.
+struct Adapted<H: ?Sized>(Box<H>);
+
+type HookFuture<'a, E> = Pin<Box<dyn Future<Output = Result<(), HookError<E>>> + Send + 'a>>;
+
+impl<M, N> PostCreate<N> for Adapted<dyn PostCreate<M>>
+where
+    M: Manager,
+    N: Manager<Type = M::Type, Error = M::Error>,
+{
+    fn post_create<'a, 'b, 'c>(&'a self, obj: &'b mut N::Type) -> HookFuture<'c, N::Error>
+    where
+        'a: 'c,
+        'b: 'c,
+        Self: 'c,
+    {
+        self.0.post_create(obj)
+    }
+}
+
+impl<M, N> PreRecycle<N> for Adapted<dyn PreRecycle<M>>
+where
+    M: Manager,
+    N: Manager<Type = M::Type, Error = M::Error>,
+{
+    fn pre_recycle<'a, 'b, 'c, 'd>(
+        &'a self,
+        obj: &'b mut N::Type,
+        metrics: &'c Metrics,
+    ) -> HookFuture<'d, N::Error>
+    where
+        'a: 'd,
+        'b: 'd,
+        'c: 'd,
+        Self: 'd,
+    {
+        self.0.pre_recycle(obj, metrics)
+    }
+}
+
+impl<M, N> PostRecycle<N> for Adapted<dyn PostRecycle<M>>
+where
+    M: Manager,
+    N: Manager<Type = M::Type, Error = M::Error>,
+{
+    fn post_recycle<'a, 'b, 'c>(&'a self, obj: &'b mut N::Type) -> HookFuture<'c, N::Error>
+    where
+        'a: 'c,
+        'b: 'c,
+        Self: 'c,
+    {
+        self.0.post_recycle(obj)
+    }
+}
+
+impl<M, N> PostIdle<N> for Adapted<dyn PostIdle<M>>
+where
+    M: Manager,
+    N: Manager<Type = M::Type, Error = M::Error>,
+{
+    fn post_idle<'a, 'b, 'c>(
+        &'a self,
+        obj: &'b mut N::Type,
+        idle: Duration,
+    ) -> HookFuture<'c, N::Error>
+    where
+        'a: 'c,
+        'b: 'c,
+        Self: 'c,
+    {
+        self.0.post_idle(obj, idle)
+    }
+}
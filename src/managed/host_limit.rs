@@ -0,0 +1,142 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use rustix::fs::{flock, FlockOperation};
+
+use crate::Runtime;
+
+use super::PoolError;
+
+/// Limit on the total number of objects shared by all processes on the same
+/// host, e.g. many replicas of the same binary connecting to a small
+/// database.
+///
+/// This is the inter-process counterpart of the [`CapacityLimiter`]. Every
+/// [`Pool`] attached to a [`HostLimiter`] via
+/// [`PoolBuilder::host_limiter()`] needs to take one of its slots for every
+/// object it creates and gives it back once that object is dropped. The
+/// slots are files in a shared directory which are claimed via advisory
+/// file locks, so the operating system releases them automatically when a
+/// process exits or crashes.
+///
+/// All the processes need to use the same directory and the same `max`.
+/// While all slots are taken, creating an object polls them every
+/// [`HostLimiter::with_poll_interval()`] until the create timeout is hit.
+/// This requires a [`Runtime`], so without one creating fails with a
+/// [`PoolError::NoRuntimeSpecified`] right away.
+///
+/// [`CapacityLimiter`]: super::CapacityLimiter
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::host_limiter()`]: super::PoolBuilder::host_limiter
+#[derive(Clone, Debug)]
+pub struct HostLimiter {
+    inner: Arc<HostLimiterInner>,
+}
+
+#[derive(Debug)]
+struct HostLimiterInner {
+    dir: PathBuf,
+    max: usize,
+    poll_interval: Duration,
+}
+
+impl HostLimiter {
+    /// Creates a new [`HostLimiter`] allowing at most `max` objects across
+    /// all the processes using the given `dir` for their slots.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of creating the `dir` or the slot files in it.
+    pub fn new(dir: impl Into<PathBuf>, max: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        for slot in 0..max {
+            let _ = open_slot(&dir, slot)?;
+        }
+        Ok(Self {
+            inner: Arc::new(HostLimiterInner {
+                dir,
+                max,
+                poll_interval: Duration::from_millis(10),
+            }),
+        })
+    }
+
+    /// Sets the interval at which the slots are polled while all of them
+    /// are taken. Defaults to 10 milliseconds.
+    #[must_use]
+    pub fn with_poll_interval(self, poll_interval: Duration) -> Self {
+        Self {
+            inner: Arc::new(HostLimiterInner {
+                dir: self.inner.dir.clone(),
+                max: self.inner.max,
+                poll_interval,
+            }),
+        }
+    }
+
+    /// Returns the directory holding the slot files.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.inner.dir
+    }
+
+    /// Returns the total number of objects allowed by this
+    /// [`HostLimiter`].
+    #[must_use]
+    pub fn max(&self) -> usize {
+        self.inner.max
+    }
+
+    /// Waits for a slot to become available as described in the
+    /// [type documentation](Self).
+    pub(crate) async fn acquire<E>(
+        &self,
+        runtime: Option<Runtime>,
+    ) -> Result<HostSlot, PoolError<E>> {
+        loop {
+            if let Some(slot) = self.try_acquire() {
+                return Ok(slot);
+            }
+            match runtime {
+                Some(runtime) => runtime.sleep(self.inner.poll_interval).await,
+                None => return Err(PoolError::NoRuntimeSpecified),
+            }
+        }
+    }
+
+    /// Takes a slot if one is available right now.
+    pub(crate) fn try_acquire(&self) -> Option<HostSlot> {
+        (0..self.inner.max).find_map(|slot| {
+            // Slot files which can't be opened anymore are treated as busy.
+            let file = open_slot(&self.inner.dir, slot).ok()?;
+            flock(&file, FlockOperation::NonBlockingLockExclusive).ok()?;
+            Some(HostSlot { _file: file })
+        })
+    }
+}
+
+/// Opens the file of the given `slot` creating it if necessary.
+fn open_slot(dir: &Path, slot: usize) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(format!("slot-{}.lock", slot)))
+}
+
+/// Slot of a [`HostLimiter`] held by an object.
+///
+/// It is stored in the [`Resources`] of the object and the lock is released
+/// by closing the file once the object is dropped.
+///
+/// [`Resources`]: super::Resources
+#[derive(Debug)]
+pub(crate) struct HostSlot {
+    _file: File,
+}
@@ -0,0 +1,134 @@
+//! Interceptors running code around every attempt to get an [`Object`].
+//!
+//! Unlike the [`hooks`], which are tied to the lifecycle of single objects,
+//! an [`Interceptor`] wraps the whole [`Pool::get()`] operation including
+//! waiting for a slot. This allows cross-cutting concerns like permission
+//! checks, tagging of the current task or custom metrics without wrapping
+//! the [`Pool`] type:
+//!
+//! ```rust
+//! use std::{
+//!     convert::Infallible,
+//!     sync::{
+//!         atomic::{AtomicBool, AtomicUsize, Ordering},
+//!         Arc,
+//!     },
+//! };
+//!
+//! use async_trait::async_trait;
+//! use deadpool::managed::{intercept::Interceptor, AcquireInfo, NoopManager, Pool, PoolError};
+//!
+//! #[derive(Clone, Default)]
+//! struct Audit {
+//!     locked: Arc<AtomicBool>,
+//!     created: Arc<AtomicUsize>,
+//! }
+//!
+//! #[async_trait]
+//! impl Interceptor<Infallible> for Audit {
+//!     async fn before_get(&self) -> Result<(), PoolError<Infallible>> {
+//!         match self.locked.load(Ordering::Relaxed) {
+//!             true => Err(PoolError::Cancelled),
+//!             false => Ok(()),
+//!         }
+//!     }
+//!
+//!     async fn after_get(
+//!         &self,
+//!         result: &Result<AcquireInfo, PoolError<Infallible>>,
+//!     ) -> Result<(), PoolError<Infallible>> {
+//!         if matches!(result, Ok(info) if info.created_new) {
+//!             self.created.fetch_add(1, Ordering::Relaxed);
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let audit = Audit::default();
+//! let pool = Pool::<NoopManager>::builder(NoopManager::new())
+//!     .interceptor(audit.clone())
+//!     .build()
+//!     .unwrap();
+//! drop(pool.get().await.unwrap());
+//! assert_eq!(audit.created.load(Ordering::Relaxed), 1);
+//!
+//! audit.locked.store(true, Ordering::Relaxed);
+//! assert!(matches!(pool.get().await, Err(PoolError::Cancelled)));
+//! # }
+//! ```
+//!
+//! Interceptors are generic over the error type of the [`Manager`] only, so
+//! they are kept by [`PoolBuilder::wrap_manager()`].
+//!
+//! [`Manager`]: super::Manager
+//! [`Object`]: super::Object
+//! [`Pool`]: super::Pool
+//! [`Pool::get()`]: super::Pool::get
+//! [`PoolBuilder::wrap_manager()`]: super::PoolBuilder::wrap_manager
+//! [`hooks`]: super::hooks
+
+use std::{fmt, future::Future, pin::Pin};
+
+use super::{AcquireInfo, PoolError};
+
+/// Future returned by the methods of an [`Interceptor`].
+pub type InterceptFuture<'a, E> =
+    Pin<Box<dyn Future<Output = Result<(), PoolError<E>>> + Send + 'a>>;
+
+/// Code running around every attempt to get an [`Object`] from a [`Pool`].
+///
+/// The methods have the signatures generated by `#[async_trait]`, so they
+/// can be implemented as `async fn`s using it. See the
+/// [module documentation](self) for an example.
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+pub trait Interceptor<E>: Sync + Send {
+    /// Called before trying to get an [`Object`].
+    ///
+    /// Returning an error rejects the attempt with that error right away.
+    /// Later interceptors aren't called then, while the earlier ones still
+    /// get that error passed to their [`Interceptor::after_get()`]. The
+    /// default implementation does nothing.
+    ///
+    /// [`Object`]: super::Object
+    fn before_get<'a, 'b>(&'a self) -> InterceptFuture<'b, E>
+    where
+        'a: 'b,
+        E: 'b,
+        Self: 'b,
+    {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called with the outcome of an attempt to get an [`Object`] once
+    /// [`Interceptor::before_get()`] succeeded.
+    ///
+    /// Returning an error replaces the outcome with that error and returns
+    /// a retrieved [`Object`] to the [`Pool`] right away. The remaining
+    /// interceptors are still called with the replaced outcome. The default
+    /// implementation does nothing.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    fn after_get<'a, 'b, 'c>(
+        &'a self,
+        _result: &'b Result<AcquireInfo, PoolError<E>>,
+    ) -> InterceptFuture<'c, E>
+    where
+        'a: 'c,
+        'b: 'c,
+        E: 'c,
+        Self: 'c,
+    {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl<E> fmt::Debug for dyn Interceptor<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:p}", self)
+    }
+}
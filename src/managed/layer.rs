@@ -0,0 +1,298 @@
+//! Middleware for wrapping [`Manager`]s with cross-cutting concerns.
+//!
+//! A [`Layer`] decorates an existing [`Manager`] without changing the type of
+//! objects it creates or the errors it returns. This allows adding things
+//! like retries or timing to any backend without writing a newtype
+//! [`Manager`] for every one of them.
+//!
+//! Layers are applied via [`PoolBuilder::wrap_manager()`]:
+//!
+//! ```rust
+//! use deadpool::managed::{
+//!     layer::{RetryLayer, TimingLayer},
+//!     NoopManager, Pool,
+//! };
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let pool = Pool::builder(NoopManager::new())
+//!     .wrap_manager(RetryLayer::new(3))
+//!     .wrap_manager(TimingLayer::new(|op, duration| println!("{:?} took {:?}", op, duration)))
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(*pool.get().await.unwrap(), 0);
+//! # }
+//! ```
+//!
+//! [`PoolBuilder::wrap_manager()`]: super::PoolBuilder::wrap_manager
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use crate::Runtime;
+
+use super::{Companion, Manager, PoolHandle, RecycleError, RecycleErrorKind, RecycleResult};
+
+/// Decorator turning a [`Manager`] into another one.
+pub trait Layer<M: Manager> {
+    /// [`Manager`] produced by this [`Layer`].
+    type Manager: Manager<Type = M::Type, Error = M::Error>;
+
+    /// Wraps the given `inner` [`Manager`].
+    fn layer(self, inner: M) -> Self::Manager;
+}
+
+/// [`Layer`] retrying failed [`Manager::create()`] calls.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryLayer {
+    retries: usize,
+    runtime: Option<Runtime>,
+}
+
+impl RetryLayer {
+    /// Creates a new [`RetryLayer`] which retries failed creations up to
+    /// `retries` times before giving up.
+    #[must_use]
+    pub fn new(retries: usize) -> Self {
+        Self {
+            retries,
+            runtime: None,
+        }
+    }
+
+    /// Makes this [`RetryLayer`] wait for the [`Manager::backoff_hint()`]
+    /// of a failed creation before retrying, using the given [`Runtime`].
+    ///
+    /// Without a [`Runtime`] all retries happen right away.
+    #[must_use]
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+}
+
+impl<M> Layer<M> for RetryLayer
+where
+    M: Manager,
+    M::Type: Send,
+{
+    type Manager = Retry<M>;
+
+    fn layer(self, inner: M) -> Retry<M> {
+        Retry {
+            inner,
+            retries: self.retries,
+            runtime: self.runtime,
+        }
+    }
+}
+
+/// [`Manager`] produced by a [`RetryLayer`].
+#[derive(Debug)]
+pub struct Retry<M> {
+    inner: M,
+    retries: usize,
+    runtime: Option<Runtime>,
+}
+
+impl<M> Retry<M> {
+    /// Returns the wrapped [`Manager`].
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<M> Manager for Retry<M>
+where
+    M: Manager,
+    M::Type: Send,
+{
+    type Type = M::Type;
+    type Error = M::Error;
+
+    async fn create(&self) -> Result<M::Type, M::Error> {
+        let mut retries = self.retries;
+        loop {
+            let hint = match self.inner.create().await {
+                Ok(obj) => return Ok(obj),
+                Err(e) if retries == 0 => return Err(e),
+                Err(e) => self.inner.backoff_hint(&e),
+            };
+            retries -= 1;
+            if let (Some(runtime), Some(hint)) = (self.runtime, hint) {
+                runtime.sleep(hint).await;
+            }
+        }
+    }
+
+    async fn recycle(&self, obj: &mut M::Type) -> RecycleResult<M::Error> {
+        self.inner.recycle(obj).await
+    }
+
+    fn detach(&self, obj: &mut M::Type) {
+        self.inner.detach(obj)
+    }
+
+    fn attach_pool(&self, handle: &PoolHandle) {
+        self.inner.attach_pool(handle)
+    }
+
+    fn detach_pool(&self) {
+        self.inner.detach_pool()
+    }
+
+    fn classify_recycle_error(&self, error: &RecycleError<M::Error>) -> RecycleErrorKind {
+        self.inner.classify_recycle_error(error)
+    }
+
+    fn backoff_hint(&self, error: &M::Error) -> Option<Duration> {
+        self.inner.backoff_hint(error)
+    }
+
+    fn companion(&self, obj: &mut M::Type) -> Option<Companion> {
+        self.inner.companion(obj)
+    }
+
+    fn size_limit(&self, obj: &M::Type) -> Option<usize> {
+        self.inner.size_limit(obj)
+    }
+
+    fn memory_usage(&self, obj: &M::Type) -> usize {
+        self.inner.memory_usage(obj)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn describe(&self, obj: &M::Type) -> serde_json::Value {
+        self.inner.describe(obj)
+    }
+}
+
+/// Operation of a [`Manager`] measured by a [`TimingLayer`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    /// [`Manager::create()`] call.
+    Create,
+
+    /// [`Manager::recycle()`] call.
+    Recycle,
+}
+
+/// [`Layer`] reporting the duration of every [`Manager::create()`] and
+/// [`Manager::recycle()`] call to a callback.
+#[derive(Clone, Copy, Debug)]
+pub struct TimingLayer<F> {
+    f: F,
+}
+
+impl<F> TimingLayer<F>
+where
+    F: Fn(Operation, Duration) + Send + Sync,
+{
+    /// Creates a new [`TimingLayer`] which reports all durations to the
+    /// given callback `f`.
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<M, F> Layer<M> for TimingLayer<F>
+where
+    M: Manager,
+    M::Type: Send,
+    F: Fn(Operation, Duration) + Send + Sync,
+{
+    type Manager = Timing<M, F>;
+
+    fn layer(self, inner: M) -> Timing<M, F> {
+        Timing { inner, f: self.f }
+    }
+}
+
+/// [`Manager`] produced by a [`TimingLayer`].
+pub struct Timing<M, F> {
+    inner: M,
+    f: F,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `F` type parameter.
+impl<M: fmt::Debug, F> fmt::Debug for Timing<M, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timing")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<M, F> Timing<M, F> {
+    /// Returns the wrapped [`Manager`].
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<M, F> Manager for Timing<M, F>
+where
+    M: Manager,
+    M::Type: Send,
+    F: Fn(Operation, Duration) + Send + Sync,
+{
+    type Type = M::Type;
+    type Error = M::Error;
+
+    async fn create(&self) -> Result<M::Type, M::Error> {
+        let start = Instant::now();
+        let result = self.inner.create().await;
+        (self.f)(Operation::Create, start.elapsed());
+        result
+    }
+
+    async fn recycle(&self, obj: &mut M::Type) -> RecycleResult<M::Error> {
+        let start = Instant::now();
+        let result = self.inner.recycle(obj).await;
+        (self.f)(Operation::Recycle, start.elapsed());
+        result
+    }
+
+    fn detach(&self, obj: &mut M::Type) {
+        self.inner.detach(obj)
+    }
+
+    fn attach_pool(&self, handle: &PoolHandle) {
+        self.inner.attach_pool(handle)
+    }
+
+    fn detach_pool(&self) {
+        self.inner.detach_pool()
+    }
+
+    fn classify_recycle_error(&self, error: &RecycleError<M::Error>) -> RecycleErrorKind {
+        self.inner.classify_recycle_error(error)
+    }
+
+    fn backoff_hint(&self, error: &M::Error) -> Option<Duration> {
+        self.inner.backoff_hint(error)
+    }
+
+    fn companion(&self, obj: &mut M::Type) -> Option<Companion> {
+        self.inner.companion(obj)
+    }
+
+    fn size_limit(&self, obj: &M::Type) -> Option<usize> {
+        self.inner.size_limit(obj)
+    }
+
+    fn memory_usage(&self, obj: &M::Type) -> usize {
+        self.inner.memory_usage(obj)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn describe(&self, obj: &M::Type) -> serde_json::Value {
+        self.inner.describe(obj)
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use super::{Manager, Object};
+
+/// Wrapper around an [`Object`] which is neither [`Send`] nor [`Sync`].
+///
+/// Futures holding a [`LocalObject`] across an `.await` point are not
+/// [`Send`] either. This makes the compiler reject code which holds a pooled
+/// [`Object`] across slow `.await`s when it is spawned on a multi-threaded
+/// executor, e.g. via `tokio::spawn`.
+///
+/// This is opt-in per [`Pool`] by using it as the wrapper type, e.g.
+/// `Pool<Manager, LocalObject<Manager>>`. Alternatively,
+/// [`LocalObject::from()`] converts a regular [`Object`] in selected code
+/// paths only.
+///
+/// ```rust,compile_fail
+/// use deadpool::managed::{LocalObject, NoopManager};
+///
+/// fn assert_send<T: Send>() {}
+///
+/// assert_send::<LocalObject<NoopManager>>();
+/// ```
+///
+/// [`Pool`]: super::Pool
+#[must_use]
+pub struct LocalObject<M: Manager> {
+    obj: Object<M>,
+    _not_send: PhantomData<*const ()>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on the struct.
+impl<M> fmt::Debug for LocalObject<M>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalObject")
+            .field("obj", &self.obj)
+            .finish()
+    }
+}
+
+impl<M: Manager> LocalObject<M> {
+    /// Converts this [`LocalObject`] back into a regular [`Object`].
+    pub fn into_inner(this: Self) -> Object<M> {
+        this.obj
+    }
+}
+
+impl<M: Manager> From<Object<M>> for LocalObject<M> {
+    fn from(obj: Object<M>) -> Self {
+        Self {
+            obj,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl<M: Manager> Deref for LocalObject<M> {
+    type Target = M::Type;
+    fn deref(&self) -> &M::Type {
+        &self.obj
+    }
+}
+
+impl<M: Manager> DerefMut for LocalObject<M> {
+    fn deref_mut(&mut self) -> &mut M::Type {
+        &mut self.obj
+    }
+}
+
+impl<M: Manager> AsRef<M::Type> for LocalObject<M> {
+    fn as_ref(&self) -> &M::Type {
+        self
+    }
+}
+
+impl<M: Manager> AsMut<M::Type> for LocalObject<M> {
+    fn as_mut(&mut self) -> &mut M::Type {
+        self
+    }
+}
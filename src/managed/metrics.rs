@@ -0,0 +1,128 @@
+use std::time::{Duration, Instant};
+
+/// Number of most recent uses considered by [`Metrics::error_rate()`].
+const ERROR_WINDOW: usize = 64;
+
+/// Statistics regarding an [`Object`].
+///
+/// [`Object`]: super::Object
+#[derive(Clone, Copy, Debug)]
+pub struct Metrics {
+    /// The instant when this [`Object`] was created.
+    ///
+    /// [`Object`]: super::Object
+    pub created: Instant,
+
+    /// The time it took to create this [`Object`].
+    ///
+    /// This is a good indicator of how expensive it is to replace this
+    /// [`Object`], e.g. due to long TLS handshakes or session warmup. Only
+    /// the successful [`Manager::create()`] call is measured, so waiting for
+    /// a creation slot or retries don't count towards it.
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    /// [`Object`]: super::Object
+    pub create_duration: Duration,
+
+    /// The instant when this [`Object`] was last recycled.
+    ///
+    /// [`Object`]: super::Object
+    pub recycled: Option<Instant>,
+
+    /// The number of times this [`Object`] was recycled.
+    ///
+    /// [`Object`]: super::Object
+    pub recycle_count: usize,
+
+    /// The instant when this [`Object`] was last returned to the [`Pool`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    pub returned: Option<Instant>,
+
+    /// The number of errors reported via [`Object::report_error()`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`Object::report_error()`]: super::Object::report_error
+    pub error_count: usize,
+
+    /// Bit `i` is set if an error has been reported during the `i`-th most
+    /// recent use.
+    error_window: u64,
+}
+
+impl Metrics {
+    pub(crate) fn new(create_duration: Duration) -> Self {
+        Self {
+            created: Instant::now(),
+            create_duration,
+            recycled: None,
+            recycle_count: 0,
+            returned: None,
+            error_count: 0,
+            error_window: 0,
+        }
+    }
+
+    /// Records that this [`Object`] has just been recycled for another use.
+    ///
+    /// [`Object`]: super::Object
+    pub(crate) fn record_recycle(&mut self) {
+        self.recycled = Some(Instant::now());
+        self.recycle_count += 1;
+        self.error_window <<= 1;
+    }
+
+    /// Records an error reported during the current use.
+    pub(crate) fn record_error(&mut self) {
+        self.error_count += 1;
+        self.error_window |= 1;
+    }
+
+    /// Returns the time elapsed since this [`Object`] was created.
+    ///
+    /// [`Object`]: super::Object
+    #[must_use]
+    pub fn age(&self) -> Duration {
+        self.created.elapsed()
+    }
+
+    /// Returns the number of times this [`Object`] has been handed out.
+    ///
+    /// [`Object`]: super::Object
+    #[must_use]
+    pub fn use_count(&self) -> usize {
+        self.recycle_count + 1
+    }
+
+    /// Returns the ratio of uses with at least one reported error among the
+    /// last 64 times this [`Object`] has been handed out.
+    ///
+    /// Older uses fall out of this window, so an [`Object`] recovering from
+    /// a burst of errors isn't judged by its whole lifetime.
+    ///
+    /// [`Object`]: super::Object
+    #[must_use]
+    pub fn error_rate(&self) -> f64 {
+        f64::from(self.error_window.count_ones()) / self.use_count().min(ERROR_WINDOW) as f64
+    }
+
+    /// Returns the time elapsed since this [`Object`] was last recycled or
+    /// created.
+    ///
+    /// [`Object`]: super::Object
+    #[must_use]
+    pub fn last_used(&self) -> Duration {
+        self.recycled.unwrap_or(self.created).elapsed()
+    }
+
+    /// Returns the time elapsed since this [`Object`] was last returned to
+    /// the [`Pool`] or created.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub fn idle(&self) -> Duration {
+        self.returned.unwrap_or(self.created).elapsed()
+    }
+}
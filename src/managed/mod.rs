@@ -50,36 +50,133 @@
 //! For a more complete example please see
 //! [`deadpool-postgres`](https://crates.io/crates/deadpool-postgres) crate.
 
+mod backend_limit;
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
 mod builder;
+mod capacity;
+mod companion;
 mod config;
+mod cooldown;
+mod create_limit;
+mod discard;
+pub mod engine;
 mod errors;
+pub mod eviction;
+mod experiment;
+pub mod fallback;
+mod handle;
 pub mod hooks;
-pub mod sync;
+#[cfg(all(unix, feature = "host_limit"))]
+mod host_limit;
+pub mod intercept;
+pub mod layer;
+mod local;
+mod metrics;
+pub mod multiplex;
+mod noop;
+pub mod race;
+pub mod random;
+mod rejections;
+mod reset;
+mod resources;
+mod retain;
+mod return_rate;
+mod self_test;
+mod shared;
+mod stats;
+mod stub;
+pub(crate) mod task_panic;
+#[cfg(feature = "test-utils")]
+mod test_utils;
+pub(crate) mod unwind;
+mod usage;
+mod value;
+mod waiters;
 
 use std::{
+    any::Any,
     collections::VecDeque,
+    convert::TryFrom,
     fmt,
     future::Future,
     marker::PhantomData,
+    mem,
     ops::{Deref, DerefMut},
     sync::{
-        atomic::{AtomicIsize, AtomicUsize, Ordering},
-        Arc, Mutex, Weak,
+        atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering},
+        Arc, Weak,
     },
-    time::Duration,
+    thread::{self, ThreadId},
+    time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
-use tokio::sync::{Semaphore, TryAcquireError};
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore, SemaphorePermit, TryAcquireError};
 
-use crate::runtime::Runtime;
+use crate::{
+    lock::Mutex,
+    runtime::{self, Runtime},
+};
+
+pub use crate::{MemoryUsage, Rejections, Status};
 
-pub use crate::Status;
+#[cfg(feature = "sync")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sync")))]
+pub use crate::sync;
 
 pub use self::{
+    backend_limit::BackendLimitExceeded,
     builder::{BuildError, PoolBuilder},
-    config::{PoolConfig, Timeouts},
-    errors::{PoolError, RecycleError, TimeoutType},
+    capacity::{CapacityLimiter, CapacitySemaphore},
+    companion::Companion,
+    config::{
+        CreateLimit, CreateRetry, ErrorRateLimit, OnExhausted, PoolConfig, Quarantine, QueueMode,
+        RecycleExperiment, TimeoutCooldown, Timeouts, WaitTimeoutScaling,
+    },
+    discard::DiscardReason,
+    errors::{PoolError, RecycleError, RecycleErrorKind, TimeoutType},
+    experiment::RecycleExperimentReport,
+    handle::PoolHandle,
+    local::LocalObject,
+    metrics::Metrics,
+    noop::NoopManager,
+    reset::Reset,
+    resources::Resources,
+    self_test::SelfTestReport,
+    shared::SharedObject,
+    stats::PoolStats,
+    stub::{StubExhausted, StubManager},
+    task_panic::BackgroundTask,
+    usage::CapacityReport,
+    value::ValueManager,
+    waiters::{WaitProgress, WaitStatus, WaiterInfo},
+};
+
+#[cfg(all(unix, feature = "host_limit"))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "host_limit"))))]
+pub use self::host_limit::HostLimiter;
+
+use self::{
+    backend_limit::OnBackendLimit,
+    builder::DeadlineExtractor,
+    cooldown::Cooldown,
+    create_limit::CreateBudget,
+    discard::OnDiscard,
+    eviction::EvictionPolicy,
+    experiment::Experiment,
+    intercept::Interceptor,
+    random::RandomSource,
+    rejections::RejectionCounters,
+    retain::{RetainEpoch, RetainFilter, Retainers},
+    return_rate::ReturnRate,
+    self_test::SelfTestCheck,
+    stats::StatsCounters,
+    task_panic::{report_panics, OnTaskPanic},
+    unwind::CatchUnwind,
+    usage::UsageHistory,
+    waiters::Waiters,
 };
 
 /// Result type of the [`Manager::recycle()`] method.
@@ -111,6 +208,96 @@ pub trait Manager: Sync + Send {
     /// any references to the handed out [`Object`]s then the default
     /// implementation can be used which does nothing.
     fn detach(&self, _obj: &mut Self::Type) {}
+
+    /// Called once right after a [`Pool`] using this [`Manager`] has been
+    /// built.
+    ///
+    /// This is the place to start background work tied to the lifetime of
+    /// the [`Pool`] like DNS refreshers or token renewers. The given
+    /// `handle` tells whether the [`Pool`] is still in use. The default
+    /// implementation does nothing.
+    fn attach_pool(&self, _handle: &PoolHandle) {}
+
+    /// Called once when the [`Pool`] using this [`Manager`] is closed or
+    /// dropped, whichever happens first.
+    ///
+    /// This is the place to stop any background work started in
+    /// [`Manager::attach_pool()`]. The default implementation does nothing.
+    fn detach_pool(&self) {}
+
+    /// Classifies an error returned by [`Manager::recycle()`].
+    ///
+    /// [`RecycleErrorKind::Transient`] errors cause the recycling to be
+    /// retried once before the [`Object`] is discarded, while
+    /// [`RecycleErrorKind::Fatal`] errors discard it right away and count
+    /// towards the [`PoolConfig::timeout_cooldown`]. The default
+    /// implementation treats all errors as [`RecycleErrorKind::Fatal`].
+    fn classify_recycle_error(&self, _error: &RecycleError<Self::Error>) -> RecycleErrorKind {
+        RecycleErrorKind::Fatal
+    }
+
+    /// Extracts a suggested backoff from an error returned by
+    /// [`Manager::create()`].
+    ///
+    /// Some servers tell how long clients should wait before trying again,
+    /// e.g. "too many connections, retry in 5s". Retry logic like the
+    /// [`RetryLayer`] waits for the returned duration before the next
+    /// attempt instead of retrying right away. The default implementation
+    /// returns [`None`].
+    ///
+    /// [`RetryLayer`]: layer::RetryLayer
+    fn backoff_hint(&self, _error: &Self::Error) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the background task accompanying a freshly created instance
+    /// of [`Manager::Type`], if any.
+    ///
+    /// This is called right after every successful [`Manager::create()`].
+    /// The returned [`Companion`] is aborted right before the instance is
+    /// dropped by the [`Pool`]. The default implementation returns
+    /// [`None`].
+    fn companion(&self, _obj: &mut Self::Type) -> Option<Companion> {
+        None
+    }
+
+    /// Returns the maximum number of instances of [`Manager::Type`] the
+    /// backend allows for the [`Pool`], e.g. derived from the
+    /// `max_connections` setting of a database server or a per-user quota.
+    ///
+    /// This is called once after the first successful [`Manager::create()`]
+    /// with the freshly created instance. It isn't async and must not block,
+    /// so it can only derive the limit from what the instance already knows,
+    /// e.g. a setting the backend reported while connecting. A
+    /// [`Pool`] configured with a larger maximum size reports this via
+    /// [`PoolBuilder::on_backend_limit()`] and shrinks itself if
+    /// [`PoolConfig::clamp_to_backend_limit`] is set. The default
+    /// implementation returns [`None`].
+    fn size_limit(&self, _obj: &Self::Type) -> Option<usize> {
+        None
+    }
+
+    /// Estimates the memory in bytes held by an instance of
+    /// [`Manager::Type`] including its buffers.
+    ///
+    /// This is summed up in the [`MemoryUsage::objects`] returned by
+    /// [`Pool::memory_usage()`]. Checked out instances are estimated once when
+    /// they are handed out. The default implementation returns zero.
+    fn memory_usage(&self, _obj: &Self::Type) -> usize {
+        0
+    }
+
+    /// Describes an instance of [`Manager::Type`] for diagnostic purposes.
+    ///
+    /// This is used by [`Pool::describe_idle()`] and can be used to include
+    /// backend-specific details (e.g. server PID, session ID or negotiated
+    /// TLS version) in debug dumps. The default implementation returns
+    /// [`serde_json::Value::Null`].
+    #[cfg(feature = "diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+    fn describe(&self, _obj: &Self::Type) -> serde_json::Value {
+        serde_json::Value::Null
+    }
 }
 
 #[derive(Debug)]
@@ -124,34 +311,207 @@ enum ObjectState {
     Dropped,
 }
 
+/// Actual pooled object alongside its [`Metrics`].
+#[derive(Debug)]
+struct ObjectInner<M: Manager> {
+    /// Secondary resources attached to this object. This is declared before
+    /// `obj` so the resources are dropped first.
+    resources: Resources,
+    obj: M::Type,
+    metrics: Metrics,
+    /// Thread which returned this object to the [`Pool`] the last time. This
+    /// is only tracked with [`PoolConfig::thread_affinity`] enabled.
+    returned_on: Option<ThreadId>,
+    /// Epoch of the last [`Pool::retain()`] predicate checked against this
+    /// object.
+    retain_epoch: RetainEpoch<M::Type>,
+    /// Number of times in a row this object has been put into quarantine as
+    /// configured via [`PoolConfig::quarantine`].
+    quarantined: usize,
+    /// Slot of the [`CapacityLimiter`] held by this object.
+    _limit: Option<OwnedSemaphorePermit>,
+}
+
 /// Wrapper around the actual pooled object which implements [`Deref`],
 /// [`DerefMut`] and [`Drop`] traits.
 ///
 /// Use this object just as if it was of type `T` and upon leaving a scope the
 /// [`Drop::drop()`] will take care of returning it to the pool.
-#[derive(Debug)]
 #[must_use]
 pub struct Object<M: Manager> {
     /// Actual pooled object.
-    obj: Option<M::Type>,
+    obj: Option<ObjectInner<M>>,
 
     /// Current state of the object.
     state: ObjectState,
 
+    /// Indicates whether this object has been created beyond the
+    /// [`PoolConfig::max_size`] and therefore doesn't hold a slot of the
+    /// [`Pool`]. See [`OnExhausted::CreateBurst`] for details.
+    burst: bool,
+
+    /// Estimated memory of the pooled object counted as checked out as
+    /// returned by [`Manager::memory_usage()`].
+    memory: usize,
+
+    /// Indicates whether this object is unusable and must not be returned to
+    /// the [`Pool`] but discarded when dropped.
+    broken: AtomicBool,
+
+    /// Tag provided via [`Pool::get_tagged()`] naming the subsystem which
+    /// checked out this object.
+    tag: Option<Arc<str>>,
+
+    /// Timeouts of the [`Pool`] handle this object has been checked out
+    /// from, also applied by [`Object::recycle_now()`].
+    timeouts: Timeouts,
+
     /// Pool to return the pooled object to.
     pool: Weak<PoolInner<M>>,
 }
 
+// Implemented manually to avoid unnecessary trait bound on the struct.
+impl<M> fmt::Debug for Object<M>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Object")
+            .field("obj", &self.obj)
+            .field("state", &self.state)
+            .field("burst", &self.burst)
+            .field("memory", &self.memory)
+            .field("broken", &self.broken)
+            .field("tag", &self.tag)
+            .field("timeouts", &self.timeouts)
+            .field("pool", &self.pool)
+            .finish()
+    }
+}
+
 impl<M: Manager> Object<M> {
     /// Takes this [`Object`] from its [`Pool`] permanently. This reduces the
     /// size of the [`Pool`].
+    ///
+    /// The slot held by this [`Object`] is released, so the [`Pool`] can
+    /// create a replacement right away. [`Manager::detach()`] is called
+    /// before the inner object is handed out and its [`Companion`] task, if
+    /// any, is detached to keep running.
     #[must_use]
     pub fn take(mut this: Self) -> M::Type {
         this.state = ObjectState::Taken;
         if let Some(pool) = this.pool.upgrade() {
             pool.manager.detach(&mut this);
         }
-        this.obj.take().unwrap()
+        let mut inner = this.obj.take().unwrap();
+        if let Some(companion) = inner.resources.remove::<Companion>() {
+            companion.detach();
+        }
+        inner.obj
+    }
+
+    /// Marks this [`Object`] as broken so it is discarded instead of being
+    /// returned to its [`Pool`] when dropped.
+    #[cfg(feature = "sync")]
+    pub(crate) fn mark_broken(this: &Self) {
+        this.broken.store(true, Ordering::Relaxed);
+    }
+
+    /// Reports an error which occurred while using this [`Object`].
+    ///
+    /// If the [`PoolConfig::max_error_rate`] is exceeded, this [`Object`] is
+    /// discarded instead of being returned to its [`Pool`].
+    pub fn report_error(this: &mut Self) {
+        this.obj.as_mut().unwrap().metrics.record_error();
+    }
+
+    /// Indicates whether this [`Object`] must be discarded instead of being
+    /// returned to the given `pool`.
+    fn is_broken(&self, pool: &PoolInner<M>) -> bool {
+        if self.broken.load(Ordering::Relaxed) {
+            return true;
+        }
+        match (&pool.config.max_error_rate, &self.obj) {
+            (Some(limit), Some(inner)) => limit.is_exceeded(&inner.metrics),
+            _ => false,
+        }
+    }
+
+    /// Recycles this [`Object`] right away while still holding its slot of
+    /// the [`Pool`].
+    ///
+    /// This runs the `pre_recycle` hooks, [`Manager::recycle()`] and the
+    /// `post_recycle` hooks just like it happens when an idle [`Object`] is
+    /// handed out. If recycling fails or a hook rejects the object, it is
+    /// discarded and a new one is created in its place. This is useful
+    /// before starting a long critical transaction on a connection which
+    /// might have become stale. The [`Timeouts`] of the [`Pool`] handle this
+    /// [`Object`] has been checked out from apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Closed`] if the [`Pool`] doesn't exist anymore.
+    /// If no replacement could be created, the slot is released and the
+    /// error is returned. See [`PoolError`] for details.
+    pub async fn recycle_now(mut this: Self) -> Result<Self, PoolError<M::Error>> {
+        let pool = match this.pool.upgrade() {
+            Some(pool) => pool,
+            None => return Err(PoolError::Closed),
+        };
+        let timeouts = this.timeouts;
+        this.state = ObjectState::Recycling;
+        let inner = this.obj.as_mut().unwrap();
+        let recycled = pool.pre_recycle(inner).await
+            && pool.recycle_object(&mut inner.obj, &timeouts).await.is_ok();
+        if recycled {
+            inner.metrics.record_recycle();
+            pool.stats.recycled();
+            if pool.post_recycle(&mut this, None).await {
+                this.state = ObjectState::Ready;
+                return Ok(this);
+            }
+        } else {
+            pool.stats.recycle_failed();
+        }
+        this.state = ObjectState::Creating;
+        // The old object must give back its slot of the `CapacityLimiter`
+        // before creating its replacement.
+        if let Some(old) = this.obj.take() {
+            pool.discard(old.obj, DiscardReason::RecycleFailed);
+        }
+        let _ = pool
+            .checked_out_memory
+            .fetch_sub(mem::take(&mut this.memory), Ordering::Relaxed);
+        this.obj = Some(pool.create_object(&timeouts).await?);
+        pool.post_create(&mut this).await?;
+        pool.hand_out(&mut this);
+        Ok(this)
+    }
+
+    /// Returns the tag this [`Object`] has been checked out with via
+    /// [`Pool::get_tagged()`].
+    pub fn tag(this: &Self) -> Option<&str> {
+        this.tag.as_deref()
+    }
+
+    /// Returns the secondary [`Resources`] attached to this [`Object`].
+    pub fn resources(this: &Self) -> &Resources {
+        &this.obj.as_ref().unwrap().resources
+    }
+
+    /// Returns the secondary [`Resources`] attached to this [`Object`]
+    /// mutably.
+    ///
+    /// Resources attached here are kept across checkouts and dropped when
+    /// this [`Object`] is discarded or taken from the [`Pool`].
+    pub fn resources_mut(this: &mut Self) -> &mut Resources {
+        &mut this.obj.as_mut().unwrap().resources
+    }
+
+    /// Returns the [`Metrics`] of this [`Object`].
+    pub fn metrics(this: &Self) -> &Metrics {
+        &this.obj.as_ref().unwrap().metrics
     }
 
     /// Returns the [`Pool`] this [`Object`] belongs to.
@@ -160,46 +520,115 @@ impl<M: Manager> Object<M> {
     /// come from, this can fail and return [`None`] instead.
     pub fn pool(this: &Self) -> Option<Pool<M>> {
         this.pool.upgrade().map(|inner| Pool {
+            timeouts: inner.config.timeouts,
             inner,
-            _wrapper: PhantomData::default(),
+            lane: None,
+            _wrapper: PhantomData,
         })
     }
 }
 
-impl<M: Manager> Drop for Object<M> {
-    fn drop(&mut self) {
-        if let Some(pool) = self.pool.upgrade() {
-            match self.state {
-                ObjectState::Waiting => {
-                    let _ = pool.available.fetch_add(1, Ordering::Relaxed);
+impl<M: Manager> Object<M> {
+    /// Returns this [`Object`] to the given `pool` or releases its slot
+    /// depending on its state.
+    fn release(&mut self, pool: &PoolInner<M>) {
+        let _ = pool
+            .checked_out_memory
+            .fetch_sub(self.memory, Ordering::Relaxed);
+        match self.state {
+            ObjectState::Waiting => {
+                let _ = pool.available.fetch_add(1, Ordering::Relaxed);
+            }
+            ObjectState::Receiving => {
+                let _ = pool.available.fetch_add(1, Ordering::Relaxed);
+                if !self.burst {
+                    pool.release_permit();
+                }
+            }
+            ObjectState::Creating | ObjectState::Taken => {
+                pool.decrease_size(1);
+                if !self.burst {
+                    pool.release_permit();
+                }
+            }
+            ObjectState::Recycling | ObjectState::Ready if self.burst => {
+                // Burst objects are never returned to the pool.
+                pool.decrease_size(1);
+                pool.burst_dropped.store(true, Ordering::Relaxed);
+            }
+            ObjectState::Recycling | ObjectState::Ready if self.is_broken(pool) => {
+                if let Some(obj) = self.obj.take() {
+                    pool.discard(obj.obj, DiscardReason::Broken);
                 }
-                ObjectState::Receiving => {
-                    let _ = pool.available.fetch_add(1, Ordering::Relaxed);
-                    pool.semaphore.add_permits(1);
+                pool.decrease_size(1);
+                pool.release_permit();
+                pool.return_rate.record();
+            }
+            ObjectState::Recycling | ObjectState::Ready if pool.is_oversized() => {
+                // The pool has been shrunk in the mean time.
+                if let Some(obj) = self.obj.take() {
+                    pool.discard(obj.obj, DiscardReason::Evicted);
+                }
+                pool.decrease_size(1);
+                pool.release_permit();
+                pool.return_rate.record();
+            }
+            ObjectState::Recycling | ObjectState::Ready => {
+                let _ = pool.available.fetch_add(1, Ordering::Relaxed);
+                let mut obj = self.obj.take().unwrap();
+                obj.metrics.returned = Some(Instant::now());
+                if let Some(reset) = pool.reset {
+                    reset(&mut obj.obj);
+                }
+                for hook in &pool.hooks.on_park {
+                    hook(&mut obj.obj);
                 }
-                ObjectState::Creating | ObjectState::Taken => {
-                    let _ = pool.size.fetch_sub(1, Ordering::Relaxed);
-                    pool.semaphore.add_permits(1);
+                if pool.config.thread_affinity {
+                    obj.returned_on = Some(thread::current().id());
                 }
-                ObjectState::Recycling | ObjectState::Ready => {
-                    let _ = pool.available.fetch_add(1, Ordering::Relaxed);
-                    let obj = self.obj.take().unwrap();
-                    {
-                        let mut queue = pool.queue.lock().unwrap();
+                let rejected = {
+                    let mut queue = pool.queue.lock();
+                    let ObjectInner {
+                        obj: inner,
+                        metrics,
+                        retain_epoch,
+                        ..
+                    } = &mut obj;
+                    if pool.retainers.check(inner, metrics, retain_epoch) {
                         queue.push_back(obj);
+                        None
+                    } else {
+                        Some(obj)
                     }
-                    pool.semaphore.add_permits(1);
-                    // The pool might have been closed in the mean time.
-                    // Hand over control to the `_cleanup` method which
-                    // takes care of this.
-                    pool.clean_up();
-                }
-                ObjectState::Dropped => {
-                    // The object has already been dropped.
+                };
+                if let Some(obj) = rejected {
+                    let _ = pool.available.fetch_sub(1, Ordering::Relaxed);
+                    pool.discard(obj.obj, DiscardReason::Filtered);
+                    pool.decrease_size(1);
                 }
+                pool.release_permit();
+                pool.return_rate.record();
+                // The pool might have been closed in the mean time.
+                // Hand over control to the `_cleanup` method which
+                // takes care of this.
+                pool.clean_up();
+            }
+            ObjectState::Dropped => {
+                // The object has already been dropped.
             }
         }
-        self.obj = None;
+    }
+}
+
+impl<M: Manager> Drop for Object<M> {
+    fn drop(&mut self) {
+        match self.pool.upgrade() {
+            Some(pool) => pool.drop_scope(|| {
+                self.release(&pool);
+                self.obj = None;
+            }),
+            None => self.obj = None,
+        }
         self.state = ObjectState::Dropped;
     }
 }
@@ -207,13 +636,13 @@ impl<M: Manager> Drop for Object<M> {
 impl<M: Manager> Deref for Object<M> {
     type Target = M::Type;
     fn deref(&self) -> &M::Type {
-        self.obj.as_ref().unwrap()
+        &self.obj.as_ref().unwrap().obj
     }
 }
 
 impl<M: Manager> DerefMut for Object<M> {
     fn deref_mut(&mut self) -> &mut M::Type {
-        self.obj.as_mut().unwrap()
+        &mut self.obj.as_mut().unwrap().obj
     }
 }
 
@@ -229,12 +658,181 @@ impl<M: Manager> AsMut<M::Type> for Object<M> {
     }
 }
 
+/// Information about how an [`Object`] was acquired from a [`Pool`].
+///
+/// This is returned by [`Pool::get_traced()`] and allows recording the costs
+/// of every single [`Pool::get()`] call without installing global hooks.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AcquireInfo {
+    /// Time spent waiting for a slot to become available.
+    pub waited: Duration,
+
+    /// Indicates whether a new [`Object`] has been created.
+    pub created_new: bool,
+
+    /// Indicates whether an existing [`Object`] has been recycled.
+    pub recycled: bool,
+}
+
+/// Progress of shrinking a [`Pool`] via [`Pool::resize()`].
+///
+/// This is returned by [`Pool::resize_progress()`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ResizeProgress {
+    /// Current number of [`Object`]s of the [`Pool`] including the ones
+    /// which are currently in use.
+    pub current_size: usize,
+
+    /// Maximum size the [`Pool`] has been resized to.
+    pub target_size: usize,
+}
+
+impl ResizeProgress {
+    /// Indicates whether the [`Pool`] has reached its target size.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.current_size <= self.target_size
+    }
+}
+
+/// Summary of what has been abandoned when closing a [`Pool`].
+///
+/// This is returned by [`Pool::close()`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShutdownReport {
+    /// Number of idle and quarantined [`Object`]s which have been dropped.
+    pub closed: usize,
+
+    /// Number of [`Object`]s which were still in use. These are dropped as
+    /// soon as they are returned to the [`Pool`].
+    pub checked_out: usize,
+
+    /// Number of tasks which were waiting for an [`Object`] and have been
+    /// rejected with [`PoolError::Closed`].
+    pub waiters_rejected: usize,
+
+    /// Number of [`Object`]s which were returned and dropped while
+    /// [`Pool::shutdown_blocking()`] was waiting for them. This is always
+    /// zero for [`Pool::close()`].
+    pub dropped: usize,
+
+    /// Number of [`Object`]s which were still in use when
+    /// [`Pool::shutdown_blocking()`] gave up waiting for them. This is
+    /// always zero for [`Pool::close()`].
+    pub remaining: usize,
+}
+
+/// Description of an idle [`Object`] returned by [`Pool::describe_idle()`].
+#[cfg(feature = "diagnostics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+#[derive(Clone, Debug)]
+pub struct ObjectDescription {
+    /// [`Metrics`] of the [`Object`].
+    pub metrics: Metrics,
+
+    /// Backend-specific details as returned by [`Manager::describe()`].
+    pub details: serde_json::Value,
+}
+
+/// Slot of a [`Pool`] which has been acquired without retrieving an
+/// [`Object`] yet.
+///
+/// Instances of this are created by calling the [`Pool::acquire_permit()`]
+/// method. Dropping a [`Permit`] releases the slot again.
+#[must_use]
+pub struct Permit<M: Manager, W: From<Object<M>> = Object<M>> {
+    obj: Object<M>,
+    info: AcquireInfo,
+    timeouts: Timeouts,
+    _wrapper: PhantomData<fn() -> W>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `W` type parameter.
+impl<M, W> fmt::Debug for Permit<M, W>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+    W: From<Object<M>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Permit")
+            .field("obj", &self.obj)
+            .field("info", &self.info)
+            .field("timeouts", &self.timeouts)
+            .field("wrapper", &self._wrapper)
+            .finish()
+    }
+}
+
+impl<M: Manager, W: From<Object<M>>> Permit<M, W> {
+    /// Exchanges this [`Permit`] for an [`Object`] by either recycling an
+    /// idle one or creating a new one.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get(self) -> Result<W, PoolError<M::Error>> {
+        let pool = self.obj.pool.upgrade().ok_or(PoolError::Closed)?;
+        let (obj, _) = pool.fill(self.obj, self.info, &self.timeouts).await?;
+        Ok(obj.into())
+    }
+
+    /// Exchanges this [`Permit`] for a newly created [`Object`] without
+    /// considering the idle ones.
+    ///
+    /// This allows creating the [`Object`] at a well-defined point in time,
+    /// e.g. within the transaction boundary of another system.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn create(mut self) -> Result<W, PoolError<M::Error>> {
+        let pool = self.obj.pool.upgrade().ok_or(PoolError::Closed)?;
+        pool.create_into(&mut self.obj, &self.timeouts).await?;
+        pool.hand_out(&mut self.obj);
+        Ok(self.obj.into())
+    }
+
+    /// Exchanges this [`Permit`] for an [`Object`] wrapping the given
+    /// `value`, which has been created outside of the [`Pool`].
+    ///
+    /// The `value` is treated like one created via [`Manager::create()`],
+    /// so the `post_create` hooks are applied to it and it is recycled
+    /// before being handed out again.
+    ///
+    /// Like a newly created one, it waits for a slot of the
+    /// [`CapacityLimiter`] and the `HostLimiter` the [`Pool`] is attached
+    /// to, if any. This counts towards the [`Timeouts::create`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Closed`] if the [`Pool`] doesn't exist anymore,
+    /// [`PoolError::Timeout`] if no slot becomes available in time and any
+    /// error of the `post_create` hooks. The `value` is dropped in these
+    /// cases.
+    pub async fn fulfill(mut self, value: M::Type) -> Result<W, PoolError<M::Error>> {
+        let pool = self.obj.pool.upgrade().ok_or(PoolError::Closed)?;
+        pool.adopt_into(&mut self.obj, value, &self.timeouts)
+            .await?;
+        pool.hand_out(&mut self.obj);
+        Ok(self.obj.into())
+    }
+}
+
 /// Generic object and connection pool.
 ///
 /// This struct can be cloned and transferred across thread boundaries and uses
 /// reference counting for its internal state.
+///
+/// Every handle carries its own default [`Timeouts`] which can be changed via
+/// [`Pool::with_timeouts()`] without affecting other handles of the same
+/// [`Pool`].
 pub struct Pool<M: Manager, W: From<Object<M>> = Object<M>> {
     inner: Arc<PoolInner<M>>,
+    timeouts: Timeouts,
+    /// Lane shared by all clones of a handle created via
+    /// [`Pool::fair_handle()`]. At most one task per lane waits for a slot.
+    lane: Option<Arc<Semaphore>>,
     _wrapper: PhantomData<fn() -> W>,
 }
 
@@ -248,6 +846,8 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Pool")
             .field("inner", &self.inner)
+            .field("timeouts", &self.timeouts)
+            .field("lane", &self.lane)
             .field("wrapper", &self._wrapper)
             .finish()
     }
@@ -257,7 +857,9 @@ impl<M: Manager, W: From<Object<M>>> Clone for Pool<M, W> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
-            _wrapper: PhantomData::default(),
+            timeouts: self.timeouts,
+            lane: self.lane.clone(),
+            _wrapper: PhantomData,
         }
     }
 }
@@ -270,30 +872,124 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         PoolBuilder::new(manager)
     }
 
-    pub(crate) fn from_builder(builder: PoolBuilder<M, W>) -> Self {
-        Self {
+    pub(crate) fn from_builder(builder: PoolBuilder<M, W>) -> Result<Self, BuildError<M::Error>> {
+        let mut queue = VecDeque::with_capacity(builder.config.max_size);
+        let retainers = Retainers::default();
+        for mut obj in builder.seed {
+            for hook in &builder.hooks.on_park {
+                hook(&mut obj);
+            }
+            #[allow(unused_mut)]
+            let mut resources = Resources::default();
+            #[cfg(all(unix, feature = "host_limit"))]
+            if let Some(limiter) = &builder.host_limiter {
+                let slot = limiter.try_acquire().ok_or_else(|| {
+                    BuildError::Config(
+                        "The HostLimiter has no free slot for a seeded object".to_string(),
+                    )
+                })?;
+                let _ = resources.insert(slot);
+            }
+            let limit = match &builder.capacity_limiter {
+                Some(limiter) => Some(limiter.try_acquire().ok_or_else(|| {
+                    BuildError::Config(
+                        "The CapacityLimiter has no free slot for a seeded object".to_string(),
+                    )
+                })?),
+                None => None,
+            };
+            queue.push_back(ObjectInner {
+                resources,
+                obj,
+                metrics: Metrics::new(Duration::ZERO),
+                returned_on: None,
+                retain_epoch: retainers.enter(),
+                quarantined: 0,
+                _limit: limit,
+            });
+        }
+        let seeded = queue.len();
+        let pool = Self {
+            timeouts: builder.config.timeouts,
             inner: Arc::new(PoolInner {
                 manager: Box::new(builder.manager),
-                queue: Mutex::new(VecDeque::with_capacity(builder.config.max_size)),
-                size: AtomicUsize::new(0),
-                available: AtomicIsize::new(0),
+                queue: Mutex::new(queue),
+                quarantine: Mutex::new(VecDeque::new()),
+                backend_limit_checked: AtomicBool::new(false),
+                backend_limit: Mutex::new(None),
+                shared: Mutex::new(None),
+                size: AtomicUsize::new(seeded),
+                max_size: AtomicUsize::new(builder.config.max_size),
+                size_decreased: Notify::new(),
+                permit_debt: AtomicUsize::new(0),
+                available: AtomicIsize::new(seeded as isize),
                 semaphore: Semaphore::new(builder.config.max_size),
+                create_semaphore: builder.config.max_concurrent_creates.map(Semaphore::new),
+                waiters: Waiters::default(),
+                rejections: RejectionCounters::default(),
+                return_rate: ReturnRate::default(),
+                usage: UsageHistory::default(),
+                cooldown: Cooldown::default(),
+                create_budget: CreateBudget::default(),
+                standby: AtomicBool::new(builder.config.standby),
+                burst_dropped: AtomicBool::new(false),
+                backfilling: AtomicBool::new(false),
+                checked_out_memory: AtomicUsize::new(0),
+                #[cfg(feature = "sync")]
+                pending_drops: Arc::default(),
+                stats: StatsCounters::default(),
+                experiment: Experiment::default(),
+                retainers,
+                handle: PoolHandle::new(builder.runtime),
                 config: builder.config,
                 hooks: builder.hooks,
+                interceptors: builder.interceptors,
+                reset: builder.reset,
+                eviction_policy: builder.eviction_policy,
+                random: builder.random,
+                deadline: builder.deadline,
+                on_discard: builder.on_discard,
+                on_backend_limit: builder.on_backend_limit,
+                on_task_panic: builder.on_task_panic,
+                capacity_limiter: builder.capacity_limiter,
+                #[cfg(all(unix, feature = "host_limit"))]
+                host_limiter: builder.host_limiter,
                 runtime: builder.runtime,
             }),
-            _wrapper: PhantomData::default(),
-        }
+            lane: None,
+            _wrapper: PhantomData,
+        };
+        pool.inner.manager.attach_pool(&pool.inner.handle);
+        Ok(pool)
     }
 
     /// Retrieves an [`Object`] from this [`Pool`] or waits for the one to
     /// become available.
     ///
+    /// This waits forever if no [`Timeouts::wait`] is configured. Prefer
+    /// [`Pool::get_unbounded()`] if that's intended.
+    ///
     /// # Errors
     ///
     /// See [`PoolError`] for details.
     pub async fn get(&self) -> Result<W, PoolError<M::Error>> {
-        self.timeout_get(&self.inner.config.timeouts).await
+        self.timeout_get(&self.timeouts).await
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] ignoring all the configured
+    /// [`Timeouts`], so this waits as long as it takes.
+    ///
+    /// The only ways to stop waiting are dropping the returned [`Future`],
+    /// e.g. via [`Pool::get_cancellable()`], a deadline configured via
+    /// [`PoolBuilder::deadline()`] and closing the [`Pool`]. While waiting,
+    /// the task is counted in [`PoolStats::unbounded_waiting`] and marked as
+    /// [`WaiterInfo::unbounded`].
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_unbounded(&self) -> Result<W, PoolError<M::Error>> {
+        self.timeout_get(&Timeouts::new()).await
     }
 
     /// Retrieves an [`Object`] from this [`Pool`] and doesn't wait if there is
@@ -304,7 +1000,7 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     ///
     /// See [`PoolError`] for details.
     pub async fn try_get(&self) -> Result<W, PoolError<M::Error>> {
-        let mut timeouts = self.inner.config.timeouts;
+        let mut timeouts = self.timeouts;
         timeouts.wait = Some(Duration::from_secs(0));
         self.timeout_get(&timeouts).await
     }
@@ -316,113 +1012,500 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     ///
     /// See [`PoolError`] for details.
     pub async fn timeout_get(&self, timeouts: &Timeouts) -> Result<W, PoolError<M::Error>> {
-        let _ = self.inner.available.fetch_sub(1, Ordering::Relaxed);
+        self.acquire(timeouts, None, None)
+            .await
+            .map(|(obj, _)| obj.into())
+    }
 
-        let mut obj = Object {
-            obj: None,
-            state: ObjectState::Waiting,
-            pool: Arc::downgrade(&self.inner),
-        };
+    /// Retrieves an [`Object`] from this [`Pool`] just like [`Pool::get()`]
+    /// does, but attaches the given `label` to the task while it is waiting.
+    ///
+    /// The label shows up in the result of [`Pool::waiters()`] and helps
+    /// finding out who is starving when the [`Pool`] is saturated.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_labeled(&self, label: impl Into<String>) -> Result<W, PoolError<M::Error>> {
+        self.acquire(&self.timeouts, Some(label.into()), None)
+            .await
+            .map(|(obj, _)| obj.into())
+    }
 
-        let non_blocking = match timeouts.wait {
-            Some(t) => t.as_nanos() == 0,
-            None => false,
-        };
+    /// Retrieves an [`Object`] from this [`Pool`] just like [`Pool::get()`]
+    /// does, but attaches the given `tag` naming the calling subsystem.
+    ///
+    /// While waiting, the tag is used as label just like with
+    /// [`Pool::get_labeled()`]. Once checked out, it is recorded in the
+    /// [`Object`] and can be retrieved via [`Object::tag()`], e.g. to name
+    /// the holder of long-held or leaked [`Object`]s in audit logs.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_tagged(&self, tag: impl Into<String>) -> Result<W, PoolError<M::Error>> {
+        let tag = tag.into();
+        let (mut obj, _) = self
+            .acquire(&self.timeouts, Some(tag.clone()), None)
+            .await?;
+        obj.tag = Some(tag.into());
+        Ok(obj.into())
+    }
 
-        let permit = if non_blocking {
-            self.inner.semaphore.try_acquire().map_err(|e| match e {
-                TryAcquireError::Closed => PoolError::Closed,
-                TryAcquireError::NoPermits => PoolError::Timeout(TimeoutType::Wait),
-            })?
-        } else {
-            apply_timeout(
-                self.inner.runtime,
-                TimeoutType::Wait,
-                self.inner.config.timeouts.wait,
-                async {
-                    self.inner
-                        .semaphore
-                        .acquire()
-                        .await
-                        .map_err(|_| PoolError::Closed)
-                },
-            )
-            .await?
-        };
+    /// Retrieves an [`Object`] from this [`Pool`] just like [`Pool::get()`]
+    /// does, but reports its position in the queue of waiting tasks via the
+    /// given `progress` side channel.
+    ///
+    /// This allows to display or act on expected wait times while the
+    /// [`Pool`] is saturated, e.g. by polling [`WaitProgress::status()`]
+    /// from another task.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_with_progress(
+        &self,
+        progress: &WaitProgress<M>,
+    ) -> Result<W, PoolError<M::Error>> {
+        self.acquire(&self.timeouts, None, Some(progress))
+            .await
+            .map(|(obj, _)| obj.into())
+    }
 
-        permit.forget();
+    /// Retrieves an [`Object`] from this [`Pool`] just like [`Pool::get()`]
+    /// does, but gives up as soon as the given `cancel` future completes.
+    ///
+    /// This allows request-scoped cancellation, e.g. by passing
+    /// `token.cancelled()` of a `CancellationToken`. Cancellation takes
+    /// effect while waiting for a slot as well as while an [`Object`] is
+    /// being created or recycled. Any slot or half-created [`Object`] is
+    /// released just as if the returned future had been dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Cancelled`] if `cancel` completed first. See
+    /// [`PoolError`] for all the other errors.
+    pub async fn get_cancellable(
+        &self,
+        cancel: impl Future<Output = ()>,
+    ) -> Result<W, PoolError<M::Error>> {
+        runtime::cancellable(self.acquire(&self.timeouts, None, None), cancel)
+            .await
+            .unwrap_or(Err(PoolError::Cancelled))
+            .map(|(obj, _)| obj.into())
+    }
 
-        loop {
-            obj.state = ObjectState::Receiving;
-            let inner_obj = {
-                let mut queue = self.inner.queue.lock().unwrap();
-                queue.pop_front()
-            };
-            match inner_obj {
-                Some(inner_obj) => {
-                    // Recycle existing object
-                    obj.state = ObjectState::Recycling;
-                    obj.obj = Some(inner_obj);
-                    match apply_timeout(
-                        self.inner.runtime,
-                        TimeoutType::Recycle,
-                        self.inner.config.timeouts.recycle,
-                        self.inner.manager.recycle(&mut obj),
-                    )
-                    .await
-                    {
-                        Ok(_) => {
-                            // Apply post_recycle hooks
-                            for hook in &self.inner.hooks.post_recycle {
-                                hook.post_recycle(&mut obj.obj.as_mut().unwrap())
-                                    .await
-                                    .map_err(PoolError::PostRecycleHook)?;
-                            }
-                            break;
-                        }
-                        Err(_) => {
-                            let _ = self.inner.available.fetch_sub(1, Ordering::Relaxed);
-                            let _ = self.inner.size.fetch_sub(1, Ordering::Relaxed);
-                            continue;
-                        }
-                    }
-                }
-                None => {
-                    // Create new object
-                    obj.state = ObjectState::Creating;
-                    let _ = self.inner.available.fetch_add(1, Ordering::Relaxed);
-                    let _ = self.inner.size.fetch_add(1, Ordering::Relaxed);
-                    obj.obj = Some(
-                        apply_timeout(
-                            self.inner.runtime,
-                            TimeoutType::Create,
-                            self.inner.config.timeouts.create,
-                            self.inner.manager.create(),
-                        )
-                        .await?,
-                    );
-                    // Apply post_create hooks
-                    for hook in &self.inner.hooks.post_create {
-                        hook.post_create(&mut obj.obj.as_mut().unwrap())
-                            .await
-                            .map_err(PoolError::PostCreateHook)?;
-                    }
-                    break;
-                }
+    /// Retrieves a [`SharedObject`] for read-only access to an [`Object`]
+    /// of this [`Pool`].
+    ///
+    /// As long as any [`SharedObject`] retrieved this way is alive, all
+    /// further calls share its [`Object`] instead of checking out another
+    /// one, so any number of readers occupy only a single slot.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_shared(&self) -> Result<SharedObject<M>, PoolError<M::Error>>
+    where
+        M: 'static,
+        M::Type: Send + Sync,
+    {
+        if let Some(obj) = self.shared() {
+            return Ok(obj);
+        }
+        let (obj, _) = self.acquire(&self.timeouts, None, None).await?;
+        let obj = Arc::new(obj);
+        let mut shared = self.inner.shared.lock();
+        match shared.as_ref().and_then(Weak::upgrade) {
+            // Another task was faster, so this `Object` goes back to the
+            // `Pool` right away.
+            Some(existing) => match existing.downcast() {
+                Ok(obj) => Ok(SharedObject { obj }),
+                Err(_) => unreachable!("shared object of a different type"),
+            },
+            None => {
+                let erased: Arc<dyn Any + Send + Sync> = obj.clone();
+                *shared = Some(Arc::downgrade(&erased));
+                Ok(SharedObject { obj })
             }
         }
+    }
 
-        obj.state = ObjectState::Ready;
-        Ok(obj.into())
+    /// Returns the [`SharedObject`] currently handed out via
+    /// [`Pool::get_shared()`], if any.
+    fn shared(&self) -> Option<SharedObject<M>>
+    where
+        M: 'static,
+        M::Type: Send + Sync,
+    {
+        let obj = self.inner.shared.lock().as_ref()?.upgrade()?;
+        obj.downcast().ok().map(|obj| SharedObject { obj })
     }
 
-    /// Closes this [`Pool`].
+    /// Retrieves an [`Object`] from this [`Pool`] just like [`Pool::get()`]
+    /// does, but also returns an [`AcquireInfo`] describing what it took to
+    /// get the [`Object`].
     ///
-    /// All current and future tasks waiting for [`Object`]s will return
-    /// [`PoolError::Closed`] immediately.
-    pub fn close(&self) {
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_traced(&self) -> Result<(W, AcquireInfo), PoolError<M::Error>> {
+        self.timeout_get_traced(&self.timeouts).await
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] using a different `timeout`
+    /// than the configured one and returns an [`AcquireInfo`] alongside it.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn timeout_get_traced(
+        &self,
+        timeouts: &Timeouts,
+    ) -> Result<(W, AcquireInfo), PoolError<M::Error>> {
+        self.acquire(timeouts, None, None)
+            .await
+            .map(|(obj, info)| (obj.into(), info))
+    }
+
+    /// Gets an [`Object`] calling the configured [`Interceptor`]s around
+    /// it.
+    async fn acquire(
+        &self,
+        timeouts: &Timeouts,
+        label: Option<String>,
+        progress: Option<&WaitProgress<M>>,
+    ) -> Result<(Object<M>, AcquireInfo), PoolError<M::Error>> {
+        framed!(self.intercept(self.acquire_checked(timeouts, label, progress, true))).await
+    }
+
+    /// Runs the given `get` calling the configured [`Interceptor`]s around
+    /// it.
+    ///
+    /// Every interceptor whose [`Interceptor::before_get()`] succeeded gets
+    /// to see the outcome, even if a later one rejected the attempt or
+    /// replaced the outcome with an error.
+    async fn intercept(
+        &self,
+        get: impl Future<Output = Result<(Object<M>, AcquireInfo), PoolError<M::Error>>>,
+    ) -> Result<(Object<M>, AcquireInfo), PoolError<M::Error>> {
+        let interceptors = &self.inner.interceptors;
+        if interceptors.is_empty() {
+            return get.await;
+        }
+        let mut entered = 0;
+        let mut rejected = None;
+        for interceptor in interceptors {
+            if let Err(e) = interceptor.before_get().await {
+                rejected = Some(e);
+                break;
+            }
+            entered += 1;
+        }
+        let (mut obj, mut result) = match rejected {
+            Some(e) => (None, Err(e)),
+            None => match get.await {
+                Ok((obj, info)) => (Some(obj), Ok(info)),
+                Err(e) => (None, Err(e)),
+            },
+        };
+        for interceptor in interceptors[..entered].iter().rev() {
+            if let Err(e) = interceptor.after_get(&result).await {
+                // Dropping `obj` returns it to the `Pool` right away.
+                obj = None;
+                result = Err(e);
+            }
+        }
+        match (obj, result) {
+            (Some(obj), Ok(info)) => Ok((obj, info)),
+            (_, Err(e)) => Err(e),
+            (None, Ok(_)) => unreachable!("object missing after successful get"),
+        }
+    }
+
+    /// Gets an [`Object`] honoring the standby and cooldown of this [`Pool`]
+    /// and records the outcome. Unless `fill` is set, the returned
+    /// [`Object`] is still empty and only holds a slot.
+    async fn acquire_checked(
+        &self,
+        timeouts: &Timeouts,
+        label: Option<String>,
+        progress: Option<&WaitProgress<M>>,
+        fill: bool,
+    ) -> Result<(Object<M>, AcquireInfo), PoolError<M::Error>> {
+        let attempt = self.inner.rejections.attempt();
+        if self.inner.standby.load(Ordering::Acquire) {
+            let result = Err(PoolError::Standby);
+            attempt.finish(&result);
+            return result;
+        }
+        if let Some(retry_after) = self.inner.cooldown.remaining() {
+            let result = Err(PoolError::CoolingDown { retry_after });
+            attempt.finish(&result);
+            return result;
+        }
+        let result = match self.acquire_slot(timeouts, label, progress).await {
+            Ok((obj, info)) if fill => self.inner.fill(obj, info, timeouts).await,
+            result => result,
+        };
+        attempt.finish(&result);
+        let timeout = matches!(result, Err(PoolError::Timeout(_)));
+        self.inner.record_usage(timeout);
+        if let Some(limit) = &self.inner.config.timeout_cooldown {
+            self.inner.cooldown.record(limit, timeout);
+        }
+        result
+    }
+
+    /// Acquires a slot of this [`Pool`] and returns an empty [`Object`] in
+    /// the [`ObjectState::Receiving`] state.
+    async fn acquire_slot(
+        &self,
+        timeouts: &Timeouts,
+        label: Option<String>,
+        progress: Option<&WaitProgress<M>>,
+    ) -> Result<(Object<M>, AcquireInfo), PoolError<M::Error>> {
+        let mut info = AcquireInfo::default();
+        let start = Instant::now();
+
+        // Yield before touching `available` as nothing would restore it if
+        // the caller dropped this future while it's yielding.
+        if self.inner.config.cooperative_yield && self.inner.waiters.len() > 0 {
+            runtime::yield_now().await;
+        }
+        let available = self.inner.available.fetch_sub(1, Ordering::Relaxed);
+
+        let mut obj = Object {
+            obj: None,
+            state: ObjectState::Waiting,
+            burst: false,
+            memory: 0,
+            broken: AtomicBool::new(false),
+            tag: None,
+            timeouts: *timeouts,
+            pool: Arc::downgrade(&self.inner),
+        };
+
+        let non_blocking = match timeouts.wait {
+            Some(t) => t.as_nanos() == 0,
+            None => false,
+        };
+
+        let on_exhausted = self.inner.config.on_exhausted;
+        let permit = if non_blocking || on_exhausted != OnExhausted::Wait {
+            match self.inner.semaphore.try_acquire() {
+                Ok(permit) => Some(permit),
+                Err(TryAcquireError::Closed) => return Err(PoolError::Closed),
+                Err(TryAcquireError::NoPermits) => match on_exhausted {
+                    OnExhausted::Wait => return Err(PoolError::Timeout(TimeoutType::Wait)),
+                    OnExhausted::Error => {
+                        return Err(PoolError::Exhausted {
+                            retry_after: self.inner.return_rate.retry_after(),
+                        })
+                    }
+                    OnExhausted::CreateBurst => None,
+                },
+            }
+        } else {
+            match self.inner.semaphore.try_acquire() {
+                Ok(permit) => Some(permit),
+                Err(TryAcquireError::Closed) => return Err(PoolError::Closed),
+                Err(TryAcquireError::NoPermits) => Some(
+                    self.wait_for_permit(timeouts, available, label, progress)
+                        .await?,
+                ),
+            }
+        };
+
+        match permit {
+            Some(permit) => permit.forget(),
+            None => obj.burst = true,
+        }
+        obj.state = ObjectState::Receiving;
+        info.waited = start.elapsed();
+        self.inner.stats.waited(info.waited);
+
+        Ok((obj, info))
+    }
+
+    /// Waits for a permit of the `semaphore` once none is available right
+    /// away. Only such callers count as waiters.
+    async fn wait_for_permit(
+        &self,
+        timeouts: &Timeouts,
+        available: isize,
+        label: Option<String>,
+        progress: Option<&WaitProgress<M>>,
+    ) -> Result<SemaphorePermit<'_>, PoolError<M::Error>> {
+        // Number of tasks which were already waiting ahead of this one.
+        let depth = usize::try_from(-available).unwrap_or(0);
+        let mut wait = timeouts
+            .wait
+            .map(|t| self.inner.config.wait_timeout_scaling.apply(t, depth));
+        if let Some(deadline) = self.inner.deadline.as_ref().and_then(|f| f()) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            wait = Some(wait.map_or(remaining, |t| t.min(remaining)));
+        }
+        let waiter = self.inner.waiters.register(label, wait.is_none());
+        if let Some(max) = self.inner.config.max_waiting {
+            // Checked after registering so concurrent callers can't exceed
+            // the limit together.
+            if self.inner.waiters.len() > max {
+                return Err(PoolError::QueueFull);
+            }
+        }
+        self.inner.record_usage(false);
+        let _progress = progress.map(|p| p.track(Arc::downgrade(&self.inner), waiter.id()));
+        apply_timeout(
+            self.inner.runtime,
+            TimeoutType::Wait,
+            wait,
+            framed!(async {
+                let _lane = match &self.lane {
+                    Some(lane) => Some(lane.acquire().await.map_err(|_| PoolError::Closed)?),
+                    None => None,
+                };
+                self.inner
+                    .semaphore
+                    .acquire()
+                    .await
+                    .map_err(|_| PoolError::Closed)
+            }),
+        )
+        .await
+    }
+
+    /// Acquires a slot of this [`Pool`] without retrieving an [`Object`] yet.
+    ///
+    /// The returned [`Permit`] can later be exchanged for an [`Object`] via
+    /// [`Permit::get()`]. This allows admission decisions and the actual
+    /// checkout to happen at different layers of an application. Dropping
+    /// the [`Permit`] releases the slot again.
+    ///
+    /// The configured [`Interceptor`]s are called around acquiring the slot
+    /// only, so they don't see the outcome of [`Permit::get()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn acquire_permit(&self) -> Result<Permit<M, W>, PoolError<M::Error>> {
+        let (obj, info) = self
+            .intercept(self.acquire_checked(&self.timeouts, None, None, false))
+            .await?;
+        Ok(Permit {
+            obj,
+            info,
+            timeouts: self.timeouts,
+            _wrapper: PhantomData,
+        })
+    }
+
+    /// Returns a [`CapacitySemaphore`] view of the slots of this [`Pool`].
+    #[must_use]
+    pub fn capacity(&self) -> CapacitySemaphore<M, W> {
+        CapacitySemaphore::new(self.clone())
+    }
+
+    /// Returns a new handle to this [`Pool`] which uses the given `timeouts`
+    /// by default instead of the ones of this handle.
+    ///
+    /// The returned handle shares all [`Object`]s and the capacity with this
+    /// one, so different parts of an application can use their own latency
+    /// budgets while still sharing the same connections.
+    ///
+    /// Keep in mind that using timeouts requires a [`Runtime`] to be
+    /// configured. Otherwise [`PoolError::NoRuntimeSpecified`] is returned
+    /// when trying to get an [`Object`].
+    #[must_use]
+    pub fn with_timeouts(&self, timeouts: Timeouts) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            timeouts,
+            lane: self.lane.clone(),
+            _wrapper: PhantomData,
+        }
+    }
+
+    /// Returns a new handle to this [`Pool`] which competes for slots as a
+    /// group together with all of its clones.
+    ///
+    /// Usually slots are granted in the order tasks started waiting, so a
+    /// subsystem spawning thousands of tasks can crowd out one with only a
+    /// few tasks. Only a single task of such a group waits for a slot at any
+    /// time, while the others queue up behind it. Slots are therefore handed
+    /// out round-robin between the groups and the tasks using regular
+    /// handles.
+    #[must_use]
+    pub fn fair_handle(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            timeouts: self.timeouts,
+            lane: Some(Arc::new(Semaphore::new(1))),
+            _wrapper: PhantomData,
+        }
+    }
+
+    /// Returns the default [`Timeouts`] used by this handle of the [`Pool`].
+    #[must_use]
+    pub fn timeouts(&self) -> Timeouts {
+        self.timeouts
+    }
+
+    /// Closes this [`Pool`].
+    ///
+    /// All current and future tasks waiting for [`Object`]s will return
+    /// [`PoolError::Closed`] immediately.
+    ///
+    /// Returns a [`ShutdownReport`] describing what has been abandoned by
+    /// closing the [`Pool`].
+    pub fn close(&self) -> ShutdownReport {
+        let waiters_rejected = self.inner.waiters.len();
         self.inner.semaphore.close();
-        self.inner.clean_up();
+        let closed = self.inner.clear();
+        self.inner.detach_pool();
+        ShutdownReport {
+            closed,
+            checked_out: self.inner.size.load(Ordering::Relaxed),
+            waiters_rejected,
+            dropped: 0,
+            remaining: 0,
+        }
+    }
+
+    /// Closes this [`Pool`] like [`Pool::close()`] and blocks the current
+    /// thread until all of its [`Object`]s have been dropped or the given
+    /// `timeout` expires. A `timeout` such as [`Duration::MAX`] which can't
+    /// be added to the current time waits without a deadline.
+    ///
+    /// This allows cleaning up deterministically where nothing can be
+    /// awaited, e.g. at the end of `main` or during FFI teardown. Checked
+    /// out [`Object`]s are dropped as soon as other threads return them.
+    /// With the `sync` feature this also waits for the objects of
+    /// `SyncWrapper`s dropped by this [`Pool`] which are still being dropped
+    /// on blocking threads. Outside of a runtime context they are dropped
+    /// on the current thread right away.
+    ///
+    /// This must not be called from within an async task, as it blocks the
+    /// thread which might be needed for returning the [`Object`]s.
+    pub fn shutdown_blocking(&self, timeout: Duration) -> ShutdownReport {
+        // A `timeout` too large to be represented means there's no deadline.
+        let deadline = Instant::now().checked_add(timeout);
+        let mut report = self.close();
+        loop {
+            report.remaining = self.inner.size.load(Ordering::Relaxed);
+            report.dropped = report.checked_out.saturating_sub(report.remaining);
+            let done = report.remaining == 0 && self.inner.pending_drops() == 0;
+            let now = Instant::now();
+            let left = match deadline {
+                Some(deadline) if now >= deadline => return report,
+                Some(deadline) => deadline - now,
+                None => Duration::MAX,
+            };
+            if done {
+                return report;
+            }
+            thread::sleep(left.min(Duration::from_millis(1)));
+        }
     }
 
     /// Indicates whether this [`Pool`] has been closed.
@@ -430,38 +1513,503 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         self.inner.is_closed()
     }
 
+    /// Starts handing out [`Object`]s of a [`Pool`] created as a warm
+    /// standby via [`PoolConfig::standby`].
+    ///
+    /// Returns `false` if this [`Pool`] was already active.
+    pub fn activate(&self) -> bool {
+        self.inner.standby.swap(false, Ordering::AcqRel)
+    }
+
+    /// Indicates whether this [`Pool`] is a warm standby which still
+    /// rejects all attempts to get an [`Object`].
+    ///
+    /// See [`PoolConfig::standby`] for details.
+    #[must_use]
+    pub fn is_standby(&self) -> bool {
+        self.inner.standby.load(Ordering::Acquire)
+    }
+
     /// Retrieves [`Status`] of this [`Pool`].
     #[must_use]
     pub fn status(&self) -> Status {
-        let max_size = self.inner.config.max_size;
+        let max_size = self.inner.max_size.load(Ordering::Relaxed);
         let size = self.inner.size.load(Ordering::Relaxed);
         let available = self.inner.available.load(Ordering::Relaxed);
+        let pending_blocking_drops = self.inner.pending_drops();
         Status {
             max_size,
             size,
             available,
+            rejections: self.inner.rejections.snapshot(),
+            pending_blocking_drops,
         }
     }
 
+    /// Returns the approximate memory held by this [`Pool`].
+    ///
+    /// Unlike [`Pool::status()`] this locks the idle and quarantined
+    /// [`Object`]s and calls [`Manager::memory_usage()`] for every one of
+    /// them, so it shouldn't be called on a hot path.
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.inner.memory_usage()
+    }
+
+    /// Drops up to `n` idle [`Object`]s of this [`Pool`] and returns how many
+    /// have actually been dropped.
+    ///
+    /// The [`Object`]s to drop are chosen by the configured
+    /// [`EvictionPolicy`]. [`Object`]s which are currently in use are never
+    /// affected.
+    pub fn evict_idle(&self, n: usize) -> usize {
+        self.inner.evict(n)
+    }
+
+    /// Returns the results of the [`PoolConfig::recycle_experiment`] or
+    /// [`None`] if it isn't configured.
+    #[must_use]
+    pub fn recycle_experiment(&self) -> Option<RecycleExperimentReport> {
+        self.inner
+            .config
+            .recycle_experiment
+            .map(|_| self.inner.experiment.report())
+    }
+
+    /// Returns [`PoolStats`] of this [`Pool`].
+    ///
+    /// The wait times are reset by every call, so calling this periodically
+    /// (e.g. when being scraped by a monitoring system) yields the wait times
+    /// of each period.
+    pub fn stats(&self) -> PoolStats {
+        self.inner.stats.take(
+            self.inner.rejections.snapshot(),
+            self.inner.waiters.len(),
+            self.inner.waiters.unbounded_len(),
+        )
+    }
+
+    /// Changes the maximum size of this [`Pool`] without dropping any
+    /// [`Object`]s which are currently in use.
+    ///
+    /// Growing the [`Pool`] makes the additional slots available right away.
+    /// Shrinking it drops surplus idle [`Object`]s right away and surplus
+    /// [`Object`]s which are currently in use as soon as they are returned.
+    /// No more than `max_size` [`Object`]s are handed out from then on.
+    ///
+    /// [`Status::max_size`] reflects the new size right away.
+    pub fn resize(&self, max_size: usize) {
+        self.inner.resize(max_size);
+    }
+
+    /// Returns the limit reported via [`Manager::size_limit()`] after the
+    /// first [`Object`] of this [`Pool`] has been created, if any.
+    ///
+    /// [`Pool::resize()`] doesn't take this limit into account.
+    #[must_use]
+    pub fn backend_limit(&self) -> Option<usize> {
+        *self.inner.backend_limit.lock()
+    }
+
+    /// Returns how far shrinking this [`Pool`] via [`Pool::resize()`] has
+    /// progressed.
+    #[must_use]
+    pub fn resize_progress(&self) -> ResizeProgress {
+        ResizeProgress {
+            current_size: self.inner.size.load(Ordering::Relaxed),
+            target_size: self.inner.max_size.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Waits until this [`Pool`] doesn't hold more [`Object`]s than the
+    /// maximum size set via [`Pool::resize()`].
+    ///
+    /// [`Object`]s in use are dropped when they are returned after shrinking
+    /// the [`Pool`], so this completes once enough of them have been
+    /// returned. It completes right away if the [`Pool`] isn't oversized.
+    pub async fn resized(&self) {
+        loop {
+            let decreased = self.inner.size_decreased.notified();
+            if !self.inner.is_oversized() {
+                return;
+            }
+            decreased.await;
+        }
+    }
+
+    /// Drops all idle [`Object`]s of this [`Pool`] for which the given
+    /// predicate returns `false` and returns how many have been dropped.
+    ///
+    /// The predicate receives the [`Metrics`] of each [`Object`], e.g. to
+    /// purge all connections created before a failover. [`Object`]s which
+    /// are currently checked out are checked against it once they are
+    /// returned and dropped instead if it returns `false`. [`Object`]s
+    /// which are still being created at the time of this call are checked
+    /// as well.
+    ///
+    /// The predicate is called while the [`Pool`] is locked, so it must be
+    /// fast and must not use the [`Pool`] itself.
+    pub fn retain<F>(&self, f: F) -> usize
+    where
+        F: Fn(&M::Type, &Metrics) -> bool + Send + Sync + 'static,
+    {
+        self.inner.retain(Arc::new(f))
+    }
+
+    /// Drops all idle [`Object`]s of this [`Pool`] exceeding the
+    /// [`PoolConfig::max_lifetime`] or the [`PoolConfig::idle_timeout`] and
+    /// returns how many have been dropped.
+    ///
+    /// This is what the reaper spawned via [`Pool::spawn_reaper()`] does
+    /// periodically.
+    pub fn reap(&self) -> usize {
+        self.inner.reap()
+    }
+
+    /// Summarizes the usage of this [`Pool`] over the given recent `window`
+    /// of time, e.g. to find a fitting [`PoolConfig::max_size`].
+    ///
+    /// The usage is tracked with a granularity of one second for up to one
+    /// hour.
+    #[must_use]
+    pub fn capacity_report(&self, window: Duration) -> CapacityReport {
+        self.inner
+            .usage
+            .report(window, self.inner.max_size.load(Ordering::Relaxed))
+    }
+
+    /// Returns summaries of all the tasks currently waiting for a slot of
+    /// this [`Pool`], ordered by the time they started waiting.
+    ///
+    /// Tasks using [`Pool::try_get()`] or a non-waiting [`OnExhausted`]
+    /// policy never wait and therefore never show up here.
+    #[must_use]
+    pub fn waiters(&self) -> Vec<WaiterInfo> {
+        self.inner.waiters.list()
+    }
+
+    /// Describes all the idle [`Object`]s of this [`Pool`] using
+    /// [`Manager::describe()`].
+    ///
+    /// [`Object`]s which are currently in use are not part of the result.
+    #[cfg(feature = "diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+    #[must_use]
+    pub fn describe_idle(&self) -> Vec<ObjectDescription> {
+        let queue = self.inner.queue.lock();
+        queue
+            .iter()
+            .map(|inner| ObjectDescription {
+                metrics: inner.metrics,
+                details: self.inner.manager.describe(&inner.obj),
+            })
+            .collect()
+    }
+
     /// Returns [`Manager`] of this [`Pool`].
     #[must_use]
     pub fn manager(&self) -> &M {
-        &*self.inner.manager
+        &self.inner.manager
+    }
+
+    /// Creates and recycles a temporary object reporting the outcome and
+    /// duration of every step.
+    ///
+    /// This verifies that the configuration and credentials of the
+    /// [`Manager`] actually work, e.g. for deep health checks or at boot
+    /// time. The temporary object is never handed out and discarded right
+    /// afterwards with [`DiscardReason::SelfTest`]. It neither takes a slot
+    /// of this [`Pool`] nor counts against the [`PoolConfig::create_limit`]
+    /// and isn't retried via the [`PoolConfig::create_retry`], but it takes
+    /// one of the [`CapacityLimiter`] and the [`Timeouts`] of this
+    /// [`Pool`] apply. No hooks are run on it.
+    pub async fn self_test(&self) -> SelfTestReport<M::Error> {
+        self.inner.self_test(&self.timeouts, None).await
+    }
+
+    /// Runs [`Pool::self_test()`] additionally applying the given `check`
+    /// to the temporary object after recycling it.
+    #[cfg(feature = "sync")]
+    pub(crate) async fn self_test_with(
+        &self,
+        check: SelfTestCheck<'_, M::Type>,
+    ) -> SelfTestReport<M::Error> {
+        self.inner.self_test(&self.timeouts, Some(check)).await
+    }
+
+    /// Makes sure this [`Pool`] holds at least `n` idle [`Object`]s by
+    /// creating the missing ones one after another.
+    ///
+    /// Existing idle [`Object`]s are left alone and nothing is checked out,
+    /// so this doesn't show up in the [`PoolStats`]. This stops early
+    /// without an error once all slots of this [`Pool`] are in use, so it
+    /// never waits for other tasks.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of creating an [`Object`] or
+    /// [`PoolError::CoolingDown`] if the [`Pool`] is in a cooldown.
+    pub async fn warm_up(&self, n: usize) -> Result<(), PoolError<M::Error>> {
+        let missing = n.saturating_sub(self.inner.queue.lock().len());
+        for _ in 0..missing {
+            if !self.inner.create_idle(&self.timeouts).await? {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<M, W> Pool<M, W>
+where
+    M: Manager + 'static,
+    M::Type: Send,
+    M::Error: Send + Sync,
+    W: From<Object<M>>,
+{
+    /// Spawns a background task which calls [`Pool::reap()`] every
+    /// `interval` until this [`Pool`] is dropped.
+    ///
+    /// If a [`PoolConfig::min_idle`] is configured, the task also creates
+    /// the missing idle [`Object`]s right away and on every run via
+    /// [`Pool::warm_up()`]. Once burst [`Object`]s have been dropped, they
+    /// are created one at a time instead if a
+    /// [`PoolConfig::backfill_interval`] is configured.
+    ///
+    /// While the [`Pool`] is a warm standby, every idle [`Object`] is also
+    /// recycled on every run and replaced if that fails, see
+    /// [`PoolConfig::standby`].
+    ///
+    /// Idle [`Object`]s exceeding the [`PoolConfig::max_lifetime`] before
+    /// the next run are replaced ahead of time: their replacements are
+    /// created before they are reaped, so the number of idle [`Object`]s
+    /// doesn't dip below the [`PoolConfig::min_idle`] meanwhile.
+    ///
+    /// The task is spawned via the configured [`Runtime`], so with
+    /// `Runtime::Tokio1` this must be called from within a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::NoRuntimeSpecified`] if this [`Pool`] has no
+    /// [`Runtime`].
+    pub fn spawn_reaper(&self, interval: Duration) -> Result<(), PoolError<M::Error>> {
+        let runtime = self.inner.runtime.ok_or(PoolError::NoRuntimeSpecified)?;
+        let pool = Arc::downgrade(&self.inner);
+        let timeouts = self.timeouts;
+        let on_panic = self.inner.on_task_panic.clone();
+        runtime.spawn(report_panics(
+            BackgroundTask::Reaper,
+            on_panic,
+            async move {
+                loop {
+                    pre_replace(&pool, &timeouts, interval).await;
+                    let warm_up = match pool.upgrade() {
+                        Some(inner) => {
+                            let _ = inner.reap();
+                            let min_idle = inner.config.min_idle;
+                            match inner.config.backfill_interval {
+                                _ if min_idle == 0 => false,
+                                _ if inner.backfilling.load(Ordering::Relaxed) => false,
+                                Some(interval)
+                                    if inner.burst_dropped.swap(false, Ordering::Relaxed) =>
+                                {
+                                    inner.backfilling.store(true, Ordering::Relaxed);
+                                    runtime.spawn(report_panics(
+                                        BackgroundTask::Backfill,
+                                        inner.on_task_panic.clone(),
+                                        backfill(
+                                            pool.clone(),
+                                            runtime,
+                                            timeouts,
+                                            min_idle,
+                                            interval,
+                                        ),
+                                    ));
+                                    false
+                                }
+                                _ => true,
+                            }
+                        }
+                        None => break,
+                    };
+                    if warm_up {
+                        top_up(&pool, &timeouts).await;
+                    }
+                    if let Some(inner) = pool.upgrade() {
+                        if inner.standby.load(Ordering::Acquire) {
+                            inner.validate_idle(&timeouts).await;
+                        }
+                    }
+                    runtime.sleep(interval).await;
+                }
+            },
+        ));
+        Ok(())
+    }
+}
+
+/// Creates the missing [`PoolConfig::min_idle`] [`Object`]s of the given
+/// `pool` right away.
+///
+/// The `pool` is only upgraded for one creation at a time so the reaper
+/// doesn't keep a dropped [`Pool`] alive while warming it up.
+async fn top_up<M: Manager>(pool: &Weak<PoolInner<M>>, timeouts: &Timeouts) {
+    while let Some(inner) = pool.upgrade() {
+        if inner.queue.lock().len() >= inner.config.min_idle
+            || !matches!(inner.create_idle(timeouts).await, Ok(true))
+        {
+            break;
+        }
+    }
+}
+
+/// Creates replacements for the idle [`Object`]s of the given `pool` which
+/// exceed the [`PoolConfig::max_lifetime`] within the given `horizon`, until
+/// the ones outlasting it satisfy the [`PoolConfig::min_idle`].
+///
+/// Idle [`Object`]s missing for other reasons are left to `top_up()` and
+/// `backfill()`.
+async fn pre_replace<M: Manager>(
+    pool: &Weak<PoolInner<M>>,
+    timeouts: &Timeouts,
+    horizon: Duration,
+) {
+    while let Some(inner) = pool.upgrade() {
+        if inner.missing_replacements(horizon) == 0
+            || !matches!(inner.create_idle(timeouts).await, Ok(true))
+        {
+            break;
+        }
+    }
+}
+
+/// Creates the missing idle [`Object`]s of the given `pool` one at a time
+/// as configured via [`PoolConfig::backfill_interval`].
+async fn backfill<M: Manager>(
+    pool: Weak<PoolInner<M>>,
+    runtime: Runtime,
+    timeouts: Timeouts,
+    min_idle: usize,
+    interval: Duration,
+) {
+    let _guard = BackfillGuard(pool.clone());
+    while let Some(inner) = pool.upgrade() {
+        // Stop once there is no slot left for creating another one.
+        if inner.queue.lock().len() >= min_idle
+            || !matches!(inner.create_idle(&timeouts).await, Ok(true))
+        {
+            break;
+        }
+        drop(inner);
+        runtime.sleep(interval).await;
+    }
+}
+
+/// Resets [`PoolInner::backfilling`] once the [`backfill()`] task is over,
+/// even if it panicked.
+struct BackfillGuard<M: Manager>(Weak<PoolInner<M>>);
+
+impl<M: Manager> Drop for BackfillGuard<M> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.0.upgrade() {
+            inner.backfilling.store(false, Ordering::Relaxed);
+        }
     }
 }
 
 struct PoolInner<M: Manager> {
     manager: Box<M>,
-    queue: Mutex<VecDeque<M::Type>>,
+    queue: Mutex<VecDeque<ObjectInner<M>>>,
+    /// Objects which failed to be recycled together with the instant they
+    /// may be retried as configured via [`PoolConfig::quarantine`], or
+    /// [`None`] if the delay is too long to ever be over.
+    quarantine: Mutex<VecDeque<(Option<Instant>, ObjectInner<M>)>>,
+    /// Set once [`Manager::size_limit()`] has been called.
+    backend_limit_checked: AtomicBool,
+    /// Limit reported via [`Manager::size_limit()`].
+    backend_limit: Mutex<Option<usize>>,
+    /// [`Object`] currently handed out via [`Pool::get_shared()`].
+    ///
+    /// It is type-erased so the [`Pool`] doesn't require the
+    /// [`Manager::Type`] to be [`Sync`] unless it is actually shared.
+    shared: Mutex<Option<Weak<dyn Any + Send + Sync>>>,
     size: AtomicUsize,
+    /// Current maximum size which starts at the [`PoolConfig::max_size`] and
+    /// can be changed via [`Pool::resize()`].
+    max_size: AtomicUsize,
+    /// Notified whenever the `size` decreases.
+    size_decreased: Notify,
+    /// Number of permits of the `semaphore` which still need to be removed
+    /// after shrinking the [`Pool`] because they were in use at that time.
+    permit_debt: AtomicUsize,
     /// Number of available [`Object`]s in the [`Pool`]. If there are no
     /// [`Object`]s in the [`Pool`] this number can become negative and store
     /// the number of [`Future`]s waiting for an [`Object`].
     available: AtomicIsize,
     semaphore: Semaphore,
+    /// Limits the number of concurrent [`Manager::create()`] calls as
+    /// configured via [`PoolConfig::max_concurrent_creates`].
+    create_semaphore: Option<Semaphore>,
+    waiters: Waiters,
+    rejections: RejectionCounters,
+    return_rate: ReturnRate,
+    usage: UsageHistory,
+    cooldown: Cooldown,
+    /// Creations counted against the [`PoolConfig::create_limit`].
+    create_budget: CreateBudget,
+    /// Set while rejecting all checkouts as configured via
+    /// [`PoolConfig::standby`] until [`Pool::activate()`] is called.
+    standby: AtomicBool,
+    /// Set once a burst [`Object`] has been dropped until the reaper starts
+    /// the [`PoolConfig::backfill_interval`] backfilling.
+    burst_dropped: AtomicBool,
+    /// Set while the [`PoolConfig::backfill_interval`] backfilling runs.
+    backfilling: AtomicBool,
+    /// Sum of the [`Manager::memory_usage()`] of all checked out
+    /// [`Object`]s.
+    checked_out_memory: AtomicUsize,
+    /// Number of `SyncWrapper`s dropped by this pool which are still
+    /// waiting to be dropped on a blocking thread.
+    #[cfg(feature = "sync")]
+    pending_drops: Arc<AtomicUsize>,
+    stats: StatsCounters,
+    experiment: Experiment,
+    /// Predicates of [`Pool::retain()`] still pending for checked out
+    /// [`Object`]s.
+    retainers: Retainers<M::Type>,
+    handle: PoolHandle,
     config: PoolConfig,
     runtime: Option<Runtime>,
     hooks: hooks::Hooks<M>,
+    /// Interceptors called around every attempt to get an [`Object`] as
+    /// configured via [`PoolBuilder::interceptor()`].
+    interceptors: Vec<Box<dyn Interceptor<M::Error>>>,
+    /// Function called on every returned [`Object`] as configured via
+    /// [`PoolBuilder::reset_on_return()`].
+    reset: Option<fn(&mut M::Type)>,
+    eviction_policy: Box<dyn EvictionPolicy>,
+    /// Source of all random decisions as configured via
+    /// [`PoolBuilder::random()`].
+    random: Box<dyn RandomSource>,
+    /// Function returning the deadline of the current request as
+    /// configured via [`PoolBuilder::deadline()`].
+    deadline: Option<DeadlineExtractor>,
+    /// Callback receiving discarded objects as configured via
+    /// [`PoolBuilder::on_discard()`].
+    on_discard: Option<OnDiscard<M::Type>>,
+    /// Callback receiving [`BackendLimitExceeded`] reports as configured via
+    /// [`PoolBuilder::on_backend_limit()`].
+    on_backend_limit: Option<OnBackendLimit>,
+    /// Callback receiving the panics of background tasks as configured via
+    /// [`PoolBuilder::on_task_panic()`].
+    on_task_panic: Option<OnTaskPanic>,
+    /// Limit shared with other [`Pool`]s as configured via
+    /// [`PoolBuilder::capacity_limiter()`].
+    capacity_limiter: Option<CapacityLimiter>,
+    /// Limit shared with other processes as configured via
+    /// [`PoolBuilder::host_limiter()`].
+    #[cfg(all(unix, feature = "host_limit"))]
+    host_limiter: Option<HostLimiter>,
 }
 
 // Implemented manually to avoid unnecessary trait bound on the struct.
@@ -471,16 +2019,54 @@ where
     M::Type: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("PoolInner")
+        let mut f = f.debug_struct("PoolInner");
+        let _ = f
             .field("manager", &self.manager)
             .field("queue", &self.queue)
+            .field("quarantine", &self.quarantine)
+            .field("backend_limit", &self.backend_limit)
+            .field("shared", &self.shared)
             .field("size", &self.size)
             .field("available", &self.available)
             .field("semaphore", &self.semaphore)
+            .field("create_semaphore", &self.create_semaphore)
+            .field("waiters", &self.waiters)
+            .field("rejections", &self.rejections)
+            .field("return_rate", &self.return_rate)
+            .field("usage", &self.usage)
+            .field("cooldown", &self.cooldown)
+            .field("create_budget", &self.create_budget)
+            .field("standby", &self.standby)
+            .field("burst_dropped", &self.burst_dropped)
+            .field("backfilling", &self.backfilling)
+            .field("checked_out_memory", &self.checked_out_memory)
+            .field("stats", &self.stats)
+            .field("experiment", &self.experiment)
+            .field("retainers", &self.retainers)
+            .field("handle", &self.handle)
             .field("config", &self.config)
             .field("runtime", &self.runtime)
             .field("hooks", &self.hooks)
-            .finish()
+            .field("interceptors", &self.interceptors)
+            .field("reset", &self.reset.is_some())
+            .field("eviction_policy", &self.eviction_policy)
+            .field("random", &self.random)
+            .field("deadline", &self.deadline.is_some())
+            .field("on_discard", &self.on_discard.is_some())
+            .field("on_backend_limit", &self.on_backend_limit.is_some())
+            .field("on_task_panic", &self.on_task_panic.is_some())
+            .field("capacity_limiter", &self.capacity_limiter);
+        #[cfg(feature = "sync")]
+        let _ = f.field("pending_drops", &self.pending_drops);
+        #[cfg(all(unix, feature = "host_limit"))]
+        let _ = f.field("host_limiter", &self.host_limiter);
+        f.finish()
+    }
+}
+
+impl<M: Manager> Drop for PoolInner<M> {
+    fn drop(&mut self) {
+        self.detach_pool();
     }
 }
 
@@ -492,18 +2078,289 @@ impl<M: Manager> PoolInner<M> {
     /// don't contain any [`Object`]s.
     fn clean_up(&self) {
         if self.is_closed() {
-            self.clear();
+            let _ = self.clear();
+        }
+    }
+
+    /// Decreases the `size` of this [`Pool`] by `n`.
+    fn decrease_size(&self, n: usize) {
+        let _ = self.size.fetch_sub(n, Ordering::Relaxed);
+        self.size_decreased.notify_waiters();
+    }
+
+    /// Returns a permit to the `semaphore` unless it is needed to pay off
+    /// the `permit_debt` of a previous [`Pool::resize()`].
+    fn release_permit(&self) {
+        if !self.pay_permit_debt() {
+            self.semaphore.add_permits(1);
         }
     }
 
-    /// Removes all the [`Object`]s which are currently part of this [`Pool`].
-    fn clear(&self) {
-        let mut queue = self.queue.lock().unwrap();
-        let _ = self.size.fetch_sub(queue.len(), Ordering::Relaxed);
-        let _ = self
-            .available
-            .fetch_sub(queue.len() as isize, Ordering::Relaxed);
-        queue.clear();
+    /// Decrements the `permit_debt` and returns whether there was any.
+    fn pay_permit_debt(&self) -> bool {
+        let mut debt = self.permit_debt.load(Ordering::Relaxed);
+        while debt > 0 {
+            match self.permit_debt.compare_exchange_weak(
+                debt,
+                debt - 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(current) => debt = current,
+            }
+        }
+        false
+    }
+
+    /// Indicates whether this [`Pool`] holds more objects than its current
+    /// maximum size allows.
+    fn is_oversized(&self) -> bool {
+        self.size.load(Ordering::Relaxed) > self.max_size.load(Ordering::Relaxed)
+    }
+
+    /// Changes the maximum size of this [`Pool`] to `max_size`.
+    fn resize(&self, max_size: usize) {
+        let old = self.max_size.swap(max_size, Ordering::Relaxed);
+        if max_size > old {
+            let mut grow = max_size - old;
+            while grow > 0 && self.pay_permit_debt() {
+                grow -= 1;
+            }
+            self.semaphore.add_permits(grow);
+        } else {
+            let mut shrink = old - max_size;
+            while shrink > 0 {
+                let n = shrink.min(self.semaphore.available_permits());
+                if n == 0 {
+                    break;
+                }
+                match self
+                    .semaphore
+                    .try_acquire_many(u32::try_from(n).unwrap_or(u32::MAX))
+                {
+                    Ok(permits) => {
+                        permits.forget();
+                        shrink -= n;
+                    }
+                    Err(TryAcquireError::Closed) => return,
+                    Err(TryAcquireError::NoPermits) => {}
+                }
+            }
+            let _ = self.permit_debt.fetch_add(shrink, Ordering::Relaxed);
+            let surplus = self.size.load(Ordering::Relaxed).saturating_sub(max_size);
+            let _ = self.evict(surplus);
+        }
+    }
+
+    /// Removes all the [`Object`]s which are currently part of this [`Pool`]
+    /// and returns their number.
+    fn clear(&self) -> usize {
+        let removed: Vec<_> = {
+            let mut queue = self.queue.lock();
+            self.decrease_size(queue.len());
+            let _ = self
+                .available
+                .fetch_sub(queue.len() as isize, Ordering::Relaxed);
+            queue.drain(..).collect()
+        };
+        let quarantined: Vec<_> = self.quarantine.lock().drain(..).collect();
+        self.release_quarantined(quarantined.len());
+        let len = removed.len() + quarantined.len();
+        for obj in removed
+            .into_iter()
+            .chain(quarantined.into_iter().map(|(_, obj)| obj))
+        {
+            self.discard(obj.obj, DiscardReason::Closed);
+        }
+        len
+    }
+
+    /// Records the current usage of this [`Pool`] in its [`UsageHistory`].
+    fn record_usage(&self, timeout: bool) {
+        let checked_out = (self.max_size.load(Ordering::Relaxed)
+            + self.permit_debt.load(Ordering::Relaxed))
+        .saturating_sub(self.semaphore.available_permits());
+        self.usage.record(checked_out, self.waiters.len(), timeout);
+    }
+
+    /// Records the `limit` reported via [`Manager::size_limit()`] and
+    /// reports or clamps a maximum size exceeding it.
+    fn apply_backend_limit(&self, limit: usize) {
+        *self.backend_limit.lock() = Some(limit);
+        let max_size = self.max_size.load(Ordering::Relaxed);
+        if max_size <= limit {
+            return;
+        }
+        let clamped = self.config.clamp_to_backend_limit;
+        if clamped {
+            self.resize(limit);
+        }
+        if let Some(on_backend_limit) = &self.on_backend_limit {
+            on_backend_limit(BackendLimitExceeded {
+                max_size,
+                limit,
+                clamped,
+            });
+        }
+    }
+
+    /// Hands the given discarded `obj` over to the callback configured via
+    /// [`PoolBuilder::on_discard()`] or simply drops it.
+    fn discard(&self, obj: M::Type, reason: DiscardReason) {
+        self.drop_scope(|| match &self.on_discard {
+            Some(on_discard) => on_discard(obj, reason),
+            None => drop(obj),
+        });
+    }
+
+    /// Runs `f` counting the `SyncWrapper`s it drops in the
+    /// `pending_drops` of this pool.
+    fn drop_scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        #[cfg(feature = "sync")]
+        {
+            sync::attribute_drops(&self.pending_drops, self.on_task_panic.as_ref(), f)
+        }
+        #[cfg(not(feature = "sync"))]
+        {
+            f()
+        }
+    }
+
+    /// Returns the number of `SyncWrapper`s dropped by this pool which are
+    /// still waiting to be dropped on a blocking thread.
+    fn pending_drops(&self) -> usize {
+        #[cfg(feature = "sync")]
+        {
+            self.pending_drops.load(Ordering::Relaxed)
+        }
+        #[cfg(not(feature = "sync"))]
+        {
+            0
+        }
+    }
+
+    /// Removes up to `n` idle [`Object`]s chosen by the configured
+    /// [`EvictionPolicy`] and returns their number.
+    fn evict(&self, n: usize) -> usize {
+        let removed = {
+            let mut queue = self.queue.lock();
+            let n = n.min(queue.len());
+            let mut order: Vec<usize> = (0..queue.len()).collect();
+            order.sort_by(|&a, &b| {
+                self.eviction_policy
+                    .compare(&queue[a].metrics, &queue[b].metrics)
+            });
+            let mut evict = vec![false; queue.len()];
+            for &i in &order[..n] {
+                evict[i] = true;
+            }
+            let mut removed = Vec::with_capacity(n);
+            for (obj, evict) in mem::take(&mut *queue).into_iter().zip(evict) {
+                if evict {
+                    removed.push(obj);
+                } else {
+                    queue.push_back(obj);
+                }
+            }
+            self.decrease_size(n);
+            let _ = self.available.fetch_sub(n as isize, Ordering::Relaxed);
+            removed
+        };
+        let n = removed.len();
+        for obj in removed {
+            self.discard(obj.obj, DiscardReason::Evicted);
+        }
+        n
+    }
+
+    /// Drops all idle [`Object`]s rejected by the given `filter` and stores it
+    /// for the checked out ones. Returns the number of dropped [`Object`]s.
+    fn retain(&self, filter: RetainFilter<M::Type>) -> usize {
+        let removed = {
+            let mut queue = self.queue.lock();
+            self.retainers.push(filter.clone());
+            let mut removed = Vec::new();
+            for mut obj in mem::take(&mut *queue) {
+                if filter(&obj.obj, &obj.metrics) {
+                    self.retainers.advance(&mut obj.retain_epoch);
+                    queue.push_back(obj);
+                } else {
+                    removed.push(obj);
+                }
+            }
+            self.decrease_size(removed.len());
+            let _ = self
+                .available
+                .fetch_sub(removed.len() as isize, Ordering::Relaxed);
+            removed
+        };
+        let n = removed.len();
+        for obj in removed {
+            self.discard(obj.obj, DiscardReason::Filtered);
+        }
+        n
+    }
+
+    /// Drops all idle and quarantined [`Object`]s exceeding the
+    /// [`PoolConfig::max_lifetime`] or the [`PoolConfig::idle_timeout`] and
+    /// returns their number. Quarantined ones are also dropped once no caller
+    /// retried them for the remaining [`Quarantine::max_retries`] delays.
+    fn reap(&self) -> usize {
+        let removed = {
+            let mut queue = self.queue.lock();
+            let mut removed = Vec::new();
+            for obj in mem::take(&mut *queue) {
+                if self.config.is_expired(&obj.metrics) {
+                    removed.push(obj);
+                } else {
+                    queue.push_back(obj);
+                }
+            }
+            self.decrease_size(removed.len());
+            let _ = self
+                .available
+                .fetch_sub(removed.len() as isize, Ordering::Relaxed);
+            let mut quarantine = self.quarantine.lock();
+            let len = removed.len();
+            for (until, obj) in mem::take(&mut *quarantine) {
+                if self.config.is_expired(&obj.metrics) || self.is_quarantine_expired(until) {
+                    removed.push(obj);
+                } else {
+                    quarantine.push_back((until, obj));
+                }
+            }
+            self.release_quarantined(removed.len() - len);
+            removed
+        };
+        let n = removed.len();
+        for obj in removed {
+            self.discard(obj.obj, DiscardReason::Expired);
+        }
+        n
+    }
+
+    /// Returns the number of idle objects exceeding the
+    /// [`PoolConfig::max_lifetime`] within the given `horizon` which need a
+    /// replacement to keep the [`PoolConfig::min_idle`].
+    fn missing_replacements(&self, horizon: Duration) -> usize {
+        let max_lifetime = match self.config.max_lifetime {
+            Some(max_lifetime) => max_lifetime,
+            None => return 0,
+        };
+        let queue = self.queue.lock();
+        let lasting = queue
+            .iter()
+            .filter(|obj| obj.metrics.age() + horizon < max_lifetime)
+            .count();
+        (queue.len() - lasting).min(self.config.min_idle.saturating_sub(lasting))
+    }
+
+    /// Calls [`Manager::detach_pool()`] unless it has been called already.
+    fn detach_pool(&self) {
+        if self.handle.close() {
+            self.manager.detach_pool();
+        }
     }
 
     /// Indicates whether this [`Pool`] has been closed.
@@ -513,6 +2370,627 @@ impl<M: Manager> PoolInner<M> {
             Err(TryAcquireError::Closed)
         )
     }
+
+    /// Fills the given empty [`Object`] holding a slot of this [`Pool`] by
+    /// either recycling an idle object or creating a new one.
+    async fn fill(
+        &self,
+        mut obj: Object<M>,
+        mut info: AcquireInfo,
+        timeouts: &Timeouts,
+    ) -> Result<(Object<M>, AcquireInfo), PoolError<M::Error>> {
+        loop {
+            obj.state = ObjectState::Receiving;
+            let inner_obj = if obj.burst {
+                None
+            } else if let Some(inner_obj) = self.take_quarantined() {
+                // Quarantined objects which are due are retried first, as
+                // they would otherwise linger while idle ones are available.
+                Some(inner_obj)
+            } else {
+                let mut queue = self.queue.lock();
+                let lifo = self.config.queue_mode == QueueMode::Lifo;
+                let local = if self.config.thread_affinity {
+                    let id = thread::current().id();
+                    let mut iter = queue.iter();
+                    let is_local = |inner: &ObjectInner<M>| inner.returned_on == Some(id);
+                    if lifo {
+                        iter.rposition(is_local)
+                    } else {
+                        iter.position(is_local)
+                    }
+                    .and_then(|i| queue.remove(i))
+                } else {
+                    None
+                };
+                local.or_else(|| {
+                    if lifo {
+                        queue.pop_back()
+                    } else {
+                        queue.pop_front()
+                    }
+                })
+            };
+            let inner_obj = match inner_obj {
+                Some(inner_obj) if self.config.is_expired(&inner_obj.metrics) => {
+                    self.discard(inner_obj.obj, DiscardReason::Expired);
+                    let _ = self.available.fetch_sub(1, Ordering::Relaxed);
+                    self.decrease_size(1);
+                    continue;
+                }
+                Some(inner_obj) => match self.unpark(inner_obj) {
+                    Some(inner_obj) => Some(inner_obj),
+                    None => continue,
+                },
+                None => None,
+            };
+            match inner_obj {
+                Some(inner_obj) => {
+                    // Recycle existing object
+                    obj.state = ObjectState::Recycling;
+                    let idle = inner_obj.metrics.idle();
+                    let sampled = match &self.config.recycle_experiment {
+                        Some(experiment) if self.experiment.sample(experiment) => {
+                            Some(idle < experiment.freshness)
+                        }
+                        _ => None,
+                    };
+                    obj.obj = Some(inner_obj);
+                    let accepted = self.pre_recycle(obj.obj.as_mut().unwrap()).await;
+                    let recycled =
+                        accepted && self.recycle_object(&mut obj, timeouts).await.is_ok();
+                    if let Some(fresh) = sampled {
+                        self.experiment.record(fresh, recycled);
+                    }
+                    if recycled {
+                        info.recycled = true;
+                        self.stats.recycled();
+                        let inner_obj = obj.obj.as_mut().unwrap();
+                        inner_obj.quarantined = 0;
+                        inner_obj.metrics.record_recycle();
+                        if self.post_recycle(&mut obj, Some(idle)).await {
+                            break;
+                        }
+                        if let Some(inner_obj) = obj.obj.take() {
+                            self.discard(inner_obj.obj, DiscardReason::RecycleFailed);
+                        }
+                        let _ = self.available.fetch_sub(1, Ordering::Relaxed);
+                        self.decrease_size(1);
+                        continue;
+                    } else {
+                        let quarantined = match obj.obj.take() {
+                            Some(inner_obj) if accepted => self.quarantine(inner_obj),
+                            Some(inner_obj) => {
+                                self.discard(inner_obj.obj, DiscardReason::RecycleFailed);
+                                false
+                            }
+                            None => false,
+                        };
+                        self.stats.recycle_failed();
+                        let _ = self.available.fetch_sub(1, Ordering::Relaxed);
+                        if !quarantined {
+                            self.decrease_size(1);
+                        }
+                        continue;
+                    }
+                }
+                None => {
+                    self.create_into(&mut obj, timeouts).await?;
+                    info.created_new = true;
+                    break;
+                }
+            }
+        }
+
+        self.hand_out(&mut obj);
+        Ok((obj, info))
+    }
+
+    /// Marks the given filled `obj` as ready counting its memory as checked
+    /// out.
+    fn hand_out(&self, obj: &mut Object<M>) {
+        obj.state = ObjectState::Ready;
+        if let Some(inner) = &obj.obj {
+            obj.memory = self.manager.memory_usage(&inner.obj);
+            let _ = self
+                .checked_out_memory
+                .fetch_add(obj.memory, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the approximate memory held by this pool.
+    // `size_of()` is only part of the prelude since Rust 1.80.
+    #[allow(unused_qualifications)]
+    fn memory_usage(&self) -> MemoryUsage {
+        let (queue_memory, idle_objects) = {
+            let queue = self.queue.lock();
+            (
+                queue.capacity() * mem::size_of::<ObjectInner<M>>(),
+                queue
+                    .iter()
+                    .map(|inner| self.manager.memory_usage(&inner.obj))
+                    .sum::<usize>(),
+            )
+        };
+        let (quarantine_memory, quarantined_objects) = {
+            let quarantine = self.quarantine.lock();
+            (
+                quarantine.capacity() * mem::size_of::<(Option<Instant>, ObjectInner<M>)>(),
+                quarantine
+                    .iter()
+                    .map(|(_, inner)| self.manager.memory_usage(&inner.obj))
+                    .sum::<usize>(),
+            )
+        };
+        MemoryUsage {
+            bookkeeping: mem::size_of::<Self>()
+                + mem::size_of_val(&*self.manager)
+                + queue_memory
+                + quarantine_memory,
+            objects: idle_objects
+                + quarantined_objects
+                + self.checked_out_memory.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Puts the given `obj` which failed to be recycled into quarantine as
+    /// configured via [`PoolConfig::quarantine`] or discards it.
+    ///
+    /// A quarantined object keeps counting towards the size of this [`Pool`]
+    /// and therefore needs a slot of its own. If there is none available or
+    /// the object exhausted its retries, it's discarded and `false` returned.
+    fn quarantine(&self, mut obj: ObjectInner<M>) -> bool {
+        match &self.config.quarantine {
+            Some(q) if obj.quarantined < q.max_retries && !self.is_closed() => {
+                match self.semaphore.try_acquire() {
+                    Ok(permit) => permit.forget(),
+                    Err(_) => {
+                        self.discard(obj.obj, DiscardReason::RecycleFailed);
+                        return false;
+                    }
+                }
+                obj.quarantined += 1;
+                for hook in &self.hooks.on_park {
+                    hook(&mut obj.obj);
+                }
+                let until = Instant::now().checked_add(q.delay);
+                self.quarantine.lock().push_back((until, obj));
+                true
+            }
+            _ => {
+                self.discard(obj.obj, DiscardReason::RecycleFailed);
+                false
+            }
+        }
+    }
+
+    /// Indicates whether the quarantined object due at `until` has been
+    /// given up on, i.e. it has been waiting for another `max_retries`
+    /// delays without any caller retrying it.
+    fn is_quarantine_expired(&self, until: Option<Instant>) -> bool {
+        match &self.config.quarantine {
+            // Overflowing instants are never reached, so the object never
+            // expires.
+            Some(q) => until
+                .and_then(|until| {
+                    let retries = u32::try_from(q.max_retries).unwrap_or(u32::MAX);
+                    until.checked_add(q.delay.checked_mul(retries)?)
+                })
+                .is_some_and(|expiry| expiry <= Instant::now()),
+            None => true,
+        }
+    }
+
+    /// Removes the given number of quarantined objects from the size of this
+    /// [`Pool`] and returns the slots they have been holding.
+    fn release_quarantined(&self, n: usize) {
+        self.decrease_size(n);
+        for _ in 0..n {
+            self.release_permit();
+        }
+    }
+
+    /// Takes the first quarantined object whose delay is over and counts it
+    /// as part of this [`Pool`] again.
+    fn take_quarantined(&self) -> Option<ObjectInner<M>> {
+        let mut quarantine = self.quarantine.lock();
+        // All objects share the same delay, so the first one is due first.
+        match quarantine.front() {
+            Some((Some(until), _)) if *until <= Instant::now() => {}
+            _ => return None,
+        }
+        let (_, obj) = quarantine.pop_front()?;
+        // The caller already holds a slot, so the one of the object is
+        // returned while it keeps counting towards the size.
+        self.release_permit();
+        let _ = self.available.fetch_add(1, Ordering::Relaxed);
+        Some(obj)
+    }
+
+    /// Applies the `on_unpark` hooks to the given idle `obj`. If any of them
+    /// fails, the object is discarded and [`None`] is returned.
+    fn unpark(&self, mut obj: ObjectInner<M>) -> Option<ObjectInner<M>> {
+        if self
+            .hooks
+            .on_unpark
+            .iter()
+            .all(|hook| hook(&mut obj.obj).is_ok())
+        {
+            return Some(obj);
+        }
+        self.discard(obj.obj, DiscardReason::RecycleFailed);
+        let _ = self.available.fetch_sub(1, Ordering::Relaxed);
+        self.decrease_size(1);
+        None
+    }
+
+    /// Applies the `pre_recycle` hooks to the given idle `obj` and returns
+    /// whether all of them accepted it.
+    async fn pre_recycle(&self, obj: &mut ObjectInner<M>) -> bool {
+        for hook in &self.hooks.pre_recycle {
+            if hook.pre_recycle(&mut obj.obj, &obj.metrics).await.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Applies the `post_idle` hooks if the `idle` time of the given `obj` is
+    /// known and the `post_recycle` hooks to it and returns whether all of
+    /// them accepted it.
+    async fn post_recycle(&self, obj: &mut Object<M>, idle: Option<Duration>) -> bool {
+        if let Some(idle) = idle {
+            for (threshold, hook) in &self.hooks.post_idle {
+                if idle > *threshold && hook.post_idle(obj, idle).await.is_err() {
+                    return false;
+                }
+            }
+        }
+        for hook in &self.hooks.post_recycle {
+            if hook.post_recycle(obj).await.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Recycles the given `obj` retrying once if the error is classified as
+    /// [`RecycleErrorKind::Transient`]. [`RecycleErrorKind::Fatal`] errors
+    /// are recorded as failures towards the [`PoolConfig::timeout_cooldown`].
+    async fn recycle_object(
+        &self,
+        obj: &mut M::Type,
+        timeouts: &Timeouts,
+    ) -> Result<(), PoolError<RecycleError<M::Error>>> {
+        let result = framed!(self.recycle(obj, timeouts)).await;
+        let kind = match &result {
+            Err(PoolError::Backend(e)) => Some(self.manager.classify_recycle_error(e)),
+            _ => None,
+        };
+        match kind {
+            Some(RecycleErrorKind::Transient) => framed!(self.recycle(obj, timeouts)).await,
+            Some(RecycleErrorKind::Fatal) => {
+                if let Some(limit) = &self.config.timeout_cooldown {
+                    self.cooldown.record(limit, true);
+                }
+                result
+            }
+            None => result,
+        }
+    }
+
+    /// Creates a new object applying the create timeout, the
+    /// [`PoolConfig::max_concurrent_creates`] limit and the
+    /// [`CapacityLimiter`].
+    async fn create_object(
+        &self,
+        timeouts: &Timeouts,
+    ) -> Result<ObjectInner<M>, PoolError<M::Error>> {
+        // Objects being created during `Pool::retain()` must be checked too.
+        let retain_epoch = self.retainers.enter();
+        #[allow(unused_mut)]
+        let mut resources = Resources::default();
+        let (obj, create_time, limit) = apply_timeout(
+            self.runtime,
+            TimeoutType::Create,
+            timeouts.create,
+            framed!(async {
+                let _permit = match &self.create_semaphore {
+                    Some(s) => Some(s.acquire().await.map_err(|_| PoolError::Closed)?),
+                    None => None,
+                };
+                let limit = match &self.capacity_limiter {
+                    Some(limiter) => Some(limiter.acquire().await?),
+                    None => None,
+                };
+                #[cfg(all(unix, feature = "host_limit"))]
+                if let Some(limiter) = &self.host_limiter {
+                    let _ = resources.insert(limiter.acquire(self.runtime).await?);
+                }
+                let (obj, create_time) = self.create_with_retry().await?;
+                Ok::<_, PoolError<M::Error>>((obj, create_time, limit))
+            }),
+        )
+        .await?;
+        Ok(self.wrap_object(obj, resources, create_time, retain_epoch, limit))
+    }
+
+    /// Wraps a freshly created `obj` attaching its [`Companion`] and checks
+    /// the [`Manager::size_limit()`] once.
+    fn wrap_object(
+        &self,
+        mut obj: M::Type,
+        mut resources: Resources,
+        create_time: Duration,
+        retain_epoch: RetainEpoch<M::Type>,
+        limit: Option<OwnedSemaphorePermit>,
+    ) -> ObjectInner<M> {
+        if let Some(companion) = self.manager.companion(&mut obj) {
+            let _ = resources.insert(companion);
+        }
+        if !self.backend_limit_checked.swap(true, Ordering::Relaxed) {
+            if let Some(limit) = self.manager.size_limit(&obj) {
+                self.apply_backend_limit(limit);
+            }
+        }
+        ObjectInner {
+            resources,
+            obj,
+            metrics: Metrics::new(create_time),
+            returned_on: None,
+            retain_epoch,
+            quarantined: 0,
+            _limit: limit,
+        }
+    }
+
+    /// Creates a new idle object in a free slot of this [`Pool`] and returns
+    /// whether there was one.
+    ///
+    /// Unlike getting an [`Object`] this never waits for a slot and isn't
+    /// recorded in the [`PoolStats`] apart from the creation itself.
+    async fn create_idle(
+        self: &Arc<Self>,
+        timeouts: &Timeouts,
+    ) -> Result<bool, PoolError<M::Error>> {
+        if let Some(retry_after) = self.cooldown.remaining() {
+            return Err(PoolError::CoolingDown { retry_after });
+        }
+        match self.semaphore.try_acquire() {
+            Ok(permit) => permit.forget(),
+            Err(TryAcquireError::Closed) => return Err(PoolError::Closed),
+            Err(TryAcquireError::NoPermits) => return Ok(false),
+        }
+        // Idle objects don't hold a permit, so the size has to be checked
+        // as well. It's reserved right away to not race concurrent calls.
+        let max_size = self.max_size.load(Ordering::Relaxed);
+        let mut size = self.size.load(Ordering::Relaxed);
+        loop {
+            if size >= max_size {
+                self.release_permit();
+                return Ok(false);
+            }
+            match self.size.compare_exchange_weak(
+                size,
+                size + 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => size = current,
+            }
+        }
+        let mut obj = Object {
+            obj: None,
+            state: ObjectState::Creating,
+            burst: false,
+            memory: 0,
+            broken: AtomicBool::new(false),
+            tag: None,
+            timeouts: *timeouts,
+            pool: Arc::downgrade(self),
+        };
+        obj.obj = Some(self.create_object(timeouts).await?);
+        self.post_create(&mut obj).await?;
+        self.hand_out(&mut obj);
+        Ok(true)
+    }
+
+    /// Recycles every idle object once as if it had been checked out and
+    /// replaces the ones failing to be recycled.
+    async fn validate_idle(self: &Arc<Self>, timeouts: &Timeouts) {
+        let n = self.queue.lock().len();
+        // Checked objects are held until the end, so each one is only
+        // recycled once.
+        let mut checked = Vec::with_capacity(n);
+        for _ in 0..n {
+            match self.semaphore.try_acquire() {
+                Ok(permit) => permit.forget(),
+                Err(_) => break,
+            }
+            let _ = self.available.fetch_sub(1, Ordering::Relaxed);
+            let obj = Object {
+                obj: None,
+                state: ObjectState::Receiving,
+                burst: false,
+                memory: 0,
+                broken: AtomicBool::new(false),
+                tag: None,
+                timeouts: *timeouts,
+                pool: Arc::downgrade(self),
+            };
+            match self.fill(obj, AcquireInfo::default(), timeouts).await {
+                Ok((obj, _)) => checked.push(obj),
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Fills the given empty `obj` with a new object created via
+    /// [`Manager::create()`] and applies the `post_create` hooks.
+    async fn create_into(
+        &self,
+        obj: &mut Object<M>,
+        timeouts: &Timeouts,
+    ) -> Result<(), PoolError<M::Error>> {
+        obj.state = ObjectState::Creating;
+        let _ = self.available.fetch_add(1, Ordering::Relaxed);
+        let _ = self.size.fetch_add(1, Ordering::Relaxed);
+        obj.obj = Some(self.create_object(timeouts).await?);
+        self.post_create(obj).await
+    }
+
+    /// Fills the given empty `obj` with the given externally created
+    /// `value` and applies the `post_create` hooks.
+    ///
+    /// The object waits for a slot of the [`CapacityLimiter`] and the
+    /// `HostLimiter` like a newly created one, applying the create
+    /// timeout.
+    async fn adopt_into(
+        &self,
+        obj: &mut Object<M>,
+        value: M::Type,
+        timeouts: &Timeouts,
+    ) -> Result<(), PoolError<M::Error>> {
+        obj.state = ObjectState::Creating;
+        let _ = self.available.fetch_add(1, Ordering::Relaxed);
+        let _ = self.size.fetch_add(1, Ordering::Relaxed);
+        #[allow(unused_mut)]
+        let mut resources = Resources::default();
+        let limit = apply_timeout(self.runtime, TimeoutType::Create, timeouts.create, async {
+            let limit = match &self.capacity_limiter {
+                Some(limiter) => Some(limiter.acquire().await?),
+                None => None,
+            };
+            #[cfg(all(unix, feature = "host_limit"))]
+            if let Some(limiter) = &self.host_limiter {
+                let _ = resources.insert(limiter.acquire(self.runtime).await?);
+            }
+            Ok::<_, PoolError<M::Error>>(limit)
+        })
+        .await?;
+        let retain_epoch = self.retainers.enter();
+        obj.obj = Some(self.wrap_object(value, resources, Duration::ZERO, retain_epoch, limit));
+        self.post_create(obj).await
+    }
+
+    /// Counts the newly created `obj` and applies the `post_create` hooks.
+    async fn post_create(&self, obj: &mut Object<M>) -> Result<(), PoolError<M::Error>> {
+        self.stats.created();
+        for hook in &self.hooks.post_create {
+            hook.post_create(obj)
+                .await
+                .map_err(PoolError::PostCreateHook)?;
+        }
+        Ok(())
+    }
+
+    /// Calls [`Manager::create()`] retrying it as configured via
+    /// [`PoolConfig::create_retry`].
+    ///
+    /// Returns the new object together with the time the successful
+    /// [`Manager::create()`] call took.
+    async fn create_with_retry(&self) -> Result<(M::Type, Duration), PoolError<M::Error>> {
+        let mut failed = 0;
+        loop {
+            // The error must not be held across the `sleep()` below.
+            let (runtime, backoff) = {
+                if let Some(limit) = &self.config.create_limit {
+                    self.create_budget
+                        .take(limit)
+                        .map_err(|retry_after| PoolError::CreateLimitReached { retry_after })?;
+                }
+                let start = Instant::now();
+                let e = match CatchUnwind(self.manager.create())
+                    .await
+                    .map_err(PoolError::Panic)?
+                {
+                    Ok(obj) => return Ok((obj, start.elapsed())),
+                    Err(e) => e,
+                };
+                failed += 1;
+                let (retry, runtime) = match (&self.config.create_retry, self.runtime) {
+                    (Some(retry), Some(runtime)) if failed < retry.max_attempts => (retry, runtime),
+                    _ => return Err(PoolError::Backend(e)),
+                };
+                let backoff = match self.manager.backoff_hint(&e) {
+                    Some(hint) => hint.max(retry.backoff(failed, &*self.random)),
+                    None => retry.backoff(failed, &*self.random),
+                };
+                (runtime, backoff)
+            };
+            runtime.sleep(backoff).await;
+        }
+    }
+
+    /// Runs the steps of [`Pool::self_test()`] on a temporary object.
+    async fn self_test(
+        &self,
+        timeouts: &Timeouts,
+        check: Option<SelfTestCheck<'_, M::Type>>,
+    ) -> SelfTestReport<M::Error> {
+        let mut report = SelfTestReport {
+            create: Err(PoolError::Closed),
+            recycle: None,
+            interact: None,
+        };
+        if self.is_closed() {
+            return report;
+        }
+        let start = Instant::now();
+        // Unlike `create_object()` this neither takes from the
+        // `create_budget` nor retries, so the test gets to see the
+        // actual outcome of `Manager::create()`.
+        let created = apply_timeout(self.runtime, TimeoutType::Create, timeouts.create, async {
+            let limit = match &self.capacity_limiter {
+                Some(limiter) => Some(limiter.acquire().await?),
+                None => None,
+            };
+            let obj = CatchUnwind(self.manager.create())
+                .await
+                .map_err(PoolError::Panic)?
+                .map_err(PoolError::Backend)?;
+            Ok::<_, PoolError<M::Error>>((obj, limit))
+        })
+        .await;
+        let (mut obj, _limit) = match created {
+            Ok(created) => created,
+            Err(e) => {
+                report.create = Err(e);
+                return report;
+            }
+        };
+        report.create = Ok(start.elapsed());
+        let start = Instant::now();
+        let recycled = self.recycle_object(&mut obj, timeouts).await;
+        let failed = recycled.is_err();
+        report.recycle = Some(recycled.map(|()| start.elapsed()));
+        if let (Some(check), false) = (check, failed) {
+            let start = Instant::now();
+            report.interact = Some(check(&obj).await.map(|()| start.elapsed()));
+        }
+        let reason = match failed {
+            true => DiscardReason::RecycleFailed,
+            false => DiscardReason::SelfTest,
+        };
+        self.discard(obj, reason);
+        report
+    }
+
+    /// Recycles the given `obj` applying the recycle timeout.
+    async fn recycle(
+        &self,
+        obj: &mut M::Type,
+        timeouts: &Timeouts,
+    ) -> Result<(), PoolError<RecycleError<M::Error>>> {
+        apply_timeout(
+            self.runtime,
+            TimeoutType::Recycle,
+            timeouts.recycle,
+            self.manager.recycle(obj),
+        )
+        .await
+    }
 }
 
 async fn apply_timeout<O, E>(
@@ -0,0 +1,241 @@
+//! Sharing pooled [`Object`]s between several concurrent users.
+//!
+//! Some backends multiplex many requests over a single connection, e.g.
+//! HTTP/2 or pipelined Redis connections. A [`MultiplexPool`] hands out
+//! [`Lease`]s to [`Object`]s of a regular [`Pool`] and allows up to a fixed
+//! number of concurrent [`Lease`]s per [`Object`]. A new [`Object`] is only
+//! retrieved from the [`Pool`] once all the leased ones hit that limit:
+//!
+//! ```rust
+//! use deadpool::managed::{multiplex::MultiplexPool, Pool, ValueManager};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let pool = Pool::<ValueManager<Vec<u8>>>::builder(ValueManager::new(Vec::new))
+//!     .max_size(4)
+//!     .build()
+//!     .unwrap();
+//! let pool = MultiplexPool::new(pool, 2);
+//! let first = pool.lease().await.unwrap();
+//! let second = pool.lease().await.unwrap();
+//! assert_eq!(pool.pool().status().size, 1);
+//! let third = pool.lease().await.unwrap();
+//! assert_eq!(pool.pool().status().size, 2);
+//! # drop((first, second, third));
+//! # }
+//! ```
+//!
+//! As [`Lease`]s only grant shared access, the [`Manager::Type`] needs to
+//! support concurrent use via `&self`. An [`Object`] is returned to its
+//! [`Pool`] and recycled there as soon as its last [`Lease`] is dropped.
+//!
+//! [`Manager::Type`]: super::Manager::Type
+
+use std::{
+    fmt,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use tokio::sync::Notify;
+
+use crate::{lock::Mutex, runtime};
+
+use super::{Manager, Object, Pool, PoolError};
+
+/// [`Pool`] handing out [`Lease`]s to shared [`Object`]s.
+///
+/// See the [module documentation](self) for details.
+pub struct MultiplexPool<M: Manager> {
+    pool: Pool<M>,
+    inner: Arc<MultiplexInner<M>>,
+}
+
+struct MultiplexInner<M: Manager> {
+    max_leases: usize,
+    /// [`Object`]s with at least one active [`Lease`].
+    slots: Mutex<Vec<Arc<Slot<M>>>>,
+    /// Notified whenever a [`Lease`] is dropped.
+    released: Notify,
+}
+
+struct Slot<M: Manager> {
+    obj: Object<M>,
+    /// Number of active [`Lease`]s. Only changed while holding the lock of
+    /// the `slots`.
+    leases: AtomicUsize,
+}
+
+// Implemented manually to avoid unnecessary trait bounds on the struct.
+impl<M> fmt::Debug for MultiplexPool<M>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiplexPool")
+            .field("pool", &self.pool)
+            .field("max_leases", &self.inner.max_leases)
+            .field("leased", &self.leased())
+            .finish()
+    }
+}
+
+impl<M: Manager> Clone for MultiplexPool<M> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<M: Manager> MultiplexPool<M> {
+    /// Creates a new [`MultiplexPool`] allowing up to `max_leases`
+    /// concurrent [`Lease`]s per [`Object`] of the given [`Pool`].
+    ///
+    /// # Panics
+    ///
+    /// If `max_leases` is zero.
+    pub fn new(pool: Pool<M>, max_leases: usize) -> Self {
+        assert!(max_leases > 0, "max_leases must be greater than zero");
+        Self {
+            pool,
+            inner: Arc::new(MultiplexInner {
+                max_leases,
+                slots: Mutex::new(Vec::new()),
+                released: Notify::new(),
+            }),
+        }
+    }
+
+    /// Leases the least used [`Object`] which is below the lease limit or
+    /// retrieves a new one from the [`Pool`] if there is none.
+    ///
+    /// Concurrent calls finding all [`Object`]s at their limit may retrieve
+    /// one new [`Object`] each. While waiting for the [`Pool`], a [`Lease`]
+    /// being dropped elsewhere cancels the retrieval and the freed up
+    /// [`Lease`] is taken instead.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn lease(&self) -> Result<Lease<M>, PoolError<M::Error>> {
+        loop {
+            // Created before checking so a release in between isn't missed.
+            let released = self.inner.released.notified();
+            if let Some(lease) = self.try_lease() {
+                return Ok(lease);
+            }
+            if let Some(obj) = runtime::cancellable(self.pool.get(), released).await {
+                let slot = Arc::new(Slot {
+                    obj: obj?,
+                    leases: AtomicUsize::new(1),
+                });
+                self.inner.slots.lock().push(slot.clone());
+                return Ok(Lease {
+                    slot,
+                    inner: self.inner.clone(),
+                });
+            }
+        }
+    }
+
+    /// Leases one of the already leased [`Object`]s without retrieving a new
+    /// one from the [`Pool`].
+    ///
+    /// Returns [`None`] if all of them hit the lease limit.
+    pub fn try_lease(&self) -> Option<Lease<M>> {
+        let slots = self.inner.slots.lock();
+        let slot = slots
+            .iter()
+            .filter(|s| s.leases.load(Ordering::Relaxed) < self.inner.max_leases)
+            .min_by_key(|s| s.leases.load(Ordering::Relaxed))?;
+        let _ = slot.leases.fetch_add(1, Ordering::Relaxed);
+        Some(Lease {
+            slot: slot.clone(),
+            inner: self.inner.clone(),
+        })
+    }
+
+    /// Returns the number of [`Object`]s which are currently leased.
+    #[must_use]
+    pub fn leased(&self) -> usize {
+        self.inner.slots.lock().len()
+    }
+
+    /// Returns the maximum number of concurrent [`Lease`]s per [`Object`].
+    #[must_use]
+    pub fn max_leases(&self) -> usize {
+        self.inner.max_leases
+    }
+
+    /// Returns the wrapped [`Pool`].
+    #[must_use]
+    pub fn pool(&self) -> &Pool<M> {
+        &self.pool
+    }
+}
+
+/// Shared access to an [`Object`] of a [`MultiplexPool`].
+///
+/// The [`Object`] is returned to its [`Pool`] once all of its [`Lease`]s
+/// have been dropped.
+#[must_use]
+pub struct Lease<M: Manager> {
+    slot: Arc<Slot<M>>,
+    inner: Arc<MultiplexInner<M>>,
+}
+
+// Implemented manually to leave out the other leases.
+impl<M> fmt::Debug for Lease<M>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lease")
+            .field("obj", &self.slot.obj)
+            .field("leases", &self.leases())
+            .finish()
+    }
+}
+
+impl<M: Manager> Lease<M> {
+    /// Returns the number of active [`Lease`]s of the same [`Object`]
+    /// including this one.
+    #[must_use]
+    pub fn leases(&self) -> usize {
+        self.slot.leases.load(Ordering::Relaxed)
+    }
+}
+
+impl<M: Manager> Deref for Lease<M> {
+    type Target = M::Type;
+
+    fn deref(&self) -> &M::Type {
+        &self.slot.obj
+    }
+}
+
+impl<M: Manager> AsRef<M::Type> for Lease<M> {
+    fn as_ref(&self) -> &M::Type {
+        self
+    }
+}
+
+impl<M: Manager> Drop for Lease<M> {
+    fn drop(&mut self) {
+        let mut slots = self.inner.slots.lock();
+        if self.slot.leases.fetch_sub(1, Ordering::Relaxed) == 1 {
+            // The `Object` is returned to its `Pool` once the `slot` is
+            // dropped right after releasing the lock.
+            slots.retain(|s| !Arc::ptr_eq(s, &self.slot));
+        }
+        drop(slots);
+        self.inner.released.notify_waiters();
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use crate::Runtime;
+
+use super::{Manager, RecycleResult};
+
+/// [`Manager`] creating objects instantly and without any backend.
+///
+/// Every object is the number of objects created before it. This is meant
+/// for benchmarking and tuning the scheduling overhead of a [`Pool`] and
+/// settings like the [`PoolConfig::queue_mode`] in isolation. An artificial
+/// latency of creating and recycling objects can be configured via
+/// [`NoopManager::with_latency()`].
+///
+/// # Example
+///
+/// ```rust
+/// use deadpool::managed::{NoopManager, Pool};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let pool = Pool::<NoopManager>::builder(NoopManager::new())
+///     .max_size(2)
+///     .build()
+///     .unwrap();
+/// assert_eq!(*pool.get().await.unwrap(), 0);
+/// # }
+/// ```
+///
+/// [`Pool`]: super::Pool
+/// [`PoolConfig::queue_mode`]: super::PoolConfig::queue_mode
+#[derive(Debug, Default)]
+pub struct NoopManager {
+    latency: Option<(Runtime, Duration, Duration)>,
+    created: AtomicUsize,
+    recycled: AtomicUsize,
+}
+
+impl NoopManager {
+    /// Creates a new [`NoopManager`] without any latency.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`NoopManager`] sleeping via the given [`Runtime`] for
+    /// the `create` latency before creating and the `recycle` latency
+    /// before recycling an object.
+    #[must_use]
+    pub fn with_latency(runtime: Runtime, create: Duration, recycle: Duration) -> Self {
+        Self {
+            latency: Some((runtime, create, recycle)),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the number of objects created so far.
+    #[must_use]
+    pub fn created(&self) -> usize {
+        self.created.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of objects recycled so far.
+    #[must_use]
+    pub fn recycled(&self) -> usize {
+        self.recycled.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl Manager for NoopManager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        if let Some((runtime, latency, _)) = self.latency {
+            runtime.sleep(latency).await;
+        }
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _obj: &mut usize) -> RecycleResult<Infallible> {
+        if let Some((runtime, _, latency)) = self.latency {
+            runtime.sleep(latency).await;
+        }
+        let _ = self.recycled.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+//! Racing connection attempts to multiple endpoints.
+//!
+//! Backends reachable via multiple addresses (e.g. dual-stack IPv4/IPv6 hosts
+//! or replicas in different availability zones) can use a [`Race`] inside
+//! their [`Manager::create()`] implementation. It starts a connection attempt
+//! per endpoint and keeps the first one that succeeds, similar to the
+//! "Happy Eyeballs" algorithm described in [RFC 8305].
+//!
+//! ```rust
+//! use std::io;
+//!
+//! use async_trait::async_trait;
+//! use deadpool::managed::{
+//!     self,
+//!     race::{Race, RaceError},
+//!     RecycleResult,
+//! };
+//!
+//! struct Connection {
+//!     addr: &'static str,
+//! }
+//!
+//! impl Connection {
+//!     async fn connect(addr: &'static str) -> io::Result<Self> {
+//!         match addr {
+//!             "[2001:db8::1]:5432" => Err(io::ErrorKind::ConnectionRefused.into()),
+//!             _ => Ok(Self { addr }),
+//!         }
+//!     }
+//! }
+//!
+//! struct Manager {
+//!     addrs: Vec<&'static str>,
+//! }
+//!
+//! #[async_trait]
+//! impl managed::Manager for Manager {
+//!     type Type = Connection;
+//!     type Error = RaceError<io::Error>;
+//!
+//!     async fn create(&self) -> Result<Connection, Self::Error> {
+//!         Race::new()
+//!             .connect(self.addrs.clone(), Connection::connect)
+//!             .await
+//!     }
+//!
+//!     async fn recycle(&self, _conn: &mut Connection) -> RecycleResult<Self::Error> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mgr = Manager {
+//!     addrs: vec!["[2001:db8::1]:5432", "192.0.2.1:5432"],
+//! };
+//! let pool: managed::Pool<Manager> = managed::Pool::builder(mgr).build().unwrap();
+//! assert_eq!(pool.get().await.unwrap().addr, "192.0.2.1:5432");
+//! # }
+//! ```
+//!
+//! [`Race::staggered()`] starts the attempts one after another instead.
+//!
+//! [`Manager::create()`]: super::Manager::create
+//! [RFC 8305]: https://datatracker.ietf.org/doc/html/rfc8305
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use crate::Runtime;
+
+/// Strategy for racing connection attempts to multiple endpoints.
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Copy, Debug)]
+pub struct Race {
+    stagger: Option<(Runtime, Duration)>,
+}
+
+impl Race {
+    /// Creates a new [`Race`] which starts all connection attempts at once.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { stagger: None }
+    }
+
+    /// Creates a new [`Race`] which starts the connection attempts one after
+    /// another, each `delay` after the previous one unless the previous one
+    /// failed earlier.
+    ///
+    /// Endpoints are tried in the order they are given, so preferred ones
+    /// should come first.
+    #[must_use]
+    pub fn staggered(runtime: Runtime, delay: Duration) -> Self {
+        Self {
+            stagger: Some((runtime, delay)),
+        }
+    }
+
+    /// Races `connect` for all the given `endpoints` and returns the first
+    /// successful result.
+    ///
+    /// All other attempts still in progress are dropped as soon as one of
+    /// them succeeds.
+    ///
+    /// # Errors
+    ///
+    /// See [`RaceError`] for details.
+    pub async fn connect<I, F, Fut, T, E>(
+        &self,
+        endpoints: I,
+        connect: F,
+    ) -> Result<T, RaceError<E>>
+    where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut pending = endpoints.into_iter().map(connect);
+        let mut running: Vec<_> = match self.stagger {
+            Some(_) => pending.next().map(Box::pin).into_iter().collect(),
+            None => pending.by_ref().map(Box::pin).collect(),
+        };
+        let mut errors = Vec::new();
+        loop {
+            if running.is_empty() {
+                return Err(if errors.is_empty() {
+                    RaceError::NoEndpoints
+                } else {
+                    RaceError::Failed(errors)
+                });
+            }
+            let first = FirstReady {
+                futures: &mut running,
+            };
+            let result = match self.stagger {
+                Some((runtime, delay)) => runtime.timeout(delay, first).await,
+                None => Some(first.await),
+            };
+            match result {
+                Some(Ok(obj)) => return Ok(obj),
+                Some(Err(e)) => errors.push(e),
+                None => {}
+            }
+            // The next attempt is started as soon as the delay elapsed or
+            // a running attempt failed.
+            if let Some(attempt) = pending.next() {
+                running.push(Box::pin(attempt));
+            }
+        }
+    }
+}
+
+impl Default for Race {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Future resolving to the output of the first of the given futures which
+/// completes. The completed future is removed from the list.
+struct FirstReady<'a, F> {
+    futures: &'a mut Vec<Pin<Box<F>>>,
+}
+
+impl<F: Future> Future for FirstReady<'_, F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        for i in 0..self.futures.len() {
+            if let Poll::Ready(output) = self.futures[i].as_mut().poll(cx) {
+                drop(self.futures.swap_remove(i));
+                return Poll::Ready(output);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// Possible errors returned by [`Race::connect()`].
+#[derive(Debug)]
+pub enum RaceError<E> {
+    /// No endpoints were given.
+    NoEndpoints,
+
+    /// All connection attempts failed. The errors are in the order in which
+    /// the attempts failed.
+    Failed(Vec<E>),
+}
+
+impl<E: fmt::Display> fmt::Display for RaceError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoEndpoints => write!(f, "No endpoints to connect to"),
+            Self::Failed(errors) => {
+                write!(f, "All {} connection attempts failed", errors.len())?;
+                if let Some(e) = errors.last() {
+                    write!(f, ": {}", e)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for RaceError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NoEndpoints => None,
+            Self::Failed(errors) => errors
+                .last()
+                .map(|e| e as &(dyn std::error::Error + 'static)),
+        }
+    }
+}
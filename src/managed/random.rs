@@ -0,0 +1,85 @@
+//! Sources of randomness used by a [`Pool`].
+//!
+//! All random decisions of a [`Pool`], e.g. the jitter of the
+//! [`PoolConfig::create_retry`] backoff, are drawn from its
+//! [`RandomSource`]. It can be set via [`PoolBuilder::random()`] and
+//! defaults to [`SystemRandom`]. Using a [`SeededRandom`] instead makes
+//! simulation tests and reproductions of production incidents
+//! deterministic.
+//!
+//! [`Pool`]: super::Pool
+//! [`PoolBuilder::random()`]: super::PoolBuilder::random
+//! [`PoolConfig::create_retry`]: super::PoolConfig::create_retry
+
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Source of random numbers.
+pub trait RandomSource: Sync + Send {
+    /// Returns the next random number.
+    fn next_u64(&self) -> u64;
+}
+
+impl fmt::Debug for dyn RandomSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:p}", self)
+    }
+}
+
+impl<F> RandomSource for F
+where
+    F: Fn() -> u64 + Sync + Send,
+{
+    fn next_u64(&self) -> u64 {
+        self()
+    }
+}
+
+/// [`RandomSource`] seeded by the operating system.
+///
+/// This is the default [`RandomSource`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemRandom;
+
+impl RandomSource for SystemRandom {
+    fn next_u64(&self) -> u64 {
+        RandomState::new().build_hasher().finish()
+    }
+}
+
+/// Deterministic [`RandomSource`] producing the same sequence for the same
+/// seed.
+///
+/// This uses the SplitMix64 algorithm, which is fast but not suitable for
+/// anything security related.
+#[derive(Debug, Default)]
+pub struct SeededRandom {
+    state: AtomicU64,
+}
+
+impl SeededRandom {
+    /// Creates a new [`SeededRandom`] starting with the given `seed`.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: AtomicU64::new(seed),
+        }
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn next_u64(&self) -> u64 {
+        const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut z = self
+            .state
+            .fetch_add(GAMMA, Ordering::Relaxed)
+            .wrapping_add(GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
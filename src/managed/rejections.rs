@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::Rejections;
+
+use super::{PoolError, TimeoutType};
+
+/// Counters of failed attempts to get an [`Object`] split by cause.
+///
+/// [`Object`]: super::Object
+#[derive(Debug, Default)]
+pub(crate) struct RejectionCounters {
+    wait_timeouts: AtomicUsize,
+    create_timeouts: AtomicUsize,
+    exhausted: AtomicUsize,
+    queue_full: AtomicUsize,
+    cancelled: AtomicUsize,
+}
+
+impl RejectionCounters {
+    /// Starts tracking an attempt to get an [`Object`].
+    ///
+    /// The returned [`Attempt`] counts as cancelled if it is dropped without
+    /// [`Attempt::finish()`] being called.
+    ///
+    /// [`Object`]: super::Object
+    pub(crate) fn attempt(&self) -> Attempt<'_> {
+        Attempt {
+            counters: self,
+            finished: false,
+        }
+    }
+
+    /// Returns the current values of all counters.
+    pub(crate) fn snapshot(&self) -> Rejections {
+        Rejections {
+            wait_timeouts: self.wait_timeouts.load(Ordering::Relaxed),
+            create_timeouts: self.create_timeouts.load(Ordering::Relaxed),
+            exhausted: self.exhausted.load(Ordering::Relaxed),
+            queue_full: self.queue_full.load(Ordering::Relaxed),
+            cancelled: self.cancelled.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Running attempt to get an [`Object`].
+///
+/// [`Object`]: super::Object
+#[derive(Debug)]
+pub(crate) struct Attempt<'a> {
+    counters: &'a RejectionCounters,
+    finished: bool,
+}
+
+impl Attempt<'_> {
+    /// Finishes this attempt and counts the given `result` if it is a
+    /// rejection.
+    pub(crate) fn finish<T, E>(mut self, result: &Result<T, PoolError<E>>) {
+        self.finished = true;
+        let counter = match result {
+            Err(PoolError::Timeout(TimeoutType::Wait)) => &self.counters.wait_timeouts,
+            Err(PoolError::Timeout(TimeoutType::Create)) => &self.counters.create_timeouts,
+            Err(PoolError::Exhausted { .. }) => &self.counters.exhausted,
+            Err(PoolError::QueueFull) => &self.counters.queue_full,
+            _ => return,
+        };
+        let _ = counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Attempt<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.counters.cancelled.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Objects which can be reset to a clean state cheaply.
+///
+/// If the [`Manager::Type`] of a [`Pool`] implements this trait,
+/// [`PoolBuilder::reset_on_return()`] makes the [`Pool`] call
+/// [`Reset::reset()`] every time an [`Object`] is returned. This allows
+/// [`Manager::recycle()`] to skip any IO for objects like buffers, parsers or
+/// local caches.
+///
+/// [`Manager::Type`]: super::Manager::Type
+/// [`Manager::recycle()`]: super::Manager::recycle
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::reset_on_return()`]: super::PoolBuilder::reset_on_return
+pub trait Reset {
+    /// Resets this object to a clean state.
+    fn reset(&mut self);
+}
+
+impl<T> Reset for Vec<T> {
+    fn reset(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Reset for VecDeque<T> {
+    fn reset(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K, V, S> Reset for HashMap<K, V, S> {
+    fn reset(&mut self) {
+        self.clear();
+    }
+}
+
+impl Reset for String {
+    fn reset(&mut self) {
+        self.clear();
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+};
+
+/// Secondary resources attached to a pooled object.
+///
+/// This allows tracking resources which depend on a pooled object, e.g.
+/// prepared statement handles of a connection, alongside it. They are kept
+/// across checkouts and dropped right before the object itself once it is
+/// discarded by its [`Pool`], so they can't outlive or leak from it.
+///
+/// Resources are keyed by their type, so there is at most one resource of
+/// every type. Use a collection type or a newtype wrapper to store multiple
+/// values of the same type.
+///
+/// The resources of an [`Object`] are accessed via [`Object::resources()`]
+/// and [`Object::resources_mut()`].
+///
+/// [`Object`]: super::Object
+/// [`Object::resources()`]: super::Object::resources
+/// [`Object::resources_mut()`]: super::Object::resources_mut
+/// [`Pool`]: super::Pool
+#[derive(Default)]
+pub struct Resources {
+    map: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl fmt::Debug for Resources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resources")
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
+impl Resources {
+    /// Attaches the given `resource` replacing and returning any previous
+    /// resource of the same type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, resource: T) -> Option<T> {
+        self.map
+            .insert(TypeId::of::<T>(), Box::new(resource))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Returns the resource of type `T` if there is one.
+    #[must_use]
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.map
+            .get(&TypeId::of::<T>())
+            .and_then(|r| r.downcast_ref())
+    }
+
+    /// Returns the resource of type `T` mutably if there is one.
+    pub fn get_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+        self.map
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|r| r.downcast_mut())
+    }
+
+    /// Returns the resource of type `T` attaching a new one created by `f`
+    /// first if there is none yet.
+    pub fn get_or_insert_with<T, F>(&mut self, f: F) -> &mut T
+    where
+        T: Any + Send + Sync,
+        F: FnOnce() -> T,
+    {
+        self.map
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(f()))
+            .downcast_mut()
+            .unwrap()
+    }
+
+    /// Detaches and returns the resource of type `T` if there is one.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        self.map
+            .remove(&TypeId::of::<T>())
+            .and_then(|r| r.downcast().ok())
+            .map(|r| *r)
+    }
+
+    /// Returns the number of attached resources.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Indicates whether there are no resources attached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
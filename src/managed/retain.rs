@@ -0,0 +1,162 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Weak},
+};
+
+use crate::lock::Mutex;
+
+use super::Metrics;
+
+/// Predicate passed to [`Pool::retain()`].
+///
+/// [`Pool::retain()`]: super::Pool::retain
+pub(crate) type RetainFilter<T> = Arc<dyn Fn(&T, &Metrics) -> bool + Send + Sync>;
+
+/// Predicates of [`Pool::retain()`] calls which still need to be applied to
+/// [`Object`]s which were checked out at that time.
+///
+/// Every call starts a new epoch. [`Object`]s remember the epoch up to which
+/// they have been checked via a [`RetainEpoch`], so each one of them sees
+/// every predicate only once. A predicate is dropped as soon as no
+/// [`Object`] of an earlier epoch is left. This must only be accessed while
+/// holding the lock of the idle queue so [`Object`]s returning concurrently
+/// to a [`Pool::retain()`] call can't miss its predicate.
+///
+/// [`Object`]: super::Object
+/// [`Pool::retain()`]: super::Pool::retain
+pub(crate) struct Retainers<T> {
+    inner: Arc<Mutex<RetainersInner<T>>>,
+}
+
+struct RetainersInner<T> {
+    epoch: usize,
+    filters: Vec<(usize, RetainFilter<T>)>,
+    /// Number of objects per epoch they have been checked up to.
+    objects: BTreeMap<usize, usize>,
+}
+
+impl<T> RetainersInner<T> {
+    fn enter(&mut self, epoch: usize) {
+        *self.objects.entry(epoch).or_insert(0) += 1;
+    }
+
+    fn leave(&mut self, epoch: usize) {
+        if let Some(count) = self.objects.get_mut(&epoch) {
+            *count -= 1;
+            if *count == 0 {
+                let _ = self.objects.remove(&epoch);
+            }
+        }
+    }
+
+    /// Drops the predicates which every object has been checked against.
+    fn prune(&mut self) {
+        match self.objects.keys().next().copied() {
+            Some(oldest) => self.filters.retain(|(epoch, _)| *epoch > oldest),
+            None => self.filters.clear(),
+        }
+    }
+}
+
+// Implemented manually as the predicates don't implement `Debug`.
+impl<T> fmt::Debug for Retainers<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.lock();
+        f.debug_struct("Retainers")
+            .field("epoch", &inner.epoch)
+            .field("pending", &inner.filters.len())
+            .finish()
+    }
+}
+
+impl<T> Default for Retainers<T> {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RetainersInner {
+                epoch: 0,
+                filters: Vec::new(),
+                objects: BTreeMap::new(),
+            })),
+        }
+    }
+}
+
+impl<T> Retainers<T> {
+    /// Returns a [`RetainEpoch`] for a new object which only needs to be
+    /// checked against predicates added from now on.
+    pub(crate) fn enter(&self) -> RetainEpoch<T> {
+        let mut inner = self.inner.lock();
+        let epoch = inner.epoch;
+        inner.enter(epoch);
+        RetainEpoch {
+            epoch,
+            retainers: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// Starts a new epoch storing the given `filter` for the objects of
+    /// earlier ones.
+    pub(crate) fn push(&self, filter: RetainFilter<T>) {
+        let mut inner = self.inner.lock();
+        inner.epoch += 1;
+        if !inner.objects.is_empty() {
+            let epoch = inner.epoch;
+            inner.filters.push((epoch, filter));
+        }
+    }
+
+    /// Applies all predicates newer than the given `epoch` and advances it
+    /// to the current one. Returns `false` as soon as one of them rejects
+    /// the object.
+    pub(crate) fn check(&self, obj: &T, metrics: &Metrics, epoch: &mut RetainEpoch<T>) -> bool {
+        let mut inner = self.inner.lock();
+        let keep = inner
+            .filters
+            .iter()
+            .filter(|(e, _)| *e > epoch.epoch)
+            .all(|(_, filter)| filter(obj, metrics));
+        Self::advance_locked(&mut inner, epoch);
+        keep
+    }
+
+    /// Advances the given `epoch` to the current one without checking any
+    /// predicates, e.g. because the object has just been checked against
+    /// the latest one.
+    pub(crate) fn advance(&self, epoch: &mut RetainEpoch<T>) {
+        Self::advance_locked(&mut self.inner.lock(), epoch);
+    }
+
+    fn advance_locked(inner: &mut RetainersInner<T>, epoch: &mut RetainEpoch<T>) {
+        inner.leave(epoch.epoch);
+        epoch.epoch = inner.epoch;
+        inner.enter(epoch.epoch);
+        inner.prune();
+    }
+}
+
+/// Epoch up to which an object has been checked against the predicates of
+/// [`Retainers`].
+///
+/// Dropping it together with the object releases the predicates which are
+/// no longer needed.
+pub(crate) struct RetainEpoch<T> {
+    epoch: usize,
+    retainers: Weak<Mutex<RetainersInner<T>>>,
+}
+
+impl<T> fmt::Debug for RetainEpoch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RetainEpoch").field(&self.epoch).finish()
+    }
+}
+
+impl<T> Drop for RetainEpoch<T> {
+    fn drop(&mut self) {
+        if let Some(retainers) = self.retainers.upgrade() {
+            let mut inner = retainers.lock();
+            inner.leave(self.epoch);
+            inner.prune();
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    convert::TryFrom,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Estimator of how often slots of a [`Pool`] become available again.
+///
+/// It keeps an exponentially weighted moving average of the interval
+/// between two consecutive returns. The updates aren't synchronized with
+/// each other which is fine as the result is only a rough estimate anyways.
+///
+/// [`Pool`]: super::Pool
+#[derive(Debug)]
+pub(crate) struct ReturnRate {
+    start: Instant,
+    /// Nanoseconds since `start` of the last return or zero if nothing has
+    /// been returned yet.
+    last_return: AtomicU64,
+    /// Average interval between two returns in nanoseconds or zero if it
+    /// isn't known yet.
+    interval: AtomicU64,
+}
+
+impl Default for ReturnRate {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            last_return: AtomicU64::new(0),
+            interval: AtomicU64::new(0),
+        }
+    }
+}
+
+impl ReturnRate {
+    /// Records that a slot has been returned.
+    pub(crate) fn record(&self) {
+        let now = u64::try_from(self.start.elapsed().as_nanos())
+            .unwrap_or(u64::MAX)
+            .max(1);
+        let last = self.last_return.swap(now, Ordering::Relaxed);
+        if last == 0 {
+            return;
+        }
+        let sample = now.saturating_sub(last);
+        let avg = self.interval.load(Ordering::Relaxed);
+        let avg = if avg == 0 {
+            sample
+        } else {
+            avg - avg / 8 + sample / 8
+        };
+        self.interval.store(avg, Ordering::Relaxed);
+    }
+
+    /// Returns the estimated time until the next slot becomes available or
+    /// [`None`] if there is no data to base an estimate on yet.
+    pub(crate) fn retry_after(&self) -> Option<Duration> {
+        match self.interval.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::{future::Future, pin::Pin, time::Duration};
+
+use super::{PoolError, RecycleError};
+
+/// Additional check run by variants of [`Pool::self_test()`] on the
+/// temporary object, e.g. an interaction with a `SyncWrapper`.
+///
+/// [`Pool::self_test()`]: super::Pool::self_test
+pub(crate) type SelfTestCheck<'a, T> = &'a (dyn Fn(&T) -> SelfTestFuture<'_> + Sync);
+
+/// Future returned by a [`SelfTestCheck`].
+pub(crate) type SelfTestFuture<'a> = Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
+
+/// Outcome of the steps run by [`Pool::self_test()`].
+///
+/// Every step records either the time it took or the error it failed with.
+/// Steps following a failed one aren't run and left as [`None`].
+///
+/// [`Pool::self_test()`]: super::Pool::self_test
+#[derive(Debug)]
+pub struct SelfTestReport<E> {
+    /// Outcome of creating the temporary object via [`Manager::create()`].
+    ///
+    /// [`Manager::create()`]: super::Manager::create
+    pub create: Result<Duration, PoolError<E>>,
+
+    /// Outcome of recycling the temporary object via
+    /// [`Manager::recycle()`].
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    pub recycle: Option<Result<Duration, PoolError<RecycleError<E>>>>,
+
+    /// Outcome of interacting with the temporary object. This is only run
+    /// by `Pool::self_test_interact()` for pools of `SyncWrapper`s and
+    /// [`None`] otherwise.
+    pub interact: Option<Result<Duration, String>>,
+}
+
+impl<E> SelfTestReport<E> {
+    /// Indicates whether all of the steps which have been run succeeded.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.create.is_ok()
+            && !matches!(self.recycle, Some(Err(_)))
+            && !matches!(self.interact, Some(Err(_)))
+    }
+}
@@ -0,0 +1,68 @@
+use std::{fmt, ops::Deref, sync::Arc};
+
+use super::{Manager, Object};
+
+/// Clonable shared guard of an [`Object`] retrieved via
+/// [`Pool::get_shared()`].
+///
+/// All clones refer to the same [`Object`], which is returned to its
+/// [`Pool`] once the last of them is dropped.
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::get_shared()`]: super::Pool::get_shared
+#[must_use]
+pub struct SharedObject<M: Manager> {
+    pub(crate) obj: Arc<Object<M>>,
+}
+
+// Implemented manually to avoid unnecessary trait bounds on the struct.
+impl<M> fmt::Debug for SharedObject<M>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedObject")
+            .field("obj", &self.obj)
+            .field("readers", &Self::readers(self))
+            .finish()
+    }
+}
+
+impl<M: Manager> Clone for SharedObject<M> {
+    fn clone(&self) -> Self {
+        Self {
+            obj: self.obj.clone(),
+        }
+    }
+}
+
+impl<M: Manager> SharedObject<M> {
+    /// Returns the number of [`SharedObject`]s referring to the same
+    /// [`Object`] including this one.
+    #[must_use]
+    pub fn readers(this: &Self) -> usize {
+        Arc::strong_count(&this.obj)
+    }
+
+    /// Indicates whether both [`SharedObject`]s refer to the same
+    /// [`Object`].
+    #[must_use]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.obj, &other.obj)
+    }
+}
+
+impl<M: Manager> Deref for SharedObject<M> {
+    type Target = M::Type;
+
+    fn deref(&self) -> &M::Type {
+        &self.obj
+    }
+}
+
+impl<M: Manager> AsRef<M::Type> for SharedObject<M> {
+    fn as_ref(&self) -> &M::Type {
+        self
+    }
+}
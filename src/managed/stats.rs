@@ -0,0 +1,160 @@
+use std::{
+    convert::TryFrom,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use crate::Rejections;
+
+/// Statistics of a [`Pool`] as returned by [`Pool::stats()`].
+///
+/// The peak usage over a recent window of time is summarized separately by
+/// [`Pool::capacity_report()`].
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::capacity_report()`]: super::Pool::capacity_report
+/// [`Pool::stats()`]: super::Pool::stats
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PoolStats {
+    /// Total number of [`Object`]s created.
+    ///
+    /// [`Object`]: super::Object
+    pub created: usize,
+
+    /// Total number of [`Object`]s recycled successfully.
+    ///
+    /// [`Object`]: super::Object
+    pub recycled: usize,
+
+    /// Total number of [`Object`]s which failed to be recycled and have
+    /// been dropped.
+    ///
+    /// [`Object`]: super::Object
+    pub recycle_failures: usize,
+
+    /// Total number of failed attempts to get an [`Object`] split by cause.
+    ///
+    /// These are the same counters as the [`Status::rejections`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`Status::rejections`]: crate::Status::rejections
+    pub rejections: Rejections,
+
+    /// Number of tasks currently waiting for a slot.
+    pub waiting: usize,
+
+    /// Number of tasks currently waiting for a slot without any timeout,
+    /// e.g. via [`Pool::get_unbounded()`]. These are included in the
+    /// [`PoolStats::waiting`] tasks.
+    ///
+    /// [`Pool::get_unbounded()`]: super::Pool::get_unbounded
+    pub unbounded_waiting: usize,
+
+    /// Number of slots acquired since the previous call of
+    /// [`Pool::stats()`].
+    ///
+    /// [`Pool::stats()`]: super::Pool::stats
+    pub acquired: usize,
+
+    /// Longest time spent waiting for a slot since the previous call of
+    /// [`Pool::stats()`].
+    ///
+    /// [`Pool::stats()`]: super::Pool::stats
+    pub max_wait: Duration,
+
+    /// Average time spent waiting for a slot since the previous call of
+    /// [`Pool::stats()`].
+    ///
+    /// [`Pool::stats()`]: super::Pool::stats
+    pub mean_wait: Duration,
+}
+
+impl PoolStats {
+    /// Returns these [`PoolStats`] with all the total counters replaced by
+    /// their increase since the given `earlier` [`PoolStats`].
+    ///
+    /// The current values like [`PoolStats::waiting`] and the wait times,
+    /// which already cover only the time since the previous call of
+    /// [`Pool::stats()`], are kept as they are.
+    ///
+    /// [`Pool::stats()`]: super::Pool::stats
+    #[must_use]
+    pub fn diff(&self, earlier: &PoolStats) -> PoolStats {
+        PoolStats {
+            created: self.created.saturating_sub(earlier.created),
+            recycled: self.recycled.saturating_sub(earlier.recycled),
+            recycle_failures: self
+                .recycle_failures
+                .saturating_sub(earlier.recycle_failures),
+            rejections: self.rejections.diff(&earlier.rejections),
+            ..*self
+        }
+    }
+}
+
+/// Counters backing the [`PoolStats`].
+///
+/// These are updated by every attempt to get an object, so they are kept
+/// lock-free. The wait times are tracked in microseconds to not overflow
+/// on platforms without 64 bit atomics.
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    created: AtomicUsize,
+    recycled: AtomicUsize,
+    recycle_failures: AtomicUsize,
+    wait_count: AtomicUsize,
+    wait_total_micros: AtomicUsize,
+    wait_max_micros: AtomicUsize,
+}
+
+impl StatsCounters {
+    pub(crate) fn created(&self) {
+        let _ = self.created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn recycled(&self) {
+        let _ = self.recycled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn recycle_failed(&self) {
+        let _ = self.recycle_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the time it took to acquire a slot.
+    pub(crate) fn waited(&self, duration: Duration) {
+        let micros = usize::try_from(duration.as_micros()).unwrap_or(usize::MAX);
+        let _ = self.wait_count.fetch_add(1, Ordering::Relaxed);
+        let _ = self.wait_total_micros.fetch_add(micros, Ordering::Relaxed);
+        let _ = self.wait_max_micros.fetch_max(micros, Ordering::Relaxed);
+    }
+
+    /// Returns the current values of all counters and resets the wait
+    /// times.
+    pub(crate) fn take(
+        &self,
+        rejections: Rejections,
+        waiting: usize,
+        unbounded_waiting: usize,
+    ) -> PoolStats {
+        let count = self.wait_count.swap(0, Ordering::Relaxed);
+        let total = Duration::from_micros(self.wait_total_micros.swap(0, Ordering::Relaxed) as u64);
+        let max_wait =
+            Duration::from_micros(self.wait_max_micros.swap(0, Ordering::Relaxed) as u64);
+        let mean_wait = match u32::try_from(count) {
+            Ok(0) => Duration::ZERO,
+            Ok(count) => total / count,
+            Err(_) => total / u32::MAX,
+        };
+        PoolStats {
+            created: self.created.load(Ordering::Relaxed),
+            recycled: self.recycled.load(Ordering::Relaxed),
+            recycle_failures: self.recycle_failures.load(Ordering::Relaxed),
+            rejections,
+            waiting,
+            unbounded_waiting,
+            acquired: count,
+            max_wait,
+            mean_wait,
+        }
+    }
+}
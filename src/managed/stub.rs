@@ -0,0 +1,91 @@
+use std::fmt;
+
+use async_trait::async_trait;
+
+use crate::lock::Mutex;
+
+use super::{Manager, RecycleResult};
+
+/// [`Manager`] serving a fixed set of pre-built objects and never creating
+/// new ones.
+///
+/// This is meant as the secondary [`Pool`] of a [`FallbackPool`] serving a
+/// degraded mode during full backend outages, e.g. with read-only or cached
+/// responses. Every object is handed out by [`Manager::create()`] once and
+/// afterwards recycled without any checks. Once all of them have been handed
+/// out, creating fails with a [`StubExhausted`] error.
+///
+/// # Example
+///
+/// ```rust
+/// use deadpool::managed::{Pool, StubManager};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let pool = Pool::<StubManager<&str>>::builder(StubManager::new(["cached"]))
+///     .max_size(1)
+///     .build()
+///     .unwrap();
+/// assert_eq!(*pool.get().await.unwrap(), "cached");
+/// # }
+/// ```
+///
+/// [`FallbackPool`]: super::fallback::FallbackPool
+/// [`Pool`]: super::Pool
+pub struct StubManager<T> {
+    objects: Mutex<Vec<T>>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `T` type parameter.
+impl<T> fmt::Debug for StubManager<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StubManager")
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+impl<T> StubManager<T> {
+    /// Creates a new [`StubManager`] handing out the given `objects` in
+    /// order.
+    pub fn new(objects: impl IntoIterator<Item = T>) -> Self {
+        let mut objects: Vec<T> = objects.into_iter().collect();
+        objects.reverse();
+        Self {
+            objects: Mutex::new(objects),
+        }
+    }
+
+    /// Returns the number of objects which haven't been handed out yet.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.objects.lock().len()
+    }
+}
+
+#[async_trait]
+impl<T: Send> Manager for StubManager<T> {
+    type Type = T;
+    type Error = StubExhausted;
+
+    async fn create(&self) -> Result<T, StubExhausted> {
+        self.objects.lock().pop().ok_or(StubExhausted)
+    }
+
+    async fn recycle(&self, _obj: &mut T) -> RecycleResult<StubExhausted> {
+        Ok(())
+    }
+}
+
+/// Error returned by a [`StubManager`] once all of its objects have been
+/// handed out.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StubExhausted;
+
+impl fmt::Display for StubExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "All pre-built objects have been handed out")
+    }
+}
+
+impl std::error::Error for StubExhausted {}
@@ -0,0 +1,53 @@
+use std::{future::Future, sync::Arc};
+
+use super::unwind::CatchUnwind;
+
+/// Background task of a [`Pool`] whose panic is passed to the callback
+/// configured via [`PoolBuilder::on_task_panic()`].
+///
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::on_task_panic()`]: super::PoolBuilder::on_task_panic
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BackgroundTask {
+    /// Task spawned via [`Pool::spawn_reaper()`].
+    ///
+    /// [`Pool::spawn_reaper()`]: super::Pool::spawn_reaper
+    Reaper,
+
+    /// Task creating idle [`Object`]s one at a time as configured via
+    /// [`PoolConfig::backfill_interval`].
+    ///
+    /// [`Object`]: super::Object
+    /// [`PoolConfig::backfill_interval`]: super::PoolConfig::backfill_interval
+    Backfill,
+
+    /// Drop of a `SyncWrapper` discarded by the [`Pool`] on a blocking
+    /// thread.
+    ///
+    /// [`Pool`]: super::Pool
+    BlockingDrop,
+}
+
+/// Callback receiving the panics of background tasks as configured via
+/// [`PoolBuilder::on_task_panic()`].
+///
+/// [`PoolBuilder::on_task_panic()`]: super::PoolBuilder::on_task_panic
+pub(crate) type OnTaskPanic = Arc<dyn Fn(BackgroundTask, String) + Send + Sync>;
+
+/// Runs the given background `task` passing a panic of it to `on_panic`
+/// instead of the [`Runtime`] if set.
+///
+/// [`Runtime`]: crate::Runtime
+pub(crate) async fn report_panics<F>(task: BackgroundTask, on_panic: Option<OnTaskPanic>, future: F)
+where
+    F: Future<Output = ()>,
+{
+    match on_panic {
+        Some(on_panic) => {
+            if let Err(msg) = CatchUnwind(Box::pin(future)).await {
+                on_panic(task, msg);
+            }
+        }
+        None => future.await,
+    }
+}
@@ -0,0 +1,62 @@
+//! Synchronous assertions about the internal state of a [`Pool`].
+
+use std::sync::atomic::Ordering;
+
+use super::{Manager, Object, Pool};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
+    /// Asserts that exactly `n` idle [`Object`]s are stored in this [`Pool`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of idle [`Object`]s differs or if the internal
+    /// counters of this [`Pool`] are inconsistent.
+    #[track_caller]
+    pub fn assert_idle(&self, n: usize) {
+        self.assert_consistent();
+        let idle = self.inner.queue.lock().len();
+        assert_eq!(idle, n, "expected {} idle objects, found {}", n, idle);
+    }
+
+    /// Asserts that this [`Pool`] currently manages exactly `n` [`Object`]s,
+    /// idle and in use ones combined.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size differs or if the internal counters of this
+    /// [`Pool`] are inconsistent.
+    #[track_caller]
+    pub fn assert_size(&self, n: usize) {
+        self.assert_consistent();
+        let size = self.inner.size.load(Ordering::Relaxed);
+        assert_eq!(size, n, "expected pool size {}, found {}", n, size);
+    }
+
+    /// Asserts that no task is currently waiting for an [`Object`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any task is waiting or if the internal counters of this
+    /// [`Pool`] are inconsistent.
+    #[track_caller]
+    pub fn assert_no_waiters(&self) {
+        self.assert_consistent();
+        let waiters = self.inner.waiters.len();
+        assert_eq!(waiters, 0, "expected no waiters, found {}", waiters);
+    }
+
+    /// Asserts that all the idle [`Object`]s are accounted for in the size
+    /// of this [`Pool`].
+    #[track_caller]
+    fn assert_consistent(&self) {
+        let idle = self.inner.queue.lock().len();
+        let size = self.inner.size.load(Ordering::Relaxed);
+        assert!(
+            idle <= size,
+            "pool holds {} idle objects but its size is {}",
+            idle,
+            size
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::lock::Mutex;
+
+/// Granularity of the [`UsageHistory`].
+const BUCKET: Duration = Duration::from_secs(1);
+
+/// Number of buckets kept by the [`UsageHistory`].
+const RETENTION: usize = 3600;
+
+/// Usage statistics of a [`Pool`] over a recent window of time as returned
+/// by [`Pool::capacity_report()`].
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::capacity_report()`]: super::Pool::capacity_report
+#[derive(Clone, Copy, Debug)]
+pub struct CapacityReport {
+    /// Window of time covered by this report.
+    ///
+    /// This might be shorter than the requested one if the [`Pool`] hasn't
+    /// existed for that long or the request exceeded the retention of one
+    /// hour.
+    ///
+    /// [`Pool`]: super::Pool
+    pub window: Duration,
+
+    /// Current [`PoolConfig::max_size`].
+    ///
+    /// [`PoolConfig::max_size`]: super::PoolConfig::max_size
+    pub max_size: usize,
+
+    /// Highest number of simultaneously checked out [`Object`]s.
+    ///
+    /// [`Object`]: super::Object
+    pub peak_checked_out: usize,
+
+    /// Highest number of simultaneously waiting tasks.
+    pub peak_waiters: usize,
+
+    /// Number of attempts which timed out.
+    pub timeouts: usize,
+}
+
+impl CapacityReport {
+    /// Returns the [`PoolConfig::max_size`] which would have been needed to
+    /// serve the peak demand of this window without any waiting.
+    ///
+    /// This is the sum of the peak of checked out [`Object`]s and the peak of
+    /// waiting tasks, so it errs on the side of too large.
+    ///
+    /// [`Object`]: super::Object
+    /// [`PoolConfig::max_size`]: super::PoolConfig::max_size
+    #[must_use]
+    pub fn recommended_max_size(&self) -> usize {
+        (self.peak_checked_out + self.peak_waiters).max(1)
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Number of [`BUCKET`]s between the creation of the [`UsageHistory`]
+    /// and the start of this one.
+    index: usize,
+    peak_checked_out: usize,
+    peak_waiters: usize,
+    timeouts: usize,
+}
+
+/// Bucket currently being recorded into without taking any lock.
+#[derive(Debug, Default)]
+struct CurrentBucket {
+    index: AtomicUsize,
+    peak_checked_out: AtomicUsize,
+    peak_waiters: AtomicUsize,
+    timeouts: AtomicUsize,
+}
+
+impl CurrentBucket {
+    fn snapshot(&self) -> Bucket {
+        Bucket {
+            index: self.index.load(Ordering::Relaxed),
+            peak_checked_out: self.peak_checked_out.load(Ordering::Relaxed),
+            peak_waiters: self.peak_waiters.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// History of the usage of a [`Pool`] split into buckets of one second.
+///
+/// Recording only touches the atomics of the current bucket. The lock of
+/// the past `buckets` is taken once per second when moving on to the next
+/// bucket, so a few samples recorded concurrently to that might get lost.
+///
+/// [`Pool`]: super::Pool
+#[derive(Debug)]
+pub(crate) struct UsageHistory {
+    created: Instant,
+    current: CurrentBucket,
+    buckets: Mutex<VecDeque<Bucket>>,
+}
+
+impl Default for UsageHistory {
+    fn default() -> Self {
+        Self {
+            created: Instant::now(),
+            current: CurrentBucket::default(),
+            buckets: Mutex::new(VecDeque::new()),
+        }
+    }
+}
+
+impl UsageHistory {
+    /// Returns the index of the bucket covering the given instant.
+    fn index(&self, now: Instant) -> usize {
+        let elapsed = now.duration_since(self.created).as_secs() / BUCKET.as_secs();
+        usize::try_from(elapsed).unwrap_or(usize::MAX)
+    }
+
+    /// Records the current number of checked out objects and waiting tasks
+    /// and whether an attempt timed out.
+    pub(crate) fn record(&self, checked_out: usize, waiters: usize, timeout: bool) {
+        let index = self.index(Instant::now());
+        let current = &self.current;
+        if current.index.load(Ordering::Relaxed) != index {
+            let mut buckets = self.buckets.lock();
+            // Another thread might have moved on while waiting for the lock.
+            if current.index.load(Ordering::Relaxed) != index {
+                let past = current.snapshot();
+                if past.peak_checked_out > 0 || past.peak_waiters > 0 || past.timeouts > 0 {
+                    if buckets.len() == RETENTION {
+                        let _ = buckets.pop_front();
+                    }
+                    buckets.push_back(past);
+                }
+                current.peak_checked_out.store(0, Ordering::Relaxed);
+                current.peak_waiters.store(0, Ordering::Relaxed);
+                current.timeouts.store(0, Ordering::Relaxed);
+                current.index.store(index, Ordering::Relaxed);
+            }
+        }
+        let _ = current
+            .peak_checked_out
+            .fetch_max(checked_out, Ordering::Relaxed);
+        let _ = current.peak_waiters.fetch_max(waiters, Ordering::Relaxed);
+        if timeout {
+            let _ = current.timeouts.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Summarizes the usage of the given `window` up to now.
+    pub(crate) fn report(&self, window: Duration, max_size: usize) -> CapacityReport {
+        let now = Instant::now();
+        let window = window
+            .min(now.duration_since(self.created))
+            .min(BUCKET * RETENTION as u32);
+        let mut report = CapacityReport {
+            window,
+            max_size,
+            peak_checked_out: 0,
+            peak_waiters: 0,
+            timeouts: 0,
+        };
+        let index = self.index(now);
+        let buckets = self.buckets.lock();
+        let current = self.current.snapshot();
+        let age =
+            |b: &Bucket| BUCKET * u32::try_from(index.saturating_sub(b.index)).unwrap_or(u32::MAX);
+        for bucket in std::iter::once(&current)
+            .chain(buckets.iter().rev())
+            .take_while(|b| age(b) < window + BUCKET)
+        {
+            report.peak_checked_out = report.peak_checked_out.max(bucket.peak_checked_out);
+            report.peak_waiters = report.peak_waiters.max(bucket.peak_waiters);
+            report.timeouts += bucket.timeouts;
+        }
+        report
+    }
+}
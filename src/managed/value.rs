@@ -0,0 +1,75 @@
+use std::{convert::Infallible, fmt, marker::PhantomData};
+
+use async_trait::async_trait;
+
+use super::{Manager, RecycleResult, Reset};
+
+/// [`Manager`] for pooling plain reusable values like byte buffers,
+/// compression contexts or scratch space.
+///
+/// New values are created by calling a closure and recycled by calling
+/// [`Reset::reset()`] on them, so neither creating nor recycling ever fails.
+///
+/// # Example
+///
+/// ```rust
+/// use deadpool::managed::{Pool, ValueManager};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let pool = Pool::<ValueManager<Vec<u8>>>::builder(ValueManager::new(|| {
+///     Vec::with_capacity(4096)
+/// }))
+///     .max_size(16)
+///     .build()
+///     .unwrap();
+/// let mut buf = pool.get().await.unwrap();
+/// buf.extend_from_slice(b"Hello, world!");
+/// # }
+/// ```
+pub struct ValueManager<T, F = fn() -> T> {
+    create: F,
+    _value: PhantomData<fn() -> T>,
+}
+
+// Implemented manually to avoid unnecessary trait bounds on the type
+// parameters.
+impl<T, F> fmt::Debug for ValueManager<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueManager").finish()
+    }
+}
+
+impl<T, F> ValueManager<T, F>
+where
+    T: Reset + Send,
+    F: Fn() -> T + Send + Sync,
+{
+    /// Creates a new [`ValueManager`] which creates values using the given
+    /// `create` closure.
+    pub fn new(create: F) -> Self {
+        Self {
+            create,
+            _value: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<T, F> Manager for ValueManager<T, F>
+where
+    T: Reset + Send,
+    F: Fn() -> T + Send + Sync,
+{
+    type Type = T;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<T, Infallible> {
+        Ok((self.create)())
+    }
+
+    async fn recycle(&self, obj: &mut T) -> RecycleResult<Infallible> {
+        obj.reset();
+        Ok(())
+    }
+}
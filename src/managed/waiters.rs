@@ -0,0 +1,206 @@
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Weak,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::lock::Mutex;
+
+use super::{Manager, PoolInner};
+
+/// Summary of a task waiting for a slot of a [`Pool`] to become available.
+///
+/// Instances of this are returned by the [`Pool::waiters()`] method.
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::waiters()`]: super::Pool::waiters
+#[derive(Clone, Debug)]
+pub struct WaiterInfo {
+    /// The instant when the task started waiting.
+    pub enqueued: Instant,
+
+    /// Label provided via [`Pool::get_labeled()`].
+    ///
+    /// [`Pool::get_labeled()`]: super::Pool::get_labeled
+    pub label: Option<String>,
+
+    /// Indicates whether the task waits without any timeout, e.g. via
+    /// [`Pool::get_unbounded()`].
+    ///
+    /// [`Pool::get_unbounded()`]: super::Pool::get_unbounded
+    pub unbounded: bool,
+}
+
+impl WaiterInfo {
+    /// Returns the time this task has been waiting so far.
+    #[must_use]
+    pub fn waiting(&self) -> Duration {
+        self.enqueued.elapsed()
+    }
+}
+
+/// Registry of all the tasks currently waiting for a slot.
+#[derive(Debug, Default)]
+pub(crate) struct Waiters {
+    next_id: AtomicU64,
+    waiters: Mutex<BTreeMap<u64, WaiterInfo>>,
+    /// Number of entries of the `waiters`, so it can be read on every
+    /// attempt to get an object without taking the lock.
+    len: AtomicUsize,
+}
+
+impl Waiters {
+    /// Registers a new waiter which is removed again when the returned
+    /// [`WaiterGuard`] is dropped.
+    pub(crate) fn register(&self, label: Option<String>, unbounded: bool) -> WaiterGuard<'_> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let info = WaiterInfo {
+            enqueued: Instant::now(),
+            label,
+            unbounded,
+        };
+        let _ = self.waiters.lock().insert(id, info);
+        let _ = self.len.fetch_add(1, Ordering::Relaxed);
+        WaiterGuard { waiters: self, id }
+    }
+
+    /// Returns the number of waiters registered before the one with the
+    /// given `id` or [`None`] if it isn't registered.
+    fn position(&self, id: u64) -> Option<usize> {
+        let waiters = self.waiters.lock();
+        if waiters.contains_key(&id) {
+            Some(waiters.range(..id).count())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of currently registered waiters.
+    pub(crate) fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of currently registered waiters without a
+    /// timeout.
+    pub(crate) fn unbounded_len(&self) -> usize {
+        self.waiters.lock().values().filter(|w| w.unbounded).count()
+    }
+
+    /// Returns all the currently registered waiters ordered by the time they
+    /// started waiting.
+    pub(crate) fn list(&self) -> Vec<WaiterInfo> {
+        self.waiters.lock().values().cloned().collect()
+    }
+}
+
+/// Guard removing a waiter from the [`Waiters`] registry once dropped.
+#[derive(Debug)]
+pub(crate) struct WaiterGuard<'a> {
+    waiters: &'a Waiters,
+    id: u64,
+}
+
+impl WaiterGuard<'_> {
+    /// Returns the ID of the registered waiter.
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl Drop for WaiterGuard<'_> {
+    fn drop(&mut self) {
+        let _ = self.waiters.waiters.lock().remove(&self.id);
+        let _ = self.waiters.len.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Side channel reporting the progress of a [`Pool::get_with_progress()`]
+/// call while it is waiting for a slot.
+///
+/// [`Pool::get_with_progress()`]: super::Pool::get_with_progress
+pub struct WaitProgress<M: Manager> {
+    waiter: Mutex<Option<(Weak<PoolInner<M>>, u64)>>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> fmt::Debug for WaitProgress<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitProgress")
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> Default for WaitProgress<M> {
+    fn default() -> Self {
+        Self {
+            waiter: Mutex::new(None),
+        }
+    }
+}
+
+impl<M: Manager> WaitProgress<M> {
+    /// Creates a new [`WaitProgress`] which isn't tracking anything yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current [`WaitStatus`] or [`None`] if the associated
+    /// [`Pool::get_with_progress()`] call isn't waiting for a slot right now.
+    ///
+    /// [`Pool::get_with_progress()`]: super::Pool::get_with_progress
+    #[must_use]
+    pub fn status(&self) -> Option<WaitStatus> {
+        let (pool, id) = self.waiter.lock().clone()?;
+        let pool = pool.upgrade()?;
+        let position = pool.waiters.position(id)?;
+        let eta = pool.return_rate.retry_after().map(|interval| {
+            let n = u32::try_from(position + 1).unwrap_or(u32::MAX);
+            interval.saturating_mul(n)
+        });
+        Some(WaitStatus { position, eta })
+    }
+
+    /// Starts tracking the waiter with the given `id` until the returned
+    /// [`ProgressGuard`] is dropped.
+    pub(super) fn track(&self, pool: Weak<PoolInner<M>>, id: u64) -> ProgressGuard<'_, M> {
+        *self.waiter.lock() = Some((pool, id));
+        ProgressGuard { progress: self }
+    }
+}
+
+/// Guard resetting a [`WaitProgress`] once dropped.
+pub(crate) struct ProgressGuard<'a, M: Manager> {
+    progress: &'a WaitProgress<M>,
+}
+
+impl<M: Manager> Drop for ProgressGuard<'_, M> {
+    fn drop(&mut self) {
+        *self.progress.waiter.lock() = None;
+    }
+}
+
+/// Progress of a task waiting for a slot of a [`Pool`].
+///
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug)]
+pub struct WaitStatus {
+    /// Number of tasks which are waiting ahead of this one.
+    pub position: usize,
+
+    /// Estimated time until this task gets a slot based on how often
+    /// [`Object`]s have recently been returned to the [`Pool`].
+    ///
+    /// This is [`None`] if no [`Object`]s have been returned yet.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool`]: super::Pool
+    pub eta: Option<Duration>,
+}
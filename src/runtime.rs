@@ -1,6 +1,13 @@
 //! Runtime-specific features.
 
-use std::{any::Any, fmt, future::Future, time::Duration};
+use std::{
+    any::Any,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 
 /// Enumeration for picking a runtime implementation.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -38,6 +45,38 @@ impl Runtime {
         }
     }
 
+    /// Waits until the specified `duration` has elapsed.
+    #[allow(unused_variables)]
+    pub async fn sleep(&self, duration: Duration) {
+        match self {
+            #[cfg(feature = "rt_tokio_1")]
+            Self::Tokio1 => tokio::time::sleep(duration).await,
+            #[cfg(feature = "rt_async-std_1")]
+            Self::AsyncStd1 => async_std::task::sleep(duration).await,
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Spawns the given [`Future`] as a new background task.
+    ///
+    /// With `Runtime::Tokio1` this must be called from within the context of
+    /// a Tokio runtime.
+    #[allow(unused_variables)]
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        match self {
+            #[cfg(feature = "rt_tokio_1")]
+            Self::Tokio1 => drop(tokio::spawn(future)),
+            #[cfg(feature = "rt_async-std_1")]
+            Self::AsyncStd1 => drop(async_std::task::spawn(future)),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
     /// Runs the given closure on a thread where blocking is acceptable.
     ///
     /// # Errors
@@ -51,11 +90,19 @@ impl Runtime {
     {
         match self {
             #[cfg(feature = "rt_tokio_1")]
-            Self::Tokio1 => tokio::task::spawn_blocking(f)
-                .await
-                .map_err(|e| SpawnBlockingError::Panic(e.into_panic())),
+            Self::Tokio1 => tokio::task::spawn_blocking(f).await.map_err(|e| {
+                if e.is_panic() {
+                    SpawnBlockingError::Panic(e.into_panic())
+                } else {
+                    SpawnBlockingError::Cancelled
+                }
+            }),
             #[cfg(feature = "rt_async-std_1")]
-            Self::AsyncStd1 => Ok(async_std::task::spawn_blocking(f).await),
+            Self::AsyncStd1 => async_std::task::spawn_blocking(|| {
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(f))
+            })
+            .await
+            .map_err(SpawnBlockingError::Panic),
             #[allow(unreachable_patterns)]
             _ => unreachable!(),
         }
@@ -66,6 +113,9 @@ impl Runtime {
     /// It works similar to [`Runtime::spawn_blocking()`] but doesn't return a
     /// [`Future`] and is meant to be used for background tasks.
     ///
+    /// Outside of the context of a Tokio runtime, e.g. once it has been shut
+    /// down, `Runtime::Tokio1` runs `f` on the current thread right away.
+    ///
     /// # Errors
     ///
     /// See [`SpawnBlockingError`] for details.
@@ -77,12 +127,20 @@ impl Runtime {
         match self {
             #[cfg(feature = "rt_tokio_1")]
             Self::Tokio1 => {
-                drop(tokio::task::spawn_blocking(f));
+                match tokio::runtime::Handle::try_current() {
+                    Ok(handle) => drop(handle.spawn_blocking(f)),
+                    // Not being within a runtime means not blocking one.
+                    Err(_) => f(),
+                }
                 Ok(())
             }
             #[cfg(feature = "rt_async-std_1")]
             Self::AsyncStd1 => {
-                drop(async_std::task::spawn_blocking(f));
+                // Like Tokio, a panic of a detached task is swallowed instead
+                // of tearing down the blocking thread.
+                drop(async_std::task::spawn_blocking(|| {
+                    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+                }));
                 Ok(())
             }
             #[allow(unreachable_patterns)]
@@ -91,17 +149,79 @@ impl Runtime {
     }
 }
 
+/// Yields execution back to the executor once.
+///
+/// This doesn't depend on any specific [`Runtime`] as it simply wakes itself
+/// up and returns [`Poll::Pending`] the first time it is polled.
+#[cfg_attr(not(feature = "managed"), allow(dead_code))]
+pub(crate) async fn yield_now() {
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    YieldNow(false).await;
+}
+
+/// Drives the given `future` to completion unless `cancel` completes first.
+///
+/// Returns [`None`] if the `future` has been cancelled, in which case it is
+/// dropped without being polled again.
+#[cfg_attr(not(feature = "managed"), allow(dead_code))]
+pub(crate) async fn cancellable<F, C>(future: F, cancel: C) -> Option<F::Output>
+where
+    F: Future,
+    C: Future<Output = ()>,
+{
+    struct Cancellable<F, C> {
+        future: Pin<Box<F>>,
+        cancel: Pin<Box<C>>,
+    }
+
+    impl<F: Future, C: Future<Output = ()>> Future for Cancellable<F, C> {
+        type Output = Option<F::Output>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.cancel.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+            self.future.as_mut().poll(cx).map(Some)
+        }
+    }
+
+    Cancellable {
+        future: Box::pin(future),
+        cancel: Box::pin(cancel),
+    }
+    .await
+}
+
 /// Error of spawning a task on a thread where blocking is acceptable.
 #[derive(Debug)]
 pub enum SpawnBlockingError {
     /// Spawned task has panicked.
     Panic(Box<dyn Any + Send + 'static>),
+
+    /// Spawned task has been cancelled before it completed, e.g. because the
+    /// [`Runtime`] is shutting down.
+    Cancelled,
 }
 
 impl fmt::Display for SpawnBlockingError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Panic(p) => write!(f, "SpawnBlockingError: Panic: {:?}", p),
+            Self::Cancelled => write!(f, "SpawnBlockingError: Cancelled"),
         }
     }
 }
@@ -0,0 +1,674 @@
+//! Helpers for writing pools for objects that don't support async and need to
+//! be run inside a thread.
+//!
+//! This is also available as `managed::sync` for backwards compatibility.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt,
+    marker::PhantomData,
+    ops::DerefMut,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::sync::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
+
+use crate::{runtime::SpawnBlockingError, Runtime};
+
+#[cfg(feature = "managed")]
+use crate::managed::{
+    task_panic::OnTaskPanic, unwind::panic_message, BackgroundTask, Manager, Object, Pool,
+    SelfTestReport,
+};
+
+/// Number of [`SyncWrapper`]s whose object is still waiting to be dropped on a
+/// blocking thread.
+static PENDING_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of [`SyncWrapper`]s whose object has been handed over
+/// to a blocking thread for dropping but hasn't been dropped yet.
+///
+/// This covers all [`SyncWrapper`]s of this process including the ones
+/// dropped due to evictions or recycling failures. A steadily growing number
+/// is a sign that the blocking thread pool of the [`Runtime`] is saturated.
+#[must_use]
+pub fn pending_drops() -> usize {
+    PENDING_DROPS.load(Ordering::Relaxed)
+}
+
+/// Pool dropping objects on the current thread as set by
+/// [`attribute_drops()`].
+#[derive(Clone)]
+struct DropScope {
+    /// Counter of the pending drops of the pool.
+    counter: Arc<AtomicUsize>,
+    /// Callback receiving the panics of the drops of the pool.
+    #[cfg(feature = "managed")]
+    on_panic: Option<OnTaskPanic>,
+}
+
+thread_local! {
+    /// Pool currently dropping objects on this thread.
+    // Initializing this via `const {}` requires Rust 1.59.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static POOL_DROPS: RefCell<Option<DropScope>> = RefCell::new(None);
+}
+
+/// Runs `f` additionally counting the pending drops of all [`SyncWrapper`]s
+/// dropped meanwhile on this thread in the given `counter` and passing
+/// their panics to `on_panic`, if set.
+///
+/// This allows pools to track their own pending drops next to the
+/// process-wide [`pending_drops()`].
+#[cfg(feature = "managed")]
+pub(crate) fn attribute_drops<R>(
+    counter: &Arc<AtomicUsize>,
+    on_panic: Option<&OnTaskPanic>,
+    f: impl FnOnce() -> R,
+) -> R {
+    let scope = DropScope {
+        counter: counter.clone(),
+        on_panic: on_panic.cloned(),
+    };
+    let previous = POOL_DROPS.with(|c| c.replace(Some(scope)));
+    // Restores the previous scope even if `f` panics.
+    struct Restore(Option<DropScope>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            POOL_DROPS.with(|c| *c.borrow_mut() = previous);
+        }
+    }
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Guard counting a pending drop of a [`SyncWrapper`] in [`PENDING_DROPS`]
+/// and the counter of the pool dropping it, if any.
+struct PendingDrop {
+    pool: Option<DropScope>,
+}
+
+impl PendingDrop {
+    fn new() -> Self {
+        let _ = PENDING_DROPS.fetch_add(1, Ordering::Relaxed);
+        let pool = POOL_DROPS.with(|c| c.borrow().clone());
+        if let Some(pool) = &pool {
+            let _ = pool.counter.fetch_add(1, Ordering::Relaxed);
+        }
+        Self { pool }
+    }
+
+    /// Passes the panic of the drop to the callback of the pool dropping it
+    /// or resumes it if there is none.
+    fn panicked(&self, payload: Box<dyn Any + Send>) {
+        #[cfg(feature = "managed")]
+        if let Some(on_panic) = self.pool.as_ref().and_then(|p| p.on_panic.as_ref()) {
+            on_panic(BackgroundTask::BlockingDrop, panic_message(&*payload));
+            return;
+        }
+        panic::resume_unwind(payload);
+    }
+}
+
+impl Drop for PendingDrop {
+    fn drop(&mut self) {
+        let _ = PENDING_DROPS.fetch_sub(1, Ordering::Relaxed);
+        if let Some(pool) = &self.pool {
+            let _ = pool.counter.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Possible errors returned when [`SyncWrapper::new()`] or
+/// [`SyncWrapper::interact()`] fail.
+#[derive(Debug)]
+pub enum InteractError<E> {
+    /// Provided callback has panicked.
+    Panic(Box<dyn Any + Send + 'static>),
+
+    /// Callback was aborted.
+    ///
+    /// The underlying object is left in an unknown state, so the
+    /// [`SyncWrapper`] is marked as aborted afterwards.
+    Aborted(AbortReason),
+
+    /// Backend returned an error.
+    Backend(E),
+}
+
+impl<E: fmt::Display> fmt::Display for InteractError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic(_) => write!(f, "Panic"),
+            Self::Aborted(reason) => write!(f, "Aborted: {}", reason),
+            Self::Backend(e) => write!(f, "Backend error: {}", e),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for InteractError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Panic(_) | Self::Aborted(_) => None,
+            Self::Backend(e) => Some(e),
+        }
+    }
+}
+
+/// Reason why [`SyncWrapper::interact()`] has been aborted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AbortReason {
+    /// Blocking task has been cancelled because the [`Runtime`] is shutting
+    /// down.
+    Shutdown,
+
+    /// Closure didn't complete within the timeout passed to
+    /// [`SyncWrapper::interact_timeout()`].
+    ///
+    /// The blocking thread can't be interrupted, so the closure might still
+    /// be running and holding the lock of the object.
+    Timeout,
+
+    /// A previous interaction panicked while holding the lock of the object,
+    /// so it is left in an unknown state.
+    Poisoned,
+}
+
+impl fmt::Display for AbortReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shutdown => write!(f, "Runtime is shutting down"),
+            Self::Timeout => write!(f, "Timeout occurred"),
+            Self::Poisoned => write!(f, "Object is poisoned"),
+        }
+    }
+}
+
+/// Time spent in the phases of a [`SyncWrapper::interact_timed()`] call.
+///
+/// This tells apart a saturated blocking thread pool from a slow backend.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct InteractTiming {
+    /// Time spent waiting for a blocking thread to pick up the closure.
+    pub queued: Duration,
+
+    /// Time spent waiting for the lock of the object, e.g. while a previous
+    /// closure which timed out is still running.
+    pub lock_wait: Duration,
+
+    /// Time spent executing the closure.
+    pub executed: Duration,
+}
+
+impl InteractTiming {
+    /// Returns the sum of all phases.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.queued + self.lock_wait + self.executed
+    }
+}
+
+/// Async half of an object split off its [`SyncWrapper`] via
+/// [`SyncWrapper::split_async()`].
+///
+/// It is guarded by an async lock of its own, so it can be used from async
+/// code without a blocking thread while blocking interactions with the rest
+/// of the object keep going through [`SyncWrapper::interact()`].
+pub struct AsyncHalf<A> {
+    inner: Arc<AsyncMutex<A>>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `A` type parameter.
+impl<A> fmt::Debug for AsyncHalf<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncHalf")
+            .field("locked", &self.inner.try_lock().is_err())
+            .finish()
+    }
+}
+
+impl<A> Clone for AsyncHalf<A> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<A> AsyncHalf<A> {
+    /// Waits for exclusive access to the async half.
+    pub async fn lock(&self) -> AsyncMutexGuard<'_, A> {
+        self.inner.lock().await
+    }
+}
+
+/// Wrapper for objects which only provides blocking functions that need to be
+/// called on a separate thread.
+///
+/// Access to the wrapped object is provided via the [`SyncWrapper::interact()`]
+/// method.
+#[must_use]
+pub struct SyncWrapper<T, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    /// Only [`None`] while the [`SyncWrapper`] is being dropped.
+    obj: Option<Arc<Mutex<T>>>,
+    runtime: Runtime,
+    /// Set once an interaction has been aborted.
+    aborted: AtomicBool,
+    _error: PhantomData<fn() -> E>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `E` type parameter.
+impl<T, E> fmt::Debug for SyncWrapper<T, E>
+where
+    T: fmt::Debug + Send + 'static,
+    E: Send + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncWrapper")
+            .field("obj", self.obj())
+            .field("runtime", &self.runtime)
+            .field("aborted", &self.aborted)
+            .field("_error", &self._error)
+            .finish()
+    }
+}
+
+impl<T, E> SyncWrapper<T, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    /// Creates a new wrapped object.
+    ///
+    /// # Errors
+    ///
+    /// If `f` panics, [`InteractError::Panic`] is returned. If the blocking
+    /// task is cancelled because the [`Runtime`] is shutting down,
+    /// [`InteractError::Aborted`] is returned. Errors of `f` itself are
+    /// returned as [`InteractError::Backend`].
+    pub async fn new<F>(runtime: Runtime, f: F) -> Result<Self, InteractError<E>>
+    where
+        F: FnOnce() -> Result<T, E> + Send + 'static,
+    {
+        let obj = match runtime.spawn_blocking(f).await {
+            Ok(result) => result.map_err(InteractError::Backend)?,
+            Err(SpawnBlockingError::Panic(p)) => return Err(InteractError::Panic(p)),
+            Err(SpawnBlockingError::Cancelled) => {
+                return Err(InteractError::Aborted(AbortReason::Shutdown))
+            }
+        };
+        Ok(Self {
+            obj: Some(Arc::new(Mutex::new(obj))),
+            runtime,
+            aborted: AtomicBool::new(false),
+            _error: PhantomData,
+        })
+    }
+
+    /// Interacts with the underlying object.
+    ///
+    /// Expects a closure that takes the object as its parameter.
+    /// The closure is executed in a separate thread so that the async runtime
+    /// is not blocked.
+    pub async fn interact<F, R>(&self, f: F) -> Result<R, InteractError<E>>
+    where
+        F: FnOnce(&mut T) -> Result<R, E> + Send + 'static,
+        R: Send + 'static,
+    {
+        let arc = self.inner_obj();
+        framed!(self.runtime.spawn_blocking(move || {
+            let mut conn = arc.lock().ok()?;
+            Some(f(&mut *conn))
+        }))
+        .await
+        .map_err(|e| self.interact_error(e))?
+        .ok_or_else(|| self.poisoned())?
+        .map_err(InteractError::Backend)
+    }
+
+    /// Interacts with the underlying object like [`SyncWrapper::interact()`]
+    /// and measures the time spent in each phase of the interaction.
+    ///
+    /// The [`InteractTiming`] is [`None`] if the closure panicked or never
+    /// ran, e.g. because the [`Runtime`] is shutting down or the object is
+    /// poisoned.
+    pub async fn interact_timed<F, R>(
+        &self,
+        f: F,
+    ) -> (Result<R, InteractError<E>>, Option<InteractTiming>)
+    where
+        F: FnOnce(&mut T) -> Result<R, E> + Send + 'static,
+        R: Send + 'static,
+    {
+        let arc = self.inner_obj();
+        let spawned = Instant::now();
+        let result = framed!(self.runtime.spawn_blocking(move || {
+            let started = Instant::now();
+            let mut conn = arc.lock().ok()?;
+            let locked = Instant::now();
+            let result = f(&mut *conn);
+            let timing = InteractTiming {
+                queued: started.duration_since(spawned),
+                lock_wait: locked.duration_since(started),
+                executed: locked.elapsed(),
+            };
+            Some((result, timing))
+        }))
+        .await;
+        match result {
+            Ok(Some((result, timing))) => (result.map_err(InteractError::Backend), Some(timing)),
+            Ok(None) => (Err(self.poisoned()), None),
+            Err(e) => (Err(self.interact_error(e)), None),
+        }
+    }
+
+    /// Interacts with the underlying object like [`SyncWrapper::interact()`]
+    /// but gives up if the closure doesn't complete within the given
+    /// `timeout`.
+    ///
+    /// On expiry [`InteractError::Aborted`] is returned and this
+    /// [`SyncWrapper`] is marked as aborted as the closure keeps running on
+    /// the blocking thread.
+    pub async fn interact_timeout<F, R>(
+        &self,
+        timeout: Duration,
+        f: F,
+    ) -> Result<R, InteractError<E>>
+    where
+        F: FnOnce(&mut T) -> Result<R, E> + Send + 'static,
+        R: Send + 'static,
+    {
+        match self.runtime.timeout(timeout, self.interact(f)).await {
+            Some(result) => result,
+            None => {
+                self.aborted.store(true, Ordering::Relaxed);
+                Err(InteractError::Aborted(AbortReason::Timeout))
+            }
+        }
+    }
+
+    /// Interacts with the underlying object writing the result into the
+    /// given reusable `buf`.
+    ///
+    /// This works like [`SyncWrapper::interact()`] but instead of returning
+    /// a freshly allocated payload, the closure appends its output to `buf`
+    /// which is handed back afterwards. Combined with a pool of buffers
+    /// (e.g. one using [`ValueManager`]) this avoids allocation churn for
+    /// blob-heavy workloads.
+    ///
+    /// If the interaction fails, `buf` isn't handed back but dropped
+    /// together with whatever the closure wrote to it. A pooled buffer is
+    /// returned to its [`Pool`] this way, so only its contents are lost.
+    ///
+    /// [`Pool`]: crate::managed::Pool
+    /// [`ValueManager`]: crate::managed::ValueManager
+    pub async fn interact_bytes<F, B>(&self, mut buf: B, f: F) -> Result<B, InteractError<E>>
+    where
+        F: FnOnce(&mut T, &mut Vec<u8>) -> Result<(), E> + Send + 'static,
+        B: DerefMut<Target = Vec<u8>> + Send + 'static,
+    {
+        let arc = self.inner_obj();
+        framed!(self.runtime.spawn_blocking(move || {
+            let mut conn = arc.lock().ok()?;
+            Some(f(&mut *conn, &mut buf).map(|()| buf))
+        }))
+        .await
+        .map_err(|e| self.interact_error(e))?
+        .ok_or_else(|| self.poisoned())?
+        .map_err(InteractError::Backend)
+    }
+
+    /// Splits off the async half of the object, e.g. a notification stream
+    /// of a mostly blocking client.
+    ///
+    /// The `accessor` runs like an interaction. Its output is handed out as
+    /// [`AsyncHalf`] which can be awaited on without going through a
+    /// blocking thread and without holding the lock of the object, so
+    /// [`SyncWrapper::interact()`] keeps exclusive access to the blocking
+    /// half.
+    pub async fn split_async<F, A>(&self, accessor: F) -> Result<AsyncHalf<A>, InteractError<E>>
+    where
+        F: FnOnce(&mut T) -> A + Send + 'static,
+        A: Send + 'static,
+    {
+        self.interact(move |obj| Ok(accessor(obj)))
+            .await
+            .map(|half| AsyncHalf {
+                inner: Arc::new(AsyncMutex::new(half)),
+            })
+    }
+
+    /// Converts the given [`SpawnBlockingError`] of an interaction into an
+    /// [`InteractError`] marking this [`SyncWrapper`] as aborted if needed.
+    fn interact_error(&self, e: SpawnBlockingError) -> InteractError<E> {
+        match e {
+            SpawnBlockingError::Panic(p) => InteractError::Panic(p),
+            SpawnBlockingError::Cancelled => {
+                self.aborted.store(true, Ordering::Relaxed);
+                InteractError::Aborted(AbortReason::Shutdown)
+            }
+        }
+    }
+
+    /// Returns the [`InteractError`] of an interaction which found the
+    /// object to be poisoned marking this [`SyncWrapper`] as aborted.
+    fn poisoned(&self) -> InteractError<E> {
+        self.aborted.store(true, Ordering::Relaxed);
+        InteractError::Aborted(AbortReason::Poisoned)
+    }
+
+    /// Get the underlying object wrapped in a Mutex that's wrapped in an Arc.
+    ///
+    /// Note: Anything you do with the object should be wrapped in a `spawn_blocking` closure
+    /// so that the async runtime is not blocked.
+    ///
+    /// A panic while using the returned [`Arc`] isn't noticed by the
+    /// `Object` holding this [`SyncWrapper`] right away. The `Object` is only
+    /// marked as broken once a later interaction through it finds the
+    /// [`Mutex`] to be poisoned, so [`Manager`]s handing out this [`Arc`]
+    /// should check [`SyncWrapper::is_mutex_poisoned()`] when recycling.
+    ///
+    /// [`Manager`]: crate::managed::Manager
+    pub fn inner_obj(&self) -> Arc<Mutex<T>> {
+        self.obj().clone()
+    }
+
+    /// Indicates whether the underlying [`Mutex`] has been poisoned.
+    ///
+    /// This happens when a panic occurs while interacting with the object.
+    pub fn is_mutex_poisoned(&self) -> bool {
+        self.obj().is_poisoned()
+    }
+
+    /// Indicates whether an interaction with the object has been aborted.
+    ///
+    /// This happens when the blocking task running the closure is cancelled
+    /// and leaves the object in an unknown state.
+    pub fn is_aborted(&self) -> bool {
+        self.aborted.load(Ordering::Relaxed)
+    }
+
+    fn obj(&self) -> &Arc<Mutex<T>> {
+        self.obj.as_ref().unwrap()
+    }
+}
+
+#[cfg(feature = "managed")]
+#[cfg_attr(docsrs, doc(cfg(feature = "managed")))]
+impl<M, T, E> Object<M>
+where
+    M: Manager<Type = SyncWrapper<T, E>>,
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    /// Interacts with the underlying object of the [`SyncWrapper`].
+    ///
+    /// This does the same as [`SyncWrapper::interact()`], but additionally
+    /// marks this [`Object`] as broken if the closure panics or is aborted,
+    /// so it is discarded instead of being returned to its [`Pool`].
+    ///
+    /// The same happens if the [`Mutex`] of the [`SyncWrapper`] is found to
+    /// be poisoned afterwards, e.g. because the [`Arc`] returned by
+    /// [`SyncWrapper::inner_obj()`] has been used incorrectly. This way sync
+    /// [`Manager`]s don't need to check [`SyncWrapper::is_mutex_poisoned()`]
+    /// in their [`Manager::recycle()`] implementation as long as all access
+    /// goes through this method.
+    ///
+    /// [`Pool`]: crate::managed::Pool
+    pub async fn interact<F, R>(&self, f: F) -> Result<R, InteractError<E>>
+    where
+        F: FnOnce(&mut T) -> Result<R, E> + Send + 'static,
+        R: Send + 'static,
+    {
+        let result = (**self).interact(f).await;
+        self.check_interaction(result)
+    }
+
+    /// Interacts with the underlying object of the [`SyncWrapper`] giving up
+    /// after the given `timeout`.
+    ///
+    /// This does the same as [`SyncWrapper::interact_timeout()`], but
+    /// additionally marks this [`Object`] as broken like
+    /// [`Object::interact()`] does, including when the timeout expires.
+    pub async fn interact_timeout<F, R>(
+        &self,
+        timeout: Duration,
+        f: F,
+    ) -> Result<R, InteractError<E>>
+    where
+        F: FnOnce(&mut T) -> Result<R, E> + Send + 'static,
+        R: Send + 'static,
+    {
+        let result = (**self).interact_timeout(timeout, f).await;
+        self.check_interaction(result)
+    }
+
+    /// Interacts with the underlying object of the [`SyncWrapper`] measuring
+    /// the time spent in each phase.
+    ///
+    /// This does the same as [`SyncWrapper::interact_timed()`], but
+    /// additionally marks this [`Object`] as broken like
+    /// [`Object::interact()`] does.
+    pub async fn interact_timed<F, R>(
+        &self,
+        f: F,
+    ) -> (Result<R, InteractError<E>>, Option<InteractTiming>)
+    where
+        F: FnOnce(&mut T) -> Result<R, E> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (result, timing) = (**self).interact_timed(f).await;
+        (self.check_interaction(result), timing)
+    }
+
+    /// Interacts with the underlying object of the [`SyncWrapper`] writing
+    /// the result into the given reusable `buf`.
+    ///
+    /// This does the same as [`SyncWrapper::interact_bytes()`], but
+    /// additionally marks this [`Object`] as broken like
+    /// [`Object::interact()`] does.
+    pub async fn interact_bytes<F, B>(&self, buf: B, f: F) -> Result<B, InteractError<E>>
+    where
+        F: FnOnce(&mut T, &mut Vec<u8>) -> Result<(), E> + Send + 'static,
+        B: DerefMut<Target = Vec<u8>> + Send + 'static,
+    {
+        let result = (**self).interact_bytes(buf, f).await;
+        self.check_interaction(result)
+    }
+
+    /// Splits off the async half of the underlying object of the
+    /// [`SyncWrapper`].
+    ///
+    /// This does the same as [`SyncWrapper::split_async()`], but
+    /// additionally marks this [`Object`] as broken like
+    /// [`Object::interact()`] does if the `accessor` panics. Once split off,
+    /// the [`AsyncHalf`] is used without going through this [`Object`], so
+    /// it can't be marked as broken for anything happening afterwards.
+    pub async fn split_async<F, A>(&self, accessor: F) -> Result<AsyncHalf<A>, InteractError<E>>
+    where
+        F: FnOnce(&mut T) -> A + Send + 'static,
+        A: Send + 'static,
+    {
+        let result = (**self).split_async(accessor).await;
+        self.check_interaction(result)
+    }
+
+    /// Marks this [`Object`] as broken if the given `result` of an
+    /// interaction indicates that it is left in an unknown state.
+    fn check_interaction<R>(
+        &self,
+        result: Result<R, InteractError<E>>,
+    ) -> Result<R, InteractError<E>> {
+        if matches!(result, Err(InteractError::Panic(_)))
+            || self.is_aborted()
+            || self.is_mutex_poisoned()
+        {
+            Object::mark_broken(self);
+        }
+        result
+    }
+}
+
+#[cfg(feature = "managed")]
+#[cfg_attr(docsrs, doc(cfg(feature = "managed")))]
+impl<M, T, E> Pool<M>
+where
+    M: Manager<Type = SyncWrapper<T, E>>,
+    T: Send + 'static,
+    E: fmt::Display + Send + 'static,
+{
+    /// Runs [`Pool::self_test()`] additionally interacting with the
+    /// temporary object after recycling it.
+    ///
+    /// The interaction does nothing with the underlying object, so this
+    /// verifies that the blocking threads of the [`Runtime`] are available.
+    pub async fn self_test_interact(&self) -> SelfTestReport<M::Error> {
+        self.self_test_with(&|obj: &SyncWrapper<T, E>| {
+            Box::pin(async move { obj.interact(|_| Ok(())).await.map_err(|e| e.to_string()) })
+        })
+        .await
+    }
+}
+
+impl<T, E> Drop for SyncWrapper<T, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    fn drop(&mut self) {
+        // Taking the object out of `self` makes sure that the last reference
+        // is dropped on the blocking thread and not here.
+        let arc = match self.obj.take() {
+            Some(arc) => arc,
+            None => return,
+        };
+        let pending = PendingDrop::new();
+        // Drop the internal connection inside a `spawn_blocking`
+        // as the `drop` function of it can block.
+        self.runtime
+            .spawn_blocking_background(move || {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    match arc.lock() {
+                        Ok(guard) => drop(guard),
+                        Err(e) => drop(e.into_inner()),
+                    }
+                    drop(arc);
+                }));
+                if let Err(payload) = result {
+                    pending.panicked(payload);
+                }
+            })
+            .unwrap();
+    }
+}
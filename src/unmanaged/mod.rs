@@ -36,14 +36,16 @@ use std::{
     ops::{Deref, DerefMut},
     sync::{
         atomic::{AtomicIsize, AtomicUsize, Ordering},
-        Arc, Mutex, Weak,
+        Arc, Weak,
     },
     time::Duration,
 };
 
 use tokio::sync::{Semaphore, TryAcquireError};
 
-pub use crate::Status;
+use crate::lock::Mutex;
+
+pub use crate::{Rejections, Status};
 
 pub use self::{config::PoolConfig, errors::PoolError};
 
@@ -81,7 +83,7 @@ impl<T> Drop for Object<T> {
         if let Some(obj) = self.obj.take() {
             if let Some(pool) = self.pool.upgrade() {
                 {
-                    let mut queue = pool.queue.lock().unwrap();
+                    let mut queue = pool.queue.lock();
                     queue.push(obj);
                 }
                 let _ = pool.available.fetch_add(1, Ordering::Relaxed);
@@ -194,7 +196,7 @@ impl<T> Pool<T> {
             TryAcquireError::Closed => PoolError::Closed,
         })?;
         let obj = {
-            let mut queue = inner.queue.lock().unwrap();
+            let mut queue = inner.queue.lock();
             queue.pop().unwrap()
         };
         permit.forget();
@@ -233,7 +235,7 @@ impl<T> Pool<T> {
             (Some(_), None) => Err(PoolError::NoRuntimeSpecified),
         }?;
         let obj = {
-            let mut queue = inner.queue.lock().unwrap();
+            let mut queue = inner.queue.lock();
             queue.pop().unwrap()
         };
         permit.forget();
@@ -293,7 +295,7 @@ impl<T> Pool<T> {
     fn _add(&self, object: T) {
         let _ = self.inner.size.fetch_add(1, Ordering::Relaxed);
         {
-            let mut queue = self.inner.queue.lock().unwrap();
+            let mut queue = self.inner.queue.lock();
             queue.push(object);
         }
         let _ = self.inner.available.fetch_add(1, Ordering::Relaxed);
@@ -341,6 +343,8 @@ impl<T> Pool<T> {
             max_size,
             size,
             available,
+            rejections: Rejections::default(),
+            pending_blocking_drops: 0,
         }
     }
 }
@@ -378,7 +382,7 @@ impl<T> PoolInner<T> {
 
     /// Removes all the [`Object`]s which are currently part of this [`Pool`].
     fn clear(&self) {
-        let mut queue = self.queue.lock().unwrap();
+        let mut queue = self.queue.lock();
         let _ = self.size.fetch_sub(queue.len(), Ordering::Relaxed);
         let _ = self
             .available
@@ -1,11 +1,22 @@
 #![cfg(feature = "managed")]
 
-use std::{convert::Infallible, time::Duration};
+use std::{
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use tokio::time;
 
-use deadpool::managed::{self, Object, PoolError, RecycleResult};
+use deadpool::managed::{
+    self, eviction, BackendLimitExceeded, BackgroundTask, CreateLimit, DiscardReason,
+    ErrorRateLimit, LocalObject, NoopManager, Object, OnExhausted, PoolError, Quarantine,
+    QueueMode, RecycleExperiment, RecycleResult, SharedObject, TimeoutType, Timeouts, ValueManager,
+};
 
 type Pool = managed::Pool<Manager>;
 
@@ -25,6 +36,74 @@ impl managed::Manager for Manager {
     }
 }
 
+/// Manager creating consecutive numbers with knobs for the optional hooks
+/// of [`managed::Manager`] which [`Manager`] leaves at their defaults.
+#[derive(Default)]
+struct CountingManager {
+    next: AtomicUsize,
+    /// Makes recycling fail while set.
+    failing: Arc<AtomicBool>,
+    /// Returned by `size_limit()`.
+    size_limit: Option<usize>,
+    /// Returned by `memory_usage()` for every object.
+    memory_usage: usize,
+    /// Incremented by the companion of an object once it's aborted.
+    companions: Option<Arc<AtomicUsize>>,
+    /// Records the calls to `attach_pool()` and `detach_pool()`.
+    lifecycle: Option<Arc<Lifecycle>>,
+}
+
+#[derive(Default)]
+struct Lifecycle {
+    handle: Mutex<Option<managed::PoolHandle>>,
+    detached: AtomicUsize,
+}
+
+#[async_trait]
+impl managed::Manager for CountingManager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(self.next.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _conn: &mut usize) -> RecycleResult<Infallible> {
+        if self.failing.load(Ordering::Relaxed) {
+            Err(managed::RecycleError::Message("failing".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn attach_pool(&self, handle: &managed::PoolHandle) {
+        if let Some(lifecycle) = &self.lifecycle {
+            *lifecycle.handle.lock().unwrap() = Some(handle.clone());
+        }
+    }
+
+    fn detach_pool(&self) {
+        if let Some(lifecycle) = &self.lifecycle {
+            let _ = lifecycle.detached.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn companion(&self, _obj: &mut usize) -> Option<managed::Companion> {
+        let aborted = self.companions.clone()?;
+        Some(managed::Companion::new(move || {
+            let _ = aborted.fetch_add(1, Ordering::Relaxed);
+        }))
+    }
+
+    fn size_limit(&self, _conn: &usize) -> Option<usize> {
+        self.size_limit
+    }
+
+    fn memory_usage(&self, _conn: &usize) -> usize {
+        self.memory_usage
+    }
+}
+
 #[tokio::test]
 async fn basic() {
     let mgr = Manager {};
@@ -80,7 +159,10 @@ async fn closing() {
     tokio::task::yield_now().await;
     assert_eq!(pool.status().available, -1);
 
-    pool.close();
+    let report = pool.close();
+    assert_eq!(report.closed, 0);
+    assert_eq!(report.checked_out, 1);
+    assert_eq!(report.waiters_rejected, 1);
     tokio::task::yield_now().await;
     assert_eq!(pool.status().available, 0);
 
@@ -162,3 +244,2002 @@ async fn object_take() {
     assert_eq!(status.size, 2);
     assert_eq!(status.available, 2);
 }
+
+#[tokio::test]
+async fn with_timeouts() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    let fast = pool.with_timeouts(Timeouts::wait_millis(0));
+    assert_eq!(fast.timeouts().wait, Some(Duration::from_millis(0)));
+    assert_eq!(pool.timeouts().wait, None);
+
+    let obj = pool.get().await.unwrap();
+    assert!(matches!(
+        fast.get().await,
+        Err(PoolError::Timeout(TimeoutType::Wait))
+    ));
+    assert!(time::timeout(Duration::from_millis(10), pool.get())
+        .await
+        .is_err());
+
+    drop(obj);
+    let _obj = fast.get().await.unwrap();
+    assert_eq!(pool.status().size, 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn with_timeouts_recycle_now() {
+    let mgr = NoopManager::with_latency(
+        deadpool::Runtime::Tokio1,
+        Duration::ZERO,
+        Duration::from_secs(1),
+    );
+    let pool = managed::Pool::<NoopManager>::builder(mgr)
+        .max_size(1)
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let fast = pool.with_timeouts(Timeouts {
+        recycle: Some(Duration::from_millis(10)),
+        ..Timeouts::default()
+    });
+
+    // The recycle timeout of the handle applies, so the object is replaced
+    // instead of waiting for the slow recycling.
+    let obj = fast.get().await.unwrap();
+    let obj = time::timeout(Duration::from_millis(500), Object::recycle_now(obj))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*obj, 1);
+}
+
+#[tokio::test]
+async fn get_traced() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let (obj, info) = pool.get_traced().await.unwrap();
+    assert!(info.created_new);
+    assert!(!info.recycled);
+    drop(obj);
+
+    let (_obj, info) = pool.get_traced().await.unwrap();
+    assert!(!info.created_new);
+    assert!(info.recycled);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn cooperative_yield() {
+    for cooperative_yield in [false, true] {
+        let pool = Pool::builder(Manager {})
+            .max_size(1)
+            .cooperative_yield(cooperative_yield)
+            .build()
+            .unwrap();
+
+        let obj = pool.get().await.unwrap();
+        let waiter = {
+            let pool = pool.clone();
+            tokio::spawn(async move { *pool.get().await.unwrap() += 1 })
+        };
+        tokio::task::yield_now().await;
+        assert_eq!(pool.status().available, -1);
+
+        // The hot task returns its object and immediately tries to get it
+        // again. The slot has been handed to the waiting task already, which
+        // only gets a chance to run and return it if the hot task yields.
+        drop(obj);
+        let result = pool.try_get().await;
+        if cooperative_yield {
+            assert_eq!(*result.unwrap(), 1);
+        } else {
+            assert!(matches!(result, Err(PoolError::Timeout(TimeoutType::Wait))));
+            drop(result);
+        }
+        waiter.await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn cooperative_yield_cancelled() {
+    let pool = Pool::builder(Manager {})
+        .max_size(1)
+        .cooperative_yield(true)
+        .build()
+        .unwrap();
+
+    let obj = pool.get().await.unwrap();
+    let waiter = {
+        let pool = pool.clone();
+        tokio::spawn(async move { drop(pool.get().await.unwrap()) })
+    };
+    tokio::task::yield_now().await;
+    assert_eq!(pool.status().available, -1);
+
+    // Dropping a `get()` while it yields to the waiting task must leave
+    // the accounting untouched.
+    tokio::select! {
+        biased;
+        _ = pool.get() => unreachable!(),
+        _ = async {} => {}
+    }
+    assert_eq!(pool.status().available, -1);
+
+    drop(obj);
+    waiter.await.unwrap();
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn seed() {
+    let pool = Pool::builder(Manager {})
+        .max_size(3)
+        .seed(vec![1, 2])
+        .build()
+        .unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+
+    let obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    let obj3 = pool.get().await.unwrap();
+    assert_eq!((*obj1, *obj2, *obj3), (1, 2, 0));
+    assert_eq!(pool.status().size, 3);
+
+    assert!(matches!(
+        Pool::builder(Manager {})
+            .max_size(1)
+            .seed(vec![1, 2])
+            .build(),
+        Err(managed::BuildError::Config(_))
+    ));
+}
+
+#[tokio::test]
+async fn capacity_limiter() {
+    let limiter = managed::CapacityLimiter::new(3);
+    let pool_a = Pool::builder(Manager {})
+        .max_size(2)
+        .capacity_limiter(limiter.clone())
+        .build()
+        .unwrap();
+    let pool_b = Pool::builder(Manager {})
+        .max_size(2)
+        .capacity_limiter(limiter.clone())
+        .build()
+        .unwrap();
+
+    let a1 = pool_a.get().await.unwrap();
+    let a2 = pool_a.get().await.unwrap();
+    let b1 = pool_b.get().await.unwrap();
+    assert_eq!(limiter.available(), 0);
+    assert!(time::timeout(Duration::from_millis(10), pool_b.get())
+        .await
+        .is_err());
+
+    // Idle objects keep their slot until they are evicted.
+    drop(a1);
+    assert_eq!(limiter.available(), 0);
+    assert_eq!(pool_a.evict_idle(1), 1);
+    assert_eq!(limiter.available(), 1);
+    let b2 = pool_b.get().await.unwrap();
+    assert_eq!(limiter.available(), 0);
+
+    drop((a2, b1, b2));
+    drop(pool_a);
+    drop(pool_b);
+    assert_eq!(limiter.available(), 3);
+}
+
+#[test]
+fn capacity_limiter_seed() {
+    let limiter = managed::CapacityLimiter::new(1);
+    let result = Pool::builder(Manager {})
+        .max_size(2)
+        .capacity_limiter(limiter.clone())
+        .seed(vec![1, 2])
+        .build();
+    assert!(matches!(result, Err(managed::BuildError::Config(_))));
+    assert_eq!(limiter.available(), 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn capacity_limiter_fulfill() {
+    let limiter = managed::CapacityLimiter::new(1);
+    let pool = Pool::builder(Manager {})
+        .max_size(2)
+        .capacity_limiter(limiter.clone())
+        .runtime(deadpool::Runtime::Tokio1)
+        .create_timeout(Some(Duration::from_millis(10)))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+
+    let permit = pool.acquire_permit().await.unwrap();
+    assert!(matches!(
+        permit.fulfill(1).await,
+        Err(PoolError::Timeout(TimeoutType::Create))
+    ));
+    assert_eq!(pool.status().size, 1);
+
+    drop(obj);
+    assert_eq!(pool.evict_idle(1), 1);
+    let permit = pool.acquire_permit().await.unwrap();
+    assert_eq!(*permit.fulfill(1).await.unwrap(), 1);
+    assert_eq!(limiter.available(), 0);
+}
+
+#[tokio::test]
+async fn capacity_report() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+
+    let obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { drop(pool.get().await.unwrap()) }
+    });
+    tokio::task::yield_now().await;
+    drop((obj1, obj2));
+    waiter.await.unwrap();
+
+    let report = pool.capacity_report(Duration::from_secs(60));
+    assert!(report.window <= Duration::from_secs(1));
+    assert_eq!(report.max_size, 2);
+    assert_eq!(report.peak_checked_out, 2);
+    assert_eq!(report.peak_waiters, 1);
+    assert_eq!(report.timeouts, 0);
+    assert_eq!(report.recommended_max_size(), 3);
+}
+
+#[tokio::test]
+async fn resources() {
+    struct Statement(Arc<AtomicUsize>);
+
+    impl Drop for Statement {
+        fn drop(&mut self) {
+            let _ = self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+
+    let mut obj = pool.get().await.unwrap();
+    assert!(Object::resources(&obj).is_empty());
+    let _ = Object::resources_mut(&mut obj).insert(Statement(dropped.clone()));
+    drop(obj);
+
+    let obj = pool.get().await.unwrap();
+    assert!(Object::resources(&obj).get::<Statement>().is_some());
+    drop(obj);
+    assert_eq!(dropped.load(Ordering::Relaxed), 0);
+
+    assert_eq!(pool.evict_idle(1), 1);
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn on_discard() {
+    let discarded = Arc::new(Mutex::new(Vec::new()));
+    let pool = Pool::builder(Manager {})
+        .max_size(3)
+        .seed(vec![1, 2, 3])
+        .on_discard({
+            let discarded = discarded.clone();
+            move |obj, reason| discarded.lock().unwrap().push((obj, reason))
+        })
+        .build()
+        .unwrap();
+
+    assert_eq!(pool.evict_idle(1), 1);
+    let obj = pool.get().await.unwrap();
+    let _ = pool.close();
+    drop(obj);
+
+    let discarded = discarded.lock().unwrap();
+    assert_eq!(discarded.len(), 3);
+    assert_eq!(discarded[0].1, DiscardReason::Evicted);
+    assert!(discarded[1..]
+        .iter()
+        .all(|(_, reason)| *reason == DiscardReason::Closed));
+}
+
+#[tokio::test]
+async fn exhausted_retry_after() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .on_exhausted(OnExhausted::Error)
+        .build()
+        .unwrap();
+
+    let obj = pool.get().await.unwrap();
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::Exhausted { retry_after: None })
+    ));
+    drop(obj);
+
+    time::sleep(Duration::from_millis(10)).await;
+    drop(pool.get().await.unwrap());
+    let obj = pool.get().await.unwrap();
+    match pool.get().await {
+        Err(PoolError::Exhausted {
+            retry_after: Some(retry_after),
+        }) => assert!(retry_after >= Duration::from_millis(10)),
+        _ => panic!("expected exhausted error with retry-after hint"),
+    }
+    drop(obj);
+}
+
+#[tokio::test]
+async fn on_exhausted_error() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .on_exhausted(OnExhausted::Error)
+        .build()
+        .unwrap();
+
+    let obj = pool.get().await.unwrap();
+    assert!(matches!(pool.get().await, Err(PoolError::Exhausted { .. })));
+    assert_eq!(pool.status().available, 0);
+
+    drop(obj);
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+async fn on_exhausted_create_burst() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .on_exhausted(OnExhausted::CreateBurst)
+        .build()
+        .unwrap();
+
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 0);
+
+    drop(obj1);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 0);
+
+    drop(obj0);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+}
+
+#[tokio::test]
+async fn acquire_permit() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let permit = pool.acquire_permit().await.unwrap();
+    assert!(matches!(pool.try_get().await, Err(PoolError::Timeout(_))));
+    assert_eq!(pool.status().size, 0);
+    drop(permit);
+
+    let permit = pool.acquire_permit().await.unwrap();
+    let obj = permit.get().await.unwrap();
+    assert_eq!(pool.status().size, 1);
+    drop(obj);
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn permit_fulfill_and_create() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(2).build().unwrap();
+
+    let permit = pool.acquire_permit().await.unwrap();
+    let obj = permit.fulfill(42).await.unwrap();
+    assert_eq!(*obj, 42);
+    assert_eq!(pool.status().size, 1);
+    drop(obj);
+    assert_eq!(pool.status().available, 1);
+
+    // Creating ignores the idle object.
+    let permit = pool.acquire_permit().await.unwrap();
+    let obj = permit.create().await.unwrap();
+    assert_eq!(*obj, 0);
+    assert_eq!(pool.status().size, 2);
+    drop(obj);
+
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 42);
+}
+
+#[tokio::test]
+async fn self_test() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let report = pool.self_test().await;
+    assert!(report.is_ok());
+    assert!(matches!(report.recycle, Some(Ok(_))));
+    assert!(report.interact.is_none());
+    assert_eq!(pool.status().size, 0);
+
+    pool.close();
+    let report = pool.self_test().await;
+    assert!(matches!(report.create, Err(PoolError::Closed)));
+    assert!(!report.is_ok());
+}
+
+#[tokio::test]
+async fn self_test_discard() {
+    let discarded = Arc::new(Mutex::new(Vec::new()));
+    let pool = Pool::builder(Manager {})
+        .max_size(1)
+        .create_limit(Some(CreateLimit { max: 1, per: None }))
+        .on_discard({
+            let discarded = discarded.clone();
+            move |obj, reason| discarded.lock().unwrap().push((obj, reason))
+        })
+        .build()
+        .unwrap();
+
+    assert!(pool.self_test().await.is_ok());
+    assert!(pool.self_test().await.is_ok());
+    assert_eq!(
+        *discarded.lock().unwrap(),
+        [(0, DiscardReason::SelfTest), (0, DiscardReason::SelfTest)]
+    );
+    // The self tests didn't use up the create limit.
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+async fn memory_usage() {
+    let mgr = CountingManager {
+        memory_usage: 1024,
+        ..CountingManager::default()
+    };
+    let pool = managed::Pool::<CountingManager>::builder(mgr)
+        .max_size(2)
+        .build()
+        .unwrap();
+    assert_eq!(pool.memory_usage().objects, 0);
+    assert!(pool.memory_usage().bookkeeping > 0);
+
+    let buf0 = pool.get().await.unwrap();
+    let buf1 = pool.get().await.unwrap();
+    assert_eq!(pool.memory_usage().objects, 2048);
+    drop(buf0);
+    assert_eq!(pool.memory_usage().objects, 2048);
+    let _ = Object::take(buf1);
+    assert_eq!(pool.memory_usage().objects, 1024);
+}
+
+#[test]
+fn shutdown_blocking() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(2).build().unwrap();
+    let (obj0, obj1) = rt.block_on(async {
+        let obj0 = pool.get().await.unwrap();
+        let obj1 = pool.get().await.unwrap();
+        (obj0, obj1)
+    });
+    drop(obj0);
+
+    let report = pool.shutdown_blocking(Duration::from_millis(10));
+    assert_eq!(report.closed, 1);
+    assert_eq!(report.checked_out, 1);
+    assert_eq!(report.dropped, 0);
+    assert_eq!(report.remaining, 1);
+
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        drop(obj1);
+    });
+    let report = pool.shutdown_blocking(Duration::from_secs(5));
+    assert_eq!(report.dropped, 1);
+    assert_eq!(report.remaining, 0);
+    assert_eq!(pool.status().size, 0);
+    handle.join().unwrap();
+}
+
+#[test]
+fn shutdown_blocking_without_deadline() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    let obj = rt.block_on(pool.get()).unwrap();
+
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        drop(obj);
+    });
+    let report = pool.shutdown_blocking(Duration::MAX);
+    assert_eq!(report.checked_out, 1);
+    assert_eq!(report.dropped, 1);
+    assert_eq!(report.remaining, 0);
+    handle.join().unwrap();
+}
+
+#[tokio::test]
+async fn object_metrics() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::metrics(&obj).recycle_count, 0);
+    assert!(Object::metrics(&obj).recycled.is_none());
+    drop(obj);
+
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::metrics(&obj).recycle_count, 1);
+    assert!(Object::metrics(&obj).recycled.is_some());
+}
+
+#[tokio::test]
+async fn on_build() {
+    let max_size = Arc::new(AtomicUsize::new(0));
+    let mgr = Manager {};
+    let _pool = Pool::builder(mgr)
+        .max_size(7)
+        .on_build({
+            let max_size = max_size.clone();
+            move |config| max_size.store(config.max_size, Ordering::Relaxed)
+        })
+        .build()
+        .unwrap();
+    assert_eq!(max_size.load(Ordering::Relaxed), 7);
+
+    let result = Pool::builder(Manager {})
+        .max_size(2)
+        .capacity_limiter(managed::CapacityLimiter::new(1))
+        .seed(vec![1, 2])
+        .on_build({
+            let max_size = max_size.clone();
+            move |config| max_size.store(config.max_size, Ordering::Relaxed)
+        })
+        .build();
+    assert!(matches!(result, Err(managed::BuildError::Config(_))));
+    assert_eq!(max_size.load(Ordering::Relaxed), 7);
+}
+
+#[tokio::test]
+async fn local_object() {
+    let mgr = Manager {};
+    let pool = managed::Pool::<Manager, LocalObject<Manager>>::builder(mgr)
+        .max_size(1)
+        .build()
+        .unwrap();
+    let mut obj = pool.get().await.unwrap();
+    *obj += 1;
+    drop(LocalObject::into_inner(obj));
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn capacity_semaphore() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(3).build().unwrap();
+    let capacity = pool.capacity();
+    assert_eq!(capacity.max_permits(), 3);
+    assert_eq!(capacity.available_permits(), 3);
+
+    let permits = capacity.try_acquire_many(2).unwrap();
+    assert_eq!(capacity.available_permits(), 1);
+    assert!(matches!(
+        capacity.try_acquire_many(2),
+        Err(PoolError::Exhausted { .. })
+    ));
+
+    let _obj = pool.get().await.unwrap();
+    assert!(matches!(
+        capacity.try_acquire(),
+        Err(PoolError::Exhausted { .. })
+    ));
+
+    drop(permits);
+    assert_eq!(capacity.available_permits(), 2);
+    assert_eq!(pool.status().available, 0);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn waiters() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    assert!(pool.waiters().is_empty());
+
+    let obj = pool.get().await.unwrap();
+    let waiter = {
+        let pool = pool.clone();
+        tokio::spawn(async move { drop(pool.get_labeled("report").await.unwrap()) })
+    };
+    tokio::task::yield_now().await;
+    let waiters = pool.waiters();
+    assert_eq!(waiters.len(), 1);
+    assert_eq!(waiters[0].label.as_deref(), Some("report"));
+
+    drop(obj);
+    waiter.await.unwrap();
+    assert!(pool.waiters().is_empty());
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test(flavor = "current_thread")]
+async fn get_unbounded() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .wait_timeout(Some(Duration::from_millis(10)))
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+
+    let obj = pool.get().await.unwrap();
+    let waiter = {
+        let pool = pool.clone();
+        tokio::spawn(async move { drop(pool.get_unbounded().await.unwrap()) })
+    };
+    tokio::task::yield_now().await;
+    assert!(matches!(pool.get().await, Err(PoolError::Timeout(_))));
+    let stats = pool.stats();
+    assert_eq!(stats.waiting, 1);
+    assert_eq!(stats.unbounded_waiting, 1);
+    assert!(pool.waiters()[0].unbounded);
+
+    drop(obj);
+    waiter.await.unwrap();
+    assert_eq!(pool.stats().unbounded_waiting, 0);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn max_waiting() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .max_waiting(Some(1))
+        .build()
+        .unwrap();
+
+    let obj = pool.get().await.unwrap();
+    let waiter = {
+        let pool = pool.clone();
+        tokio::spawn(async move { drop(pool.get().await.unwrap()) })
+    };
+    tokio::task::yield_now().await;
+    assert!(matches!(pool.get().await, Err(PoolError::QueueFull)));
+    assert_eq!(pool.status().rejections.queue_full, 1);
+    assert_eq!(pool.waiters().len(), 1);
+
+    drop(obj);
+    waiter.await.unwrap();
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+async fn max_waiting_spare_capacity() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(4)
+        .max_waiting(Some(0))
+        .build()
+        .unwrap();
+
+    // Callers getting a slot right away don't count as waiting.
+    let objs = vec![
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+    ];
+    assert_eq!(pool.status().rejections.queue_full, 0);
+    assert!(matches!(pool.get().await, Err(PoolError::QueueFull)));
+    drop(objs);
+}
+
+#[tokio::test]
+async fn get_with_progress() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let obj = pool.get().await.unwrap();
+    let first = tokio::spawn({
+        let pool = pool.clone();
+        async move { drop(pool.get().await.unwrap()) }
+    });
+    tokio::task::yield_now().await;
+
+    let progress = Arc::new(managed::WaitProgress::new());
+    assert!(progress.status().is_none());
+    let second = tokio::spawn({
+        let pool = pool.clone();
+        let progress = progress.clone();
+        async move { drop(pool.get_with_progress(&progress).await.unwrap()) }
+    });
+    tokio::task::yield_now().await;
+    assert_eq!(progress.status().unwrap().position, 1);
+
+    drop(obj);
+    first.await.unwrap();
+    second.await.unwrap();
+    assert!(progress.status().is_none());
+}
+
+#[tokio::test]
+async fn get_tagged() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let obj = pool.get_tagged("report-generator").await.unwrap();
+    assert_eq!(Object::tag(&obj), Some("report-generator"));
+    drop(obj);
+
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::tag(&obj), None);
+}
+
+#[tokio::test]
+async fn get_cancellable() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let obj = pool.get_cancellable(std::future::pending()).await.unwrap();
+    let cancel = time::sleep(Duration::from_millis(10));
+    assert!(matches!(
+        pool.get_cancellable(cancel).await,
+        Err(PoolError::Cancelled)
+    ));
+    assert_eq!(pool.status().available, 0);
+
+    drop(obj);
+    assert!(matches!(
+        pool.get_cancellable(async {}).await,
+        Err(PoolError::Cancelled)
+    ));
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn close_report() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(3).build().unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let _obj2 = pool.get().await.unwrap();
+    drop(obj0);
+    drop(obj1);
+
+    let report = pool.close();
+    assert_eq!(report.closed, 2);
+    assert_eq!(report.checked_out, 1);
+    assert_eq!(report.waiters_rejected, 0);
+}
+
+#[tokio::test]
+async fn reset_on_return() {
+    struct BufferManager {}
+
+    #[async_trait]
+    impl managed::Manager for BufferManager {
+        type Type = Vec<u8>;
+        type Error = Infallible;
+
+        async fn create(&self) -> Result<Vec<u8>, Infallible> {
+            Ok(Vec::with_capacity(64))
+        }
+
+        async fn recycle(&self, buf: &mut Vec<u8>) -> RecycleResult<Infallible> {
+            assert!(buf.is_empty());
+            Ok(())
+        }
+    }
+
+    let pool = managed::Pool::<BufferManager>::builder(BufferManager {})
+        .max_size(1)
+        .reset_on_return()
+        .build()
+        .unwrap();
+    pool.get().await.unwrap().extend_from_slice(b"hello");
+    let buf = pool.get().await.unwrap();
+    assert!(buf.is_empty());
+    assert!(buf.capacity() >= 64);
+}
+
+#[tokio::test]
+async fn value_manager() {
+    let pool = managed::Pool::<ValueManager<String>>::builder(ValueManager::new(String::new))
+        .max_size(1)
+        .build()
+        .unwrap();
+    pool.get().await.unwrap().push_str("scratch");
+    assert!(pool.get().await.unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn fair_handle() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let obj = pool.get().await.unwrap();
+    let spawn = |pool: Pool, name: &'static str| {
+        let order = order.clone();
+        tokio::spawn(async move {
+            let _obj = pool.get().await.unwrap();
+            order.lock().unwrap().push(name);
+        })
+    };
+    let busy = pool.fair_handle();
+    let tasks = vec![
+        spawn(busy.clone(), "busy"),
+        spawn(busy.clone(), "busy"),
+        spawn(busy, "busy"),
+    ];
+    tokio::task::yield_now().await;
+    let other = spawn(pool.clone(), "other");
+    tokio::task::yield_now().await;
+
+    drop(obj);
+    other.await.unwrap();
+    for task in tasks {
+        task.await.unwrap();
+    }
+    assert_eq!(*order.lock().unwrap(), ["busy", "other", "busy", "busy"]);
+}
+
+#[tokio::test]
+async fn max_error_rate() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .max_error_rate(Some(ErrorRateLimit {
+            max_rate: 0.5,
+            min_uses: 2,
+        }))
+        .build()
+        .unwrap();
+
+    let mut obj = pool.get().await.unwrap();
+    Object::report_error(&mut obj);
+    drop(obj);
+    assert_eq!(pool.status().size, 1);
+
+    let mut obj = pool.get().await.unwrap();
+    assert_eq!(Object::metrics(&obj).error_count, 1);
+    Object::report_error(&mut obj);
+    drop(obj);
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn max_error_rate_window() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .max_error_rate(Some(ErrorRateLimit {
+            max_rate: 0.5,
+            min_uses: 1,
+        }))
+        .build()
+        .unwrap();
+
+    for _ in 0..100 {
+        drop(pool.get().await.unwrap());
+    }
+    // A burst of errors counts although the object had a clean history.
+    let mut errors = 0;
+    while pool.status().size == 1 {
+        let mut obj = pool.get().await.unwrap();
+        Object::report_error(&mut obj);
+        errors += 1;
+    }
+    assert_eq!(errors, 33);
+}
+
+#[tokio::test]
+async fn evict_idle() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(2)
+        .eviction_policy(eviction::Oldest)
+        .build()
+        .unwrap();
+
+    let mut obj0 = pool.get().await.unwrap();
+    time::sleep(Duration::from_millis(1)).await;
+    let mut obj1 = pool.get().await.unwrap();
+    *obj0 = 10;
+    *obj1 = 20;
+    drop(obj1);
+    drop(obj0);
+
+    assert_eq!(pool.evict_idle(1), 1);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+    assert_eq!(*pool.get().await.unwrap(), 20);
+
+    assert_eq!(pool.evict_idle(5), 1);
+    assert_eq!(pool.status().size, 0);
+}
+
+/// Manager creating objects holding the milliseconds their creation took.
+struct SlowManager {
+    delays: Mutex<Vec<usize>>,
+}
+
+#[async_trait]
+impl managed::Manager for SlowManager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        let delay = self.delays.lock().unwrap().remove(0);
+        time::sleep(Duration::from_millis(delay as u64)).await;
+        Ok(delay)
+    }
+
+    async fn recycle(&self, _conn: &mut usize) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn evict_cheapest_to_recreate() {
+    let mgr = SlowManager {
+        delays: Mutex::new(vec![30, 0, 15]),
+    };
+    let pool = managed::Pool::<SlowManager>::builder(mgr)
+        .max_size(3)
+        .eviction_policy(eviction::CheapestToRecreate)
+        .build()
+        .unwrap();
+
+    let objs = vec![
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+    ];
+    drop(objs);
+
+    assert_eq!(pool.evict_idle(1), 1);
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let mut remaining = [*obj0, *obj1];
+    remaining.sort_unstable();
+    assert_eq!(remaining, [15, 30]);
+}
+
+#[tokio::test]
+async fn create_duration() {
+    let mgr = SlowManager {
+        delays: Mutex::new(vec![20, 20]),
+    };
+    let pool = managed::Pool::<SlowManager>::builder(mgr)
+        .max_size(2)
+        .max_concurrent_creates(Some(1))
+        .build()
+        .unwrap();
+
+    // The second creation waits for the first one, which isn't measured.
+    let (obj0, obj1) = tokio::join!(pool.get(), pool.get());
+    for obj in [obj0.unwrap(), obj1.unwrap()] {
+        let duration = Object::metrics(&obj).create_duration;
+        assert!(duration >= Duration::from_millis(20));
+        assert!(duration < Duration::from_millis(40), "{:?}", duration);
+    }
+}
+
+#[tokio::test]
+async fn stats() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    drop(pool.get().await.unwrap());
+    let obj = pool.get().await.unwrap();
+    let waiter = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(pool.stats().waiting, 1);
+    drop(obj);
+    waiter.await.unwrap().unwrap();
+
+    let stats = pool.stats();
+    assert_eq!(stats.created, 1);
+    assert_eq!(stats.recycled, 2);
+    assert_eq!(stats.recycle_failures, 0);
+    assert_eq!(stats.rejections.total(), 0);
+    assert_eq!(stats.waiting, 0);
+    assert_eq!(stats.acquired, 1);
+    assert!(stats.max_wait >= Duration::from_millis(10));
+
+    let stats = pool.stats();
+    assert_eq!(stats.acquired, 0);
+    assert_eq!(stats.max_wait, Duration::ZERO);
+}
+
+#[tokio::test]
+async fn resize() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let obj0 = pool.get().await.unwrap();
+    assert!(pool.try_get().await.is_err());
+    pool.resize(3);
+    let obj1 = pool.try_get().await.unwrap();
+    let obj2 = pool.try_get().await.unwrap();
+    assert_eq!(pool.status().max_size, 3);
+
+    pool.resize(1);
+    let status = pool.status();
+    assert_eq!(status.max_size, 1);
+    assert_eq!(status.size, 3);
+    drop(obj0);
+    drop(obj1);
+    assert_eq!(pool.status().size, 1);
+    assert!(pool.try_get().await.is_err());
+
+    drop(obj2);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+    drop(pool.try_get().await.unwrap());
+
+    pool.resize(2);
+    let _obj0 = pool.try_get().await.unwrap();
+    let _obj1 = pool.try_get().await.unwrap();
+    assert!(pool.try_get().await.is_err());
+}
+
+#[tokio::test]
+async fn resized() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(3).build().unwrap();
+    pool.resized().await;
+
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    pool.resize(1);
+    let progress = pool.resize_progress();
+    assert_eq!(progress.current_size, 3);
+    assert_eq!(progress.target_size, 1);
+    assert!(!progress.is_done());
+
+    let resized = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.resized().await })
+    };
+    drop(obj0);
+    assert!(time::timeout(Duration::from_millis(10), pool.resized())
+        .await
+        .is_err());
+    drop(obj1);
+    time::timeout(Duration::from_millis(100), resized)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(pool.resize_progress().is_done());
+    drop(obj2);
+}
+
+#[tokio::test]
+async fn recycle_experiment() {
+    struct FlakyManager {}
+
+    #[async_trait]
+    impl managed::Manager for FlakyManager {
+        type Type = bool;
+        type Error = ();
+
+        async fn create(&self) -> Result<bool, ()> {
+            Ok(true)
+        }
+
+        async fn recycle(&self, healthy: &mut bool) -> RecycleResult<()> {
+            if *healthy {
+                Ok(())
+            } else {
+                Err(managed::RecycleError::Backend(()))
+            }
+        }
+    }
+
+    let pool = managed::Pool::<FlakyManager>::builder(FlakyManager {})
+        .max_size(1)
+        .build()
+        .unwrap();
+    assert!(pool.recycle_experiment().is_none());
+
+    let pool = managed::Pool::<FlakyManager>::builder(FlakyManager {})
+        .max_size(1)
+        .recycle_experiment(Some(RecycleExperiment {
+            sample_rate: 0.5,
+            freshness: Duration::from_secs(3600),
+        }))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    for _ in 0..4 {
+        drop(pool.get().await.unwrap());
+    }
+    let report = pool.recycle_experiment().unwrap();
+    assert_eq!(report.sampled, 2);
+    assert_eq!(report.fresh, 2);
+    assert_eq!(report.disagreements, 0);
+
+    // Every other checkout is sampled.
+    for _ in 0..2 {
+        let mut obj = pool.get().await.unwrap();
+        *obj = false;
+    }
+    drop(pool.get().await.unwrap());
+    let report = pool.recycle_experiment().unwrap();
+    assert_eq!(report.sampled, 3);
+    assert_eq!(report.fresh, 3);
+    assert_eq!(report.disagreements, 1);
+    assert!((report.disagreement_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+}
+
+#[tokio::test]
+async fn max_lifetime() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .max_lifetime(Some(Duration::from_millis(20)))
+        .build()
+        .unwrap();
+
+    *pool.get().await.unwrap() = 1;
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    time::sleep(Duration::from_millis(40)).await;
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn reap() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(2)
+        .idle_timeout(Some(Duration::from_millis(20)))
+        .build()
+        .unwrap();
+
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    drop(obj0);
+    time::sleep(Duration::from_millis(40)).await;
+    drop(obj1);
+
+    assert_eq!(pool.reap(), 1);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn spawn_reaper() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .idle_timeout(Some(Duration::from_millis(10)))
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    pool.spawn_reaper(Duration::from_millis(10)).unwrap();
+
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 1);
+    time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn warm_up() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(4).build().unwrap();
+
+    pool.warm_up(2).await.unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+
+    let _obj = pool.get().await.unwrap();
+    pool.warm_up(10).await.unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 4);
+    assert_eq!(status.available, 3);
+    assert_eq!(status.rejections.wait_timeouts, 0);
+
+    // Only the get recycled an object, warming up just creates them.
+    let stats = pool.stats();
+    assert_eq!(stats.created, 4);
+    assert_eq!(stats.recycled, 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn build_warm() {
+    let pool = Pool::builder(Manager {})
+        .max_size(4)
+        .min_idle(2)
+        .build_warm()
+        .await
+        .unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+
+    let mgr = NoopManager::with_latency(
+        deadpool::Runtime::Tokio1,
+        Duration::from_millis(50),
+        Duration::ZERO,
+    );
+    let result = managed::Pool::<NoopManager>::builder(mgr)
+        .max_size(1)
+        .min_idle(1)
+        .create_timeout(Some(Duration::from_millis(1)))
+        .runtime(deadpool::Runtime::Tokio1)
+        .build_warm()
+        .await;
+    assert!(matches!(
+        result,
+        Err(managed::BuildError::WarmUp(PoolError::Timeout(
+            TimeoutType::Create
+        )))
+    ));
+}
+
+#[test]
+fn min_idle_exceeding_max_size() {
+    let result = Pool::builder(Manager {}).max_size(2).min_idle(3).build();
+    assert!(matches!(result, Err(managed::BuildError::Config(_))));
+}
+
+#[test]
+fn max_concurrent_creates_zero() {
+    let result = Pool::builder(Manager {})
+        .max_concurrent_creates(Some(0))
+        .build();
+    assert!(matches!(result, Err(managed::BuildError::Config(_))));
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn min_idle() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(4)
+        .min_idle(2)
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    pool.spawn_reaper(Duration::from_millis(10)).unwrap();
+    time::sleep(Duration::from_millis(5)).await;
+    assert_eq!(pool.status().available, 2);
+
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let _ = Object::take(obj0);
+    let _ = Object::take(obj1);
+    assert_eq!(pool.status().size, 0);
+    time::sleep(Duration::from_millis(50)).await;
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn min_idle_pre_replace() {
+    let mgr = NoopManager::with_latency(
+        deadpool::Runtime::Tokio1,
+        Duration::from_millis(20),
+        Duration::ZERO,
+    );
+    let pool = managed::Pool::<NoopManager>::builder(mgr)
+        .max_size(2)
+        .min_idle(1)
+        .max_lifetime(Some(Duration::from_millis(100)))
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    pool.spawn_reaper(Duration::from_millis(20)).unwrap();
+    time::sleep(Duration::from_millis(30)).await;
+
+    // The replacement of an expiring object exists before it's reaped.
+    for _ in 0..50 {
+        assert!(pool.status().available >= 1);
+        time::sleep(Duration::from_millis(5)).await;
+    }
+    let obj = pool.get().await.unwrap();
+    assert!(*obj > 0);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn on_task_panic() {
+    let panics = Arc::new(Mutex::new(Vec::new()));
+    let pool = Pool::builder(Manager {})
+        .max_size(1)
+        .max_lifetime(Some(Duration::from_millis(10)))
+        .on_discard(|_, _| panic!("discard failed"))
+        .on_task_panic({
+            let panics = panics.clone();
+            move |task, msg| panics.lock().unwrap().push((task, msg))
+        })
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    time::sleep(Duration::from_millis(20)).await;
+
+    pool.spawn_reaper(Duration::from_millis(10)).unwrap();
+    time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(
+        *panics.lock().unwrap(),
+        [(BackgroundTask::Reaper, "discard failed".to_string())]
+    );
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn backfill_interval() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(2)
+        .min_idle(2)
+        .on_exhausted(OnExhausted::CreateBurst)
+        .backfill_interval(Some(Duration::from_millis(200)))
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    pool.spawn_reaper(Duration::from_millis(10)).unwrap();
+    time::sleep(Duration::from_millis(5)).await;
+    assert_eq!(pool.status().available, 2);
+
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let burst = pool.get().await.unwrap();
+    drop(burst);
+    let _ = Object::take(obj0);
+    let _ = Object::take(obj1);
+    assert_eq!(pool.status().size, 0);
+    time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.status().size, 1);
+    time::sleep(Duration::from_millis(250)).await;
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn standby() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(4)
+        .min_idle(2)
+        .standby(true)
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    pool.spawn_reaper(Duration::from_millis(10)).unwrap();
+    time::sleep(Duration::from_millis(5)).await;
+    assert!(pool.is_standby());
+    assert_eq!(pool.status().available, 2);
+    assert!(matches!(pool.get().await, Err(PoolError::Standby)));
+    assert!(matches!(pool.try_get().await, Err(PoolError::Standby)));
+
+    assert!(pool.activate());
+    assert!(!pool.activate());
+    assert!(!pool.is_standby());
+    let _obj = pool.get().await.unwrap();
+    assert_eq!(pool.status().size, 2);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn standby_validation() {
+    let failing = Arc::new(AtomicBool::new(false));
+    let discarded = Arc::new(Mutex::new(Vec::new()));
+    let mgr = CountingManager {
+        failing: failing.clone(),
+        ..CountingManager::default()
+    };
+    let pool = managed::Pool::<CountingManager>::builder(mgr)
+        .max_size(4)
+        .min_idle(2)
+        .standby(true)
+        .on_discard({
+            let discarded = discarded.clone();
+            move |obj, reason| discarded.lock().unwrap().push((obj, reason))
+        })
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    pool.spawn_reaper(Duration::from_millis(10)).unwrap();
+    time::sleep(Duration::from_millis(5)).await;
+    assert_eq!(pool.manager().next.load(Ordering::Relaxed), 2);
+
+    // Broken idle objects are replaced without anybody trying to get them.
+    failing.store(true, Ordering::Relaxed);
+    for _ in 0..20 {
+        if discarded.lock().unwrap().len() >= 2 {
+            break;
+        }
+        time::sleep(Duration::from_millis(5)).await;
+    }
+    failing.store(false, Ordering::Relaxed);
+    assert_eq!(
+        discarded.lock().unwrap()[..2],
+        [
+            (0, DiscardReason::RecycleFailed),
+            (1, DiscardReason::RecycleFailed)
+        ]
+    );
+    time::sleep(Duration::from_millis(20)).await;
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+
+    assert!(pool.activate());
+    assert!(*pool.get().await.unwrap() >= 2);
+}
+
+#[tokio::test]
+async fn get_shared() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(2).build().unwrap();
+
+    let r0 = pool.get_shared().await.unwrap();
+    let r1 = pool.get_shared().await.unwrap();
+    let r2 = r1.clone();
+    assert!(SharedObject::ptr_eq(&r0, &r2));
+    assert_eq!(SharedObject::readers(&r0), 3);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 0);
+
+    let obj = pool.get().await.unwrap();
+    assert_eq!(pool.status().size, 2);
+    drop((r0, r1, r2, obj));
+    assert_eq!(pool.status().available, 2);
+
+    let _r3 = pool.get_shared().await.unwrap();
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn backend_limit() {
+    let limited = || CountingManager {
+        size_limit: Some(2),
+        ..CountingManager::default()
+    };
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let pool = managed::Pool::<CountingManager>::builder(limited())
+        .max_size(4)
+        .clamp_to_backend_limit(true)
+        .on_backend_limit({
+            let reports = reports.clone();
+            move |report| reports.lock().unwrap().push(report)
+        })
+        .build()
+        .unwrap();
+    assert_eq!(pool.backend_limit(), None);
+
+    let _obj = pool.get().await.unwrap();
+    let _obj = pool.get().await.unwrap();
+    assert_eq!(pool.backend_limit(), Some(2));
+    assert_eq!(pool.status().max_size, 2);
+    assert_eq!(
+        *reports.lock().unwrap(),
+        vec![BackendLimitExceeded {
+            max_size: 4,
+            limit: 2,
+            clamped: true,
+        }]
+    );
+
+    let pool = managed::Pool::<CountingManager>::builder(limited())
+        .max_size(1)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.backend_limit(), Some(2));
+    assert_eq!(pool.status().max_size, 1);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn noop_manager() {
+    let mgr = NoopManager::with_latency(
+        deadpool::Runtime::Tokio1,
+        Duration::from_millis(20),
+        Duration::ZERO,
+    );
+    let pool = managed::Pool::<NoopManager>::builder(mgr)
+        .max_size(2)
+        .create_timeout(Some(Duration::from_millis(10)))
+        .runtime(deadpool::Runtime::Tokio1)
+        .build()
+        .unwrap();
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::Timeout(TimeoutType::Create))
+    ));
+
+    let mut timeouts = pool.timeouts();
+    timeouts.create = None;
+    let obj = pool.timeout_get(&timeouts).await.unwrap();
+    assert_eq!(*obj, 0);
+    drop(obj);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+    assert_eq!(pool.manager().created(), 1);
+    assert_eq!(pool.manager().recycled(), 1);
+}
+
+#[tokio::test]
+async fn max_concurrent_creates() {
+    #[derive(Default)]
+    struct SlowManager {
+        creating: AtomicUsize,
+        max_creating: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl managed::Manager for SlowManager {
+        type Type = ();
+        type Error = Infallible;
+
+        async fn create(&self) -> Result<(), Infallible> {
+            let creating = self.creating.fetch_add(1, Ordering::Relaxed) + 1;
+            let _ = self.max_creating.fetch_max(creating, Ordering::Relaxed);
+            time::sleep(Duration::from_millis(10)).await;
+            let _ = self.creating.fetch_sub(1, Ordering::Relaxed);
+            Ok(())
+        }
+
+        async fn recycle(&self, _obj: &mut ()) -> RecycleResult<Infallible> {
+            Ok(())
+        }
+    }
+
+    let pool = managed::Pool::<SlowManager>::builder(SlowManager::default())
+        .max_size(8)
+        .max_concurrent_creates(Some(2))
+        .build()
+        .unwrap();
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let pool = pool.clone();
+            tokio::spawn(async move { Object::take(pool.get().await.unwrap()) })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+    assert_eq!(pool.manager().max_creating.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn status_diff() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let status = pool.status();
+    let stats = pool.stats();
+
+    let obj = pool.get().await.unwrap();
+    assert!(pool.try_get().await.is_err());
+    let diff = pool.status().diff(&status);
+    assert_eq!(diff.max_size, 0);
+    assert_eq!(diff.size, 1);
+    assert_eq!(diff.available, 0);
+    assert_eq!(diff.rejections.wait_timeouts, 1);
+    assert_eq!(diff.rejections.total(), 1);
+    drop(obj);
+    assert_eq!(pool.status().diff(&status).available, 1);
+
+    drop(pool.get().await.unwrap());
+    let later = pool.stats();
+    let diff = later.diff(&stats);
+    assert_eq!(diff.created, 1);
+    assert_eq!(diff.recycled, 1);
+    assert_eq!(diff.rejections.wait_timeouts, 1);
+    assert_eq!(diff.acquired, later.acquired);
+    assert_eq!(later.diff(&later).created, 0);
+}
+
+#[tokio::test]
+async fn rejections() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+
+    let _obj = pool.get().await.unwrap();
+    assert!(pool.try_get().await.is_err());
+    let cancel = time::sleep(Duration::from_millis(10));
+    assert!(pool.get_cancellable(cancel).await.is_err());
+    assert!(time::timeout(Duration::from_millis(10), pool.get())
+        .await
+        .is_err());
+
+    let rejections = pool.status().rejections;
+    assert_eq!(rejections.wait_timeouts, 1);
+    assert_eq!(rejections.create_timeouts, 0);
+    assert_eq!(rejections.exhausted, 0);
+    assert_eq!(rejections.cancelled, 2);
+
+    let pool = Pool::builder(Manager {})
+        .max_size(0)
+        .on_exhausted(OnExhausted::Error)
+        .build()
+        .unwrap();
+    assert!(pool.get().await.is_err());
+    assert_eq!(pool.status().rejections.exhausted, 1);
+}
+
+#[tokio::test]
+async fn thread_affinity() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(2)
+        .thread_affinity(true)
+        .build()
+        .unwrap();
+
+    let mut obj0 = pool.get().await.unwrap();
+    let mut obj1 = pool.get().await.unwrap();
+    *obj0 = 1;
+    *obj1 = 2;
+    drop(obj0);
+    let pool2 = pool.clone();
+    let value = std::thread::spawn(move || {
+        drop(obj1);
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let value = *rt.block_on(pool2.get()).unwrap();
+        value
+    })
+    .join()
+    .unwrap();
+    assert_eq!(value, 2);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn queue_mode() {
+    for (mode, expected) in [(QueueMode::Fifo, [1, 2]), (QueueMode::Lifo, [2, 1])] {
+        let pool = Pool::builder(Manager {})
+            .max_size(2)
+            .queue_mode(mode)
+            .build()
+            .unwrap();
+        let mut obj0 = pool.get().await.unwrap();
+        let mut obj1 = pool.get().await.unwrap();
+        *obj0 = 1;
+        *obj1 = 2;
+        drop(obj0);
+        drop(obj1);
+
+        let obj0 = pool.get().await.unwrap();
+        let obj1 = pool.get().await.unwrap();
+        assert_eq!([*obj0, *obj1], expected);
+    }
+}
+
+#[tokio::test]
+async fn aggregate_status() {
+    let pool0 = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    let pool1 = Pool::builder(Manager {}).max_size(3).build().unwrap();
+    let _obj0 = pool0.get().await.unwrap();
+    drop(pool1.get().await.unwrap());
+
+    let status = deadpool::aggregate_status(vec![pool0.status(), pool1.status()]);
+    assert_eq!(status.total.max_size, 5);
+    assert_eq!(status.total.size, 2);
+    assert_eq!(status.total.available, 1);
+    assert_eq!(status.pools.len(), 2);
+    assert_eq!(status.pools[1].available, 1);
+}
+
+#[tokio::test]
+async fn attach_detach_pool() {
+    let lifecycle = Arc::new(Lifecycle::default());
+    let mgr = CountingManager {
+        lifecycle: Some(lifecycle.clone()),
+        ..CountingManager::default()
+    };
+    let pool = managed::Pool::<CountingManager>::builder(mgr)
+        .build()
+        .unwrap();
+    let handle = lifecycle.handle.lock().unwrap().clone().unwrap();
+    assert!(!handle.is_closed());
+    assert_eq!(lifecycle.detached.load(Ordering::Relaxed), 0);
+
+    let _ = pool.close();
+    assert!(handle.is_closed());
+    assert_eq!(lifecycle.detached.load(Ordering::Relaxed), 1);
+    drop(pool);
+    assert_eq!(lifecycle.detached.load(Ordering::Relaxed), 1);
+
+    let lifecycle = Arc::new(Lifecycle::default());
+    let mgr = CountingManager {
+        lifecycle: Some(lifecycle.clone()),
+        ..CountingManager::default()
+    };
+    drop(
+        managed::Pool::<CountingManager>::builder(mgr)
+            .build()
+            .unwrap(),
+    );
+    assert_eq!(lifecycle.detached.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn companion() {
+    let aborted = Arc::new(AtomicUsize::new(0));
+    let pool = managed::Pool::<CountingManager>::builder(CountingManager {
+        companions: Some(aborted.clone()),
+        ..CountingManager::default()
+    })
+    .max_size(2)
+    .build()
+    .unwrap();
+
+    let obj = pool.get().await.unwrap();
+    drop(obj);
+    assert_eq!(aborted.load(Ordering::Relaxed), 0);
+    assert_eq!(pool.evict_idle(1), 1);
+    assert_eq!(aborted.load(Ordering::Relaxed), 1);
+
+    let obj = pool.get().await.unwrap();
+    let _ = Object::take(obj);
+    assert_eq!(aborted.load(Ordering::Relaxed), 1);
+
+    let obj = pool.get().await.unwrap();
+    drop(obj);
+    let _ = pool.close();
+    drop(pool);
+    assert_eq!(aborted.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn retain() {
+    let discarded = Arc::new(Mutex::new(Vec::new()));
+    let on_discard = discarded.clone();
+    let pool = managed::Pool::<CountingManager>::builder(CountingManager::default())
+        .max_size(3)
+        .on_discard(move |obj, reason| on_discard.lock().unwrap().push((obj, reason)))
+        .build()
+        .unwrap();
+
+    let objs = vec![
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+    ];
+    let mut objs = objs.into_iter();
+    drop(objs.next());
+    drop(objs.next());
+    let checked_out = objs.next().unwrap();
+    assert_eq!(*checked_out, 2);
+
+    assert_eq!(
+        pool.retain(|obj, metrics| *obj == 1 && metrics.recycle_count == 0),
+        1
+    );
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 1);
+    assert_eq!(*discarded.lock().unwrap(), [(0, DiscardReason::Filtered)]);
+
+    drop(checked_out);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 1);
+    assert_eq!(discarded.lock().unwrap().len(), 2);
+    assert_eq!(discarded.lock().unwrap()[1], (2, DiscardReason::Filtered));
+
+    // Objects which have already been checked aren't checked again.
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 1);
+    drop(obj);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.retain(|_, _| true), 0);
+}
+
+#[tokio::test]
+async fn retain_panic() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    drop(pool.get().await.unwrap());
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        pool.retain(|_, _| panic!("filter failed"))
+    }));
+    assert!(result.is_err());
+
+    // The internal locks ignore poisoning, so the pool keeps working.
+    let _ = pool.status();
+    assert!(pool.get().await.is_ok());
+    assert_eq!(pool.retain(|_, _| true), 0);
+}
+
+#[tokio::test]
+async fn retain_forgets_checked_filters() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    let old = pool.get().await.unwrap();
+
+    let token = Arc::new(());
+    let filter_token = token.clone();
+    assert_eq!(
+        pool.retain(move |_, _| Arc::strong_count(&filter_token) > 0),
+        0
+    );
+    assert_eq!(Arc::strong_count(&token), 2);
+
+    // Objects created afterwards don't need the filter, so it is dropped as
+    // soon as the older object has been checked.
+    let new = pool.get().await.unwrap();
+    drop(old);
+    assert_eq!(Arc::strong_count(&token), 1);
+    drop(new);
+    assert_eq!(pool.status().size, 2);
+}
+
+#[tokio::test]
+async fn quarantine() {
+    let build = |partitioned: &Arc<AtomicBool>, max_retries| {
+        let discarded = Arc::new(Mutex::new(Vec::new()));
+        let on_discard = discarded.clone();
+        let mgr = CountingManager {
+            failing: partitioned.clone(),
+            ..CountingManager::default()
+        };
+        let pool = managed::Pool::<CountingManager>::builder(mgr)
+            .max_size(2)
+            .quarantine(Some(Quarantine {
+                delay: Duration::from_millis(20),
+                max_retries,
+            }))
+            .on_discard(move |obj, reason| on_discard.lock().unwrap().push((obj, reason)))
+            .build()
+            .unwrap();
+        (pool, discarded)
+    };
+
+    let partitioned = Arc::new(AtomicBool::new(false));
+    let (pool, discarded) = build(&partitioned, 1);
+    drop(pool.get().await.unwrap());
+    partitioned.store(true, Ordering::Relaxed);
+    let first = pool.get().await.unwrap();
+    assert_eq!(*first, 1);
+    partitioned.store(false, Ordering::Relaxed);
+
+    // The quarantined object keeps counting towards the maximum size.
+    assert_eq!(pool.status().size, 2);
+    assert!(matches!(pool.try_get().await, Err(PoolError::Timeout(_))));
+
+    // Once its delay is over, it's retried before any idle object.
+    drop(first);
+    time::sleep(Duration::from_millis(30)).await;
+    let second = pool.get().await.unwrap();
+    assert_eq!(*second, 0);
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.status().available, 1);
+    assert!(discarded.lock().unwrap().is_empty());
+    drop(second);
+
+    // Quarantined objects nobody retries are eventually reaped.
+    partitioned.store(true, Ordering::Relaxed);
+    let first = pool.get().await.unwrap();
+    assert_eq!(pool.status().size, 2);
+    time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.reap(), 1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(
+        discarded.lock().unwrap().last().map(|(_, reason)| *reason),
+        Some(DiscardReason::Expired)
+    );
+    drop(first);
+    partitioned.store(false, Ordering::Relaxed);
+
+    let (pool, discarded) = build(&partitioned, 0);
+    drop(pool.get().await.unwrap());
+    partitioned.store(true, Ordering::Relaxed);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+    assert_eq!(
+        *discarded.lock().unwrap(),
+        [(0, DiscardReason::RecycleFailed)]
+    );
+}
+
+#[tokio::test]
+async fn quarantine_overflow() {
+    let partitioned = Arc::new(AtomicBool::new(false));
+    let mgr = CountingManager {
+        failing: partitioned.clone(),
+        ..CountingManager::default()
+    };
+    let pool = managed::Pool::<CountingManager>::builder(mgr)
+        .max_size(2)
+        .quarantine(Some(Quarantine {
+            delay: Duration::MAX,
+            max_retries: usize::MAX,
+        }))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    partitioned.store(true, Ordering::Relaxed);
+    let first = pool.get().await.unwrap();
+    assert_eq!(*first, 1);
+    drop(first);
+
+    // A delay which can't be represented is never over and never expires.
+    assert_eq!(pool.reap(), 0);
+    assert_eq!(pool.status().size, 2);
+    partitioned.store(false, Ordering::Relaxed);
+    let second = pool.get().await.unwrap();
+    assert_eq!(*second, 1);
+
+    // Closing the pool drops the quarantined object as well.
+    drop(second);
+    assert_eq!(pool.close().closed, 2);
+}
+
+#[tokio::test]
+async fn create_limit() {
+    let pool = Pool::builder(Manager {})
+        .max_size(2)
+        .create_limit(Some(CreateLimit { max: 1, per: None }))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::CreateLimitReached { retry_after: None })
+    ));
+    assert_eq!(pool.status().size, 1);
+    drop(obj);
+    assert!(pool.get().await.is_ok());
+
+    let pool = Pool::builder(Manager {})
+        .max_size(2)
+        .create_limit(Some(CreateLimit {
+            max: 1,
+            per: Some(Duration::from_millis(50)),
+        }))
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+    match pool.get().await {
+        Err(PoolError::CreateLimitReached {
+            retry_after: Some(retry_after),
+        }) => assert!(retry_after <= Duration::from_millis(50)),
+        _ => panic!("expected the create limit to be reached"),
+    }
+    time::sleep(Duration::from_millis(60)).await;
+    assert!(pool.get().await.is_ok());
+
+    // A window too long to be represented never resets.
+    let pool = Pool::builder(Manager {})
+        .max_size(2)
+        .create_limit(Some(CreateLimit {
+            max: 1,
+            per: Some(Duration::MAX),
+        }))
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::CreateLimitReached { retry_after: None })
+    ));
+}
@@ -0,0 +1,42 @@
+#![cfg(all(feature = "managed", feature = "async-backtrace"))]
+
+use std::{convert::Infallible, time::Duration};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{self, Pool, RecycleResult};
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(0)
+    }
+
+    async fn recycle(&self, _obj: &mut usize) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn stuck_waiter() {
+    let pool = Pool::<Manager>::builder(Manager {})
+        .max_size(1)
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { drop(pool.get().await) })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    let dump = async_backtrace::taskdump_tree(false);
+    assert!(dump.contains("wait_for_permit"), "{}", dump);
+    waiting.abort();
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "blocking")]
+
+use std::{convert::Infallible, thread, time::Duration};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{self, blocking::BlockingPool, Pool, PoolError, RecycleResult};
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        Ok(0)
+    }
+
+    async fn recycle(&self, _obj: &mut usize) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[test]
+fn blocking_pool() {
+    let pool = BlockingPool::new(Pool::builder(Manager {}).max_size(2)).unwrap();
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                for _ in 0..10 {
+                    pool.with(|n| *n += 1).unwrap();
+                }
+            })
+        })
+        .collect();
+    for t in threads {
+        t.join().unwrap();
+    }
+    let status = pool.status();
+    assert_eq!(status.available, status.size as isize);
+
+    let obj0 = pool.get().unwrap();
+    let obj1 = pool.get().unwrap();
+    assert_eq!(*obj0 + *obj1, 40);
+    drop(obj0);
+    drop(obj1);
+
+    let _ = pool.close();
+    assert!(matches!(pool.get(), Err(PoolError::Closed)));
+}
+
+#[test]
+fn background_tasks() {
+    let pool = BlockingPool::new(Pool::builder(Manager {}).max_size(4).min_idle(2)).unwrap();
+    assert_eq!(pool.status().size, 2);
+    pool.block_on(async { pool.pool().spawn_reaper(Duration::from_millis(10)) })
+        .unwrap();
+
+    // The reaper keeps running without any thread blocking on the pool.
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(pool.status().size, 2);
+
+    let n = pool.block_on(async {
+        let obj = pool.pool().get().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        *obj
+    });
+    assert_eq!(n, 0);
+}
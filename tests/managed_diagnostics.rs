@@ -0,0 +1,44 @@
+#![cfg(feature = "diagnostics")]
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use deadpool::managed::{self, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(42)
+    }
+
+    async fn recycle(&self, _conn: &mut usize) -> RecycleResult<()> {
+        Ok(())
+    }
+
+    fn describe(&self, obj: &usize) -> serde_json::Value {
+        json!({ "value": obj })
+    }
+}
+
+#[tokio::test]
+async fn describe_idle() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    assert!(pool.describe_idle().is_empty());
+
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    drop(obj0);
+    let idle = pool.describe_idle();
+    assert_eq!(idle.len(), 1);
+    assert_eq!(idle[0].details, json!({ "value": 42 }));
+    assert_eq!(idle[0].metrics.recycle_count, 0);
+    drop(obj1);
+    assert_eq!(pool.describe_idle().len(), 2);
+}
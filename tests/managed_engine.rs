@@ -0,0 +1,78 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{convert::Infallible, time::Duration};
+
+use async_trait::async_trait;
+
+use deadpool::{
+    managed::{self, engine::PoolCore, Object, PoolError, RecycleResult, Timeouts},
+    Runtime,
+};
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(0)
+    }
+
+    async fn recycle(&self, _obj: &mut usize) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+/// Minimal blocking frontend built on top of a [`PoolCore`].
+struct BlockingPool<C> {
+    core: C,
+    rt: tokio::runtime::Runtime,
+}
+
+impl<C: PoolCore> BlockingPool<C> {
+    fn get(
+        &self,
+    ) -> Result<Object<C::Manager>, PoolError<<C::Manager as managed::Manager>::Error>> {
+        self.rt
+            .block_on(async { self.core.acquire(&self.core.timeouts()).await?.get().await })
+    }
+}
+
+#[test]
+fn blocking_frontend() {
+    let pool = managed::Pool::<Manager>::builder(Manager {})
+        .max_size(1)
+        .runtime(Runtime::Tokio1)
+        .wait_timeout(Some(Duration::from_millis(10)))
+        .build()
+        .unwrap();
+    let frontend = BlockingPool {
+        core: pool.clone(),
+        rt: tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap(),
+    };
+
+    let obj = frontend.get().unwrap();
+    assert_eq!(frontend.core.status().size, 1);
+    assert!(matches!(frontend.get(), Err(PoolError::Timeout(_))));
+    assert_eq!(frontend.core.status().rejections.wait_timeouts, 1);
+    drop(obj);
+    assert_eq!(frontend.core.status().available, 1);
+
+    let mut timeouts = Timeouts::new();
+    timeouts.wait = Some(Duration::ZERO);
+    let permit = frontend
+        .rt
+        .block_on(frontend.core.acquire(&timeouts))
+        .unwrap();
+    assert!(frontend.rt.block_on(pool.try_get()).is_err());
+    drop(permit);
+
+    frontend.core.close();
+    assert!(frontend.core.is_closed());
+    assert!(matches!(frontend.get(), Err(PoolError::Closed)));
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "managed")]
+
+use async_trait::async_trait;
+
+use deadpool::managed::{
+    self,
+    fallback::{FallbackObject, FallbackPool},
+    PoolError, RecycleResult, StubExhausted, StubManager, Timeouts,
+};
+
+struct Manager {
+    value: usize,
+    fail: bool,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        if self.fail {
+            Err(())
+        } else {
+            Ok(self.value)
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut usize) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+type Pool = managed::Pool<Manager>;
+
+fn pool(value: usize, fail: bool) -> Pool {
+    Pool::builder(Manager { value, fail })
+        .max_size(1)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn primary() {
+    let fallback = FallbackPool::new(pool(1, false), pool(2, false));
+    let obj = fallback.get().await.unwrap();
+    assert!(obj.is_primary());
+    assert_eq!(*obj, 1);
+}
+
+#[tokio::test]
+async fn secondary_on_error() {
+    let fallback = FallbackPool::new(pool(1, true), pool(2, false));
+    let obj = fallback.get().await.unwrap();
+    assert!(matches!(obj, FallbackObject::Secondary(_)));
+    assert_eq!(*obj, 2);
+}
+
+#[tokio::test]
+async fn secondary_on_timeout() {
+    let primary = pool(1, false);
+    let _obj = primary.get().await.unwrap();
+    let fallback = FallbackPool::new(
+        primary.with_timeouts(Timeouts::wait_millis(0)),
+        pool(2, false),
+    );
+    assert!(fallback.get().await.unwrap().is_secondary());
+}
+
+#[tokio::test]
+async fn both_failing() {
+    let fallback = FallbackPool::new(pool(1, true), pool(2, true));
+    let err = fallback.get().await.err().unwrap();
+    assert!(matches!(err.primary, PoolError::Backend(())));
+    assert!(matches!(err.secondary, PoolError::Backend(())));
+}
+
+#[tokio::test]
+async fn stub_secondary() {
+    let stub = managed::Pool::<StubManager<usize>>::builder(StubManager::new([7]))
+        .max_size(2)
+        .build()
+        .unwrap();
+    let fallback = FallbackPool::new(pool(1, true), stub);
+
+    let obj = fallback.get().await.unwrap();
+    assert!(obj.is_secondary());
+    assert_eq!(*obj, 7);
+    let err = fallback.get().await.err().unwrap();
+    assert!(matches!(err.secondary, PoolError::Backend(StubExhausted)));
+    drop(obj);
+
+    assert_eq!(*fallback.get().await.unwrap(), 7);
+}
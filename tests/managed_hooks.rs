@@ -1,10 +1,12 @@
 #![cfg(feature = "managed")]
 
+use std::time::Duration;
+
 use async_trait::async_trait;
 
 use deadpool::managed::{
-    hooks::{HookError, PostCreate, PostRecycle},
-    Manager, Pool, RecycleResult,
+    hooks::{HookError, PostCreate, PostIdle, PostRecycle, PreRecycle},
+    Manager, Metrics, Object, Pool, RecycleResult,
 };
 
 struct Computer {}
@@ -41,6 +43,39 @@ impl PostRecycle<Computer> for IncrementHook {
     }
 }
 
+struct MaxRecycleCountHook(usize);
+
+#[async_trait]
+impl PreRecycle<Computer> for MaxRecycleCountHook {
+    async fn pre_recycle(&self, _: &mut usize, metrics: &Metrics) -> Result<(), HookError<()>> {
+        if metrics.recycle_count >= self.0 {
+            return Err(HookError::Message("too old".to_string()));
+        }
+        Ok(())
+    }
+}
+
+struct MaxValueHook(usize);
+
+#[async_trait]
+impl PostRecycle<Computer> for MaxValueHook {
+    async fn post_recycle(&self, obj: &mut usize) -> Result<(), HookError<()>> {
+        *obj += 1;
+        if *obj > self.0 {
+            return Err(HookError::Message("too large".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PostIdle<Computer> for IncrementHook {
+    async fn post_idle(&self, obj: &mut usize, _: Duration) -> Result<(), HookError<()>> {
+        *obj += 1;
+        Ok(())
+    }
+}
+
 #[tokio::test]
 async fn post_create() {
     let manager = Computer {};
@@ -65,3 +100,91 @@ async fn post_recycle() {
     assert!(*pool.get().await.unwrap() == 44);
     assert!(*pool.get().await.unwrap() == 45);
 }
+
+#[tokio::test]
+async fn post_recycle_veto() {
+    let manager = Computer {};
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .post_recycle(MaxValueHook(43))
+        .build()
+        .unwrap();
+    assert!(*pool.get().await.unwrap() == 42);
+    assert!(*pool.get().await.unwrap() == 43);
+    // The hook rejects the recycled object, so it gets replaced.
+    assert!(*pool.get().await.unwrap() == 42);
+    assert_eq!(pool.status().size, 1);
+
+    let obj = pool.get().await.unwrap();
+    assert!(*obj == 43);
+    let obj = Object::recycle_now(obj).await.unwrap();
+    assert!(*obj == 42);
+    let obj = Object::recycle_now(obj).await.unwrap();
+    assert!(*obj == 43);
+    drop(obj);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn pre_recycle() {
+    let manager = Computer {};
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .pre_recycle(MaxRecycleCountHook(1))
+        .post_recycle(IncrementHook {})
+        .build()
+        .unwrap();
+    assert!(*pool.get().await.unwrap() == 42);
+    assert!(*pool.get().await.unwrap() == 43);
+    // The object was recycled once already and gets replaced.
+    assert!(*pool.get().await.unwrap() == 42);
+    assert!(*pool.get().await.unwrap() == 43);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn post_idle() {
+    let manager = Computer {};
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .post_idle(Duration::from_millis(50), IncrementHook {})
+        .build()
+        .unwrap();
+    assert!(*pool.get().await.unwrap() == 42);
+    assert!(*pool.get().await.unwrap() == 42);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(*pool.get().await.unwrap() == 43);
+    assert!(*pool.get().await.unwrap() == 43);
+}
+
+#[tokio::test]
+async fn park_unpark() {
+    let manager = Computer {};
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .on_park(|obj| *obj = 0)
+        .on_unpark(|obj| {
+            assert_eq!(*obj, 0);
+            *obj = 7;
+            Ok(())
+        })
+        .build()
+        .unwrap();
+    assert!(*pool.get().await.unwrap() == 42);
+    assert!(*pool.get().await.unwrap() == 7);
+}
+
+#[tokio::test]
+async fn unpark_failure() {
+    let manager = Computer {};
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .on_park(|obj| *obj = 0)
+        .on_unpark(|_| Err(HookError::Message("sealed".to_string())))
+        .build()
+        .unwrap();
+    assert!(*pool.get().await.unwrap() == 42);
+    assert!(*pool.get().await.unwrap() == 42);
+    assert_eq!(pool.status().size, 1);
+}
@@ -0,0 +1,57 @@
+#![cfg(all(unix, feature = "host_limit", feature = "rt_tokio_1"))]
+
+use std::{convert::Infallible, time::Duration};
+
+use async_trait::async_trait;
+
+use deadpool::{
+    managed::{self, HostLimiter, PoolError, RecycleResult, TimeoutType},
+    Runtime,
+};
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(0)
+    }
+
+    async fn recycle(&self, _conn: &mut usize) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+type Pool = managed::Pool<Manager>;
+
+#[tokio::test]
+async fn shared_slots() {
+    let dir = std::env::temp_dir().join(format!("deadpool-host-limit-{}", std::process::id()));
+    // Every pool uses its own limiter just like separate processes would.
+    let pools: Vec<Pool> = (0..2)
+        .map(|_| {
+            Pool::builder(Manager {})
+                .max_size(2)
+                .host_limiter(HostLimiter::new(&dir, 3).unwrap())
+                .create_timeout(Some(Duration::from_millis(50)))
+                .runtime(Runtime::Tokio1)
+                .build()
+                .unwrap()
+        })
+        .collect();
+
+    let a0 = pools[0].get().await.unwrap();
+    let _a1 = pools[0].get().await.unwrap();
+    let _b0 = pools[1].get().await.unwrap();
+    assert!(matches!(
+        pools[1].get().await,
+        Err(PoolError::Timeout(TimeoutType::Create))
+    ));
+
+    let _ = managed::Object::take(a0);
+    let _b1 = pools[1].get().await.unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "managed")]
+
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{
+    intercept::Interceptor, AcquireInfo, Manager, Pool, PoolError, RecycleResult,
+};
+
+struct Computer {}
+
+#[async_trait]
+impl Manager for Computer {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<Self::Type, Self::Error> {
+        Ok(42)
+    }
+
+    async fn recycle(&self, _: &mut Self::Type) -> RecycleResult<Self::Error> {
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct GateState {
+    closed: AtomicBool,
+    reject_after: AtomicBool,
+    before: AtomicUsize,
+    after: AtomicUsize,
+    failed: AtomicUsize,
+    created: AtomicUsize,
+}
+
+struct Gate(Arc<GateState>);
+
+#[async_trait]
+impl Interceptor<()> for Gate {
+    async fn before_get(&self) -> Result<(), PoolError<()>> {
+        let _ = self.0.before.fetch_add(1, Ordering::Relaxed);
+        if self.0.closed.load(Ordering::Relaxed) {
+            return Err(PoolError::Cancelled);
+        }
+        Ok(())
+    }
+
+    async fn after_get(
+        &self,
+        result: &Result<AcquireInfo, PoolError<()>>,
+    ) -> Result<(), PoolError<()>> {
+        let _ = self.0.after.fetch_add(1, Ordering::Relaxed);
+        if result.is_err() {
+            let _ = self.0.failed.fetch_add(1, Ordering::Relaxed);
+        }
+        if matches!(result, Ok(info) if info.created_new) {
+            let _ = self.0.created.fetch_add(1, Ordering::Relaxed);
+        }
+        if self.0.reject_after.load(Ordering::Relaxed) {
+            return Err(PoolError::Cancelled);
+        }
+        Ok(())
+    }
+}
+
+/// Interceptor relying on the default implementations.
+struct Noop;
+
+impl Interceptor<()> for Noop {}
+
+#[tokio::test]
+async fn interceptors() {
+    let gate = Arc::new(GateState::default());
+    let pool = Pool::<Computer>::builder(Computer {})
+        .max_size(1)
+        .interceptor(Gate(gate.clone()))
+        .interceptor(Noop)
+        .build()
+        .unwrap();
+
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(gate.before.load(Ordering::Relaxed), 2);
+    assert_eq!(gate.created.load(Ordering::Relaxed), 1);
+
+    gate.reject_after.store(true, Ordering::Relaxed);
+    assert!(matches!(pool.get().await, Err(PoolError::Cancelled)));
+    assert_eq!(pool.status().available, 1);
+
+    gate.closed.store(true, Ordering::Relaxed);
+    assert!(matches!(pool.try_get().await, Err(PoolError::Cancelled)));
+    assert_eq!(gate.before.load(Ordering::Relaxed), 4);
+}
+
+#[tokio::test]
+async fn interceptors_nested() {
+    let outer = Arc::new(GateState::default());
+    let inner = Arc::new(GateState::default());
+    let pool = Pool::<Computer>::builder(Computer {})
+        .max_size(1)
+        .interceptor(Gate(outer.clone()))
+        .interceptor(Gate(inner.clone()))
+        .build()
+        .unwrap();
+
+    // The outer interceptor sees the rejection of the inner one.
+    inner.closed.store(true, Ordering::Relaxed);
+    assert!(matches!(pool.get().await, Err(PoolError::Cancelled)));
+    assert_eq!(inner.after.load(Ordering::Relaxed), 0);
+    assert_eq!(outer.after.load(Ordering::Relaxed), 1);
+    assert_eq!(outer.failed.load(Ordering::Relaxed), 1);
+
+    // An error of the inner `after_get` reaches the outer one as well.
+    inner.closed.store(false, Ordering::Relaxed);
+    inner.reject_after.store(true, Ordering::Relaxed);
+    assert!(matches!(pool.get().await, Err(PoolError::Cancelled)));
+    assert_eq!(inner.after.load(Ordering::Relaxed), 1);
+    assert_eq!(outer.after.load(Ordering::Relaxed), 2);
+    assert_eq!(outer.failed.load(Ordering::Relaxed), 2);
+    assert_eq!(pool.status().available, 1);
+
+    // Permits are intercepted as well.
+    inner.reject_after.store(false, Ordering::Relaxed);
+    drop(pool.acquire_permit().await.unwrap());
+    assert_eq!(outer.before.load(Ordering::Relaxed), 3);
+    assert_eq!(outer.after.load(Ordering::Relaxed), 3);
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{
+    self,
+    hooks::{HookError, PostCreate},
+    layer::{Operation, RetryLayer, TimingLayer},
+    RecycleResult,
+};
+
+struct Manager {
+    attempts: AtomicUsize,
+    failures: usize,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        let attempt = self.attempts.fetch_add(1, Ordering::Relaxed);
+        if attempt < self.failures {
+            Err(())
+        } else {
+            Ok(0)
+        }
+    }
+
+    async fn recycle(&self, _obj: &mut usize) -> RecycleResult<()> {
+        Ok(())
+    }
+
+    fn backoff_hint(&self, _error: &()) -> Option<Duration> {
+        Some(Duration::from_millis(20))
+    }
+}
+
+struct IncrementHook {}
+
+#[async_trait]
+impl PostCreate<Manager> for IncrementHook {
+    async fn post_create(&self, obj: &mut usize) -> Result<(), HookError<()>> {
+        *obj += 1;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn retry() {
+    let manager = Manager {
+        attempts: AtomicUsize::new(0),
+        failures: 2,
+    };
+    let pool = managed::Pool::builder(manager)
+        .wrap_manager(RetryLayer::new(2))
+        .build()
+        .unwrap();
+    assert!(pool.get().await.is_ok());
+    assert_eq!(pool.manager().inner().attempts.load(Ordering::Relaxed), 3);
+}
+
+#[tokio::test]
+async fn retry_exhausted() {
+    let manager = Manager {
+        attempts: AtomicUsize::new(0),
+        failures: 2,
+    };
+    let pool = managed::Pool::builder(manager)
+        .wrap_manager(RetryLayer::new(1))
+        .build()
+        .unwrap();
+    assert!(pool.get().await.is_err());
+    assert_eq!(pool.manager().inner().attempts.load(Ordering::Relaxed), 2);
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn retry_backoff_hint() {
+    let manager = Manager {
+        attempts: AtomicUsize::new(0),
+        failures: 2,
+    };
+    let pool = managed::Pool::builder(manager)
+        .wrap_manager(RetryLayer::new(2).with_runtime(deadpool::Runtime::Tokio1))
+        .build()
+        .unwrap();
+    let start = std::time::Instant::now();
+    assert!(pool.get().await.is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(40));
+}
+
+#[tokio::test]
+async fn timing() {
+    let creates = Arc::new(AtomicUsize::new(0));
+    let recycles = Arc::new(AtomicUsize::new(0));
+    let manager = Manager {
+        attempts: AtomicUsize::new(0),
+        failures: 0,
+    };
+    let pool = managed::Pool::builder(manager)
+        .max_size(1)
+        .wrap_manager(TimingLayer::new({
+            let creates = creates.clone();
+            let recycles = recycles.clone();
+            move |op, _| {
+                let counter = match op {
+                    Operation::Create => &creates,
+                    Operation::Recycle => &recycles,
+                };
+                let _ = counter.fetch_add(1, Ordering::Relaxed);
+            }
+        }))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(creates.load(Ordering::Relaxed), 1);
+    assert_eq!(recycles.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn hooks_are_kept() {
+    let manager = Manager {
+        attempts: AtomicUsize::new(0),
+        failures: 0,
+    };
+    let pool = managed::Pool::builder(manager)
+        .post_create(IncrementHook {})
+        .wrap_manager(RetryLayer::new(1))
+        .build()
+        .unwrap();
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{self, multiplex::MultiplexPool, RecycleResult};
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _conn: &mut usize) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn lease_limit() {
+    let pool = managed::Pool::builder(Manager::default())
+        .max_size(2)
+        .build()
+        .unwrap();
+    let pool = MultiplexPool::new(pool, 2);
+
+    let l0 = pool.lease().await.unwrap();
+    let l1 = pool.lease().await.unwrap();
+    assert_eq!(*l0, 0);
+    assert_eq!(*l1, 0);
+    assert_eq!(l1.leases(), 2);
+    assert_eq!(pool.leased(), 1);
+    assert_eq!(pool.pool().status().size, 1);
+
+    let l2 = pool.lease().await.unwrap();
+    assert_eq!(*l2, 1);
+    assert_eq!(pool.leased(), 2);
+    let l3 = pool.try_lease().unwrap();
+    assert_eq!(*l3, 1);
+    assert!(pool.try_lease().is_none());
+
+    drop(l0);
+    let l4 = pool.try_lease().unwrap();
+    assert_eq!(*l4, 0);
+
+    drop((l1, l4));
+    assert_eq!(pool.leased(), 1);
+    assert_eq!(pool.pool().status().available, 1);
+    drop((l2, l3));
+    assert_eq!(pool.leased(), 0);
+    assert_eq!(pool.pool().status().available, 2);
+}
+
+#[tokio::test]
+async fn lease_released() {
+    let pool = managed::Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    let pool = MultiplexPool::new(pool, 2);
+
+    let l0 = pool.lease().await.unwrap();
+    let _l1 = pool.lease().await.unwrap();
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { *pool.lease().await.unwrap() })
+    };
+    tokio::task::yield_now().await;
+
+    // The object stays checked out, so only the lease limit frees up.
+    drop(l0);
+    let n = tokio::time::timeout(Duration::from_secs(1), waiting)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(n, 0);
+}
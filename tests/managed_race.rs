@@ -0,0 +1,58 @@
+#![cfg(feature = "managed")]
+
+use std::time::Duration;
+
+use deadpool::managed::race::{Race, RaceError};
+
+async fn connect(
+    endpoint: (u64, Result<&'static str, &'static str>),
+) -> Result<&'static str, &'static str> {
+    let (delay, result) = endpoint;
+    tokio::time::sleep(Duration::from_millis(delay)).await;
+    result
+}
+
+#[tokio::test]
+async fn first_success() {
+    let endpoints = vec![(50, Ok("slow")), (0, Err("broken")), (10, Ok("fast"))];
+    let result = Race::new().connect(endpoints, connect).await;
+    assert_eq!(result.unwrap(), "fast");
+}
+
+#[tokio::test]
+async fn all_failed() {
+    let endpoints = vec![(10, Err("a")), (0, Err("b"))];
+    match Race::new().connect(endpoints, connect).await {
+        Err(RaceError::Failed(errors)) => assert_eq!(errors, vec!["b", "a"]),
+        _ => panic!("expected all attempts to fail"),
+    }
+}
+
+#[tokio::test]
+async fn no_endpoints() {
+    let endpoints: Vec<(u64, Result<&'static str, &'static str>)> = vec![];
+    let result = Race::new().connect(endpoints, connect).await;
+    assert!(matches!(result, Err(RaceError::NoEndpoints)));
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn staggered() {
+    use deadpool::Runtime;
+
+    let race = Race::staggered(Runtime::Tokio1, Duration::from_millis(20));
+
+    // The preferred endpoint wins if it answers within the delay.
+    let endpoints = vec![(10, Ok("preferred")), (0, Ok("fallback"))];
+    assert_eq!(race.connect(endpoints, connect).await.unwrap(), "preferred");
+
+    // Otherwise the next endpoint is tried in parallel.
+    let endpoints = vec![(200, Ok("preferred")), (0, Ok("fallback"))];
+    assert_eq!(race.connect(endpoints, connect).await.unwrap(), "fallback");
+
+    // A failure starts the next attempt without waiting for the delay.
+    let endpoints = vec![(0, Err("broken")), (200, Ok("slow")), (0, Ok("fallback"))];
+    let result =
+        tokio::time::timeout(Duration::from_millis(100), race.connect(endpoints, connect)).await;
+    assert_eq!(result.unwrap().unwrap(), "fallback");
+}
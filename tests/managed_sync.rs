@@ -0,0 +1,443 @@
+#![cfg(all(feature = "managed", feature = "sync", feature = "rt_tokio_1"))]
+
+use std::{
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::{
+    managed::{
+        self,
+        sync::{self, AbortReason, InteractError, SyncWrapper},
+        BackgroundTask, Pool, PoolError, RecycleResult, ValueManager,
+    },
+    Runtime,
+};
+
+struct UnitManager;
+
+#[async_trait]
+impl managed::Manager for UnitManager {
+    type Type = SyncWrapper<(), Infallible>;
+    type Error = InteractError<Infallible>;
+
+    async fn create(&self) -> Result<Self::Type, Self::Error> {
+        SyncWrapper::new(Runtime::Tokio1, || Ok(())).await
+    }
+
+    async fn recycle(&self, _obj: &mut Self::Type) -> RecycleResult<Self::Error> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn interact_bytes() {
+    let buffers = Pool::<ValueManager<Vec<u8>>>::builder(ValueManager::new(Vec::new))
+        .max_size(1)
+        .build()
+        .unwrap();
+    let wrapper = SyncWrapper::<_, Infallible>::new(Runtime::Tokio1, || Ok(String::from("blob")))
+        .await
+        .unwrap();
+
+    let buf = buffers.get().await.unwrap();
+    let buf = wrapper
+        .interact_bytes(buf, |s, out| {
+            out.extend_from_slice(s.as_bytes());
+            Ok(())
+        })
+        .await
+        .unwrap();
+    assert_eq!(&buf[..], b"blob");
+    let capacity = buf.capacity();
+    drop(buf);
+
+    let buf = buffers.get().await.unwrap();
+    assert!(buf.is_empty());
+    assert_eq!(buf.capacity(), capacity);
+
+    // A failed interaction drops the buffer returning it to its pool.
+    let result = wrapper
+        .interact_bytes(buf, |_, _| -> Result<(), _> { panic!("boom") })
+        .await;
+    assert!(matches!(result, Err(InteractError::Panic(_))));
+    assert_eq!(buffers.status().available, 1);
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[tokio::test]
+async fn create_panic() {
+    struct Manager {
+        panic: AtomicBool,
+    }
+
+    #[async_trait]
+    impl managed::Manager for Manager {
+        type Type = SyncWrapper<(), Infallible>;
+        type Error = InteractError<Infallible>;
+
+        async fn create(&self) -> Result<Self::Type, Self::Error> {
+            let panic = self.panic.load(Ordering::Relaxed);
+            SyncWrapper::new(Runtime::Tokio1, move || {
+                if panic {
+                    panic!("connect failed");
+                }
+                Ok(())
+            })
+            .await
+        }
+
+        async fn recycle(&self, _obj: &mut Self::Type) -> RecycleResult<Self::Error> {
+            Ok(())
+        }
+    }
+
+    let pool = Pool::<Manager>::builder(Manager {
+        panic: AtomicBool::new(true),
+    })
+    .max_size(1)
+    .build()
+    .unwrap();
+    // `InteractError` isn't `Sync` which must not keep the future from being
+    // spawned.
+    let get = pool.get();
+    assert_send(&get);
+    match get.await {
+        Err(PoolError::Backend(InteractError::Panic(p))) => {
+            assert_eq!(p.downcast_ref::<&str>(), Some(&"connect failed"))
+        }
+        _ => panic!("expected the panic to be reported"),
+    }
+    assert_eq!(pool.status().size, 0);
+
+    pool.manager().panic.store(false, Ordering::Relaxed);
+    assert!(pool.get().await.is_ok());
+}
+
+#[test]
+fn interact_shutdown() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let wrapper = rt
+        .block_on(SyncWrapper::<_, Infallible>::new(
+            Runtime::Tokio1,
+            || Ok(()),
+        ))
+        .unwrap();
+    let handle = rt.handle().clone();
+    rt.shutdown_background();
+
+    let other = tokio::runtime::Runtime::new().unwrap();
+    other.block_on(async move {
+        let result = {
+            let _guard = handle.enter();
+            wrapper.interact(|_| Ok(())).await
+        };
+        assert!(matches!(
+            result,
+            Err(InteractError::Aborted(AbortReason::Shutdown))
+        ));
+        assert!(wrapper.is_aborted());
+    });
+}
+
+#[tokio::test]
+async fn interact_timed() {
+    let wrapper = SyncWrapper::<_, ()>::new(Runtime::Tokio1, || Ok(0))
+        .await
+        .unwrap();
+
+    let (result, timing) = wrapper
+        .interact_timed(|n| {
+            std::thread::sleep(Duration::from_millis(20));
+            *n += 1;
+            Ok(*n)
+        })
+        .await;
+    assert_eq!(result.unwrap(), 1);
+    let timing = timing.unwrap();
+    assert!(timing.executed >= Duration::from_millis(20));
+    assert!(timing.total() >= timing.executed);
+
+    let (result, timing) = wrapper.interact_timed(|_| Err::<(), _>(())).await;
+    assert!(matches!(result, Err(InteractError::Backend(()))));
+    assert!(timing.is_some());
+
+    let (result, timing) = wrapper
+        .interact_timed(|_| -> Result<(), ()> { panic!("boom") })
+        .await;
+    assert!(matches!(result, Err(InteractError::Panic(_))));
+    assert!(timing.is_none());
+}
+
+#[tokio::test]
+async fn interact_poisoned() {
+    let wrapper = SyncWrapper::<_, Infallible>::new(Runtime::Tokio1, || Ok(0))
+        .await
+        .unwrap();
+
+    let result = wrapper
+        .interact(|_| -> Result<(), _> { panic!("boom") })
+        .await;
+    assert!(matches!(result, Err(InteractError::Panic(_))));
+    assert!(wrapper.is_mutex_poisoned());
+    assert!(!wrapper.is_aborted());
+
+    let result = wrapper.interact(|n| Ok(*n)).await;
+    assert!(matches!(
+        result,
+        Err(InteractError::Aborted(AbortReason::Poisoned))
+    ));
+    assert!(wrapper.is_aborted());
+}
+
+#[tokio::test]
+async fn interact_timeout() {
+    let wrapper = SyncWrapper::<_, Infallible>::new(Runtime::Tokio1, || Ok(0))
+        .await
+        .unwrap();
+
+    let result = wrapper
+        .interact_timeout(Duration::from_millis(100), |n| {
+            *n += 1;
+            Ok(*n)
+        })
+        .await;
+    assert_eq!(result.unwrap(), 1);
+    assert!(!wrapper.is_aborted());
+
+    let result = wrapper
+        .interact_timeout(Duration::from_millis(10), |n| {
+            std::thread::sleep(Duration::from_millis(50));
+            *n += 1;
+            Ok(*n)
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(InteractError::Aborted(AbortReason::Timeout))
+    ));
+    assert!(wrapper.is_aborted());
+
+    // The closure keeps running in the background.
+    assert_eq!(wrapper.interact(|n| Ok(*n)).await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn pending_drops() {
+    struct SlowDrop(mpsc::Receiver<()>);
+
+    impl Drop for SlowDrop {
+        fn drop(&mut self) {
+            let _ = self.0.recv();
+        }
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let wrapper = SyncWrapper::<_, Infallible>::new(Runtime::Tokio1, move || Ok(SlowDrop(rx)))
+        .await
+        .unwrap();
+    drop(wrapper);
+    assert!(sync::pending_drops() >= 1);
+
+    tx.send(()).unwrap();
+    for _ in 0..100 {
+        if sync::pending_drops() == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(sync::pending_drops(), 0);
+}
+
+#[tokio::test]
+async fn status_pending_drops() {
+    struct SlowDrop(mpsc::Receiver<()>);
+
+    impl Drop for SlowDrop {
+        fn drop(&mut self) {
+            let _ = self.0.recv();
+        }
+    }
+
+    struct Manager(Mutex<Option<mpsc::Receiver<()>>>);
+
+    #[async_trait]
+    impl managed::Manager for Manager {
+        type Type = SyncWrapper<SlowDrop, Infallible>;
+        type Error = InteractError<Infallible>;
+
+        async fn create(&self) -> Result<Self::Type, Self::Error> {
+            let rx = self.0.lock().unwrap().take().unwrap();
+            SyncWrapper::new(Runtime::Tokio1, move || Ok(SlowDrop(rx))).await
+        }
+
+        async fn recycle(&self, _obj: &mut Self::Type) -> RecycleResult<Self::Error> {
+            Ok(())
+        }
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let pool = Pool::<Manager>::builder(Manager(Mutex::new(Some(rx))))
+        .max_size(1)
+        .build()
+        .unwrap();
+    let other = Pool::<UnitManager>::builder(UnitManager)
+        .max_size(1)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(other.get().await.unwrap());
+
+    assert_eq!(pool.retain(|_, _| false), 1);
+    assert_eq!(pool.status().pending_blocking_drops, 1);
+    assert_eq!(other.status().pending_blocking_drops, 0);
+
+    tx.send(()).unwrap();
+    for _ in 0..100 {
+        if pool.status().pending_blocking_drops == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(pool.status().pending_blocking_drops, 0);
+}
+
+#[tokio::test]
+async fn drop_panic() {
+    struct PanicDrop;
+
+    impl Drop for PanicDrop {
+        fn drop(&mut self) {
+            panic!("close failed");
+        }
+    }
+
+    struct Manager;
+
+    #[async_trait]
+    impl managed::Manager for Manager {
+        type Type = SyncWrapper<PanicDrop, Infallible>;
+        type Error = InteractError<Infallible>;
+
+        async fn create(&self) -> Result<Self::Type, Self::Error> {
+            SyncWrapper::new(Runtime::Tokio1, || Ok(PanicDrop)).await
+        }
+
+        async fn recycle(&self, _obj: &mut Self::Type) -> RecycleResult<Self::Error> {
+            Ok(())
+        }
+    }
+
+    let panics = Arc::new(Mutex::new(Vec::new()));
+    let pool = Pool::<Manager>::builder(Manager)
+        .max_size(1)
+        .on_task_panic({
+            let panics = panics.clone();
+            move |task, msg| panics.lock().unwrap().push((task, msg))
+        })
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.retain(|_, _| false), 1);
+    for _ in 0..100 {
+        if !panics.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(
+        *panics.lock().unwrap(),
+        [(BackgroundTask::BlockingDrop, "close failed".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn split_async() {
+    use tokio::sync::watch;
+
+    let wrapper = SyncWrapper::<_, Infallible>::new(Runtime::Tokio1, || Ok(watch::channel(0)))
+        .await
+        .unwrap();
+    let half = wrapper.split_async(|(_, rx)| rx.clone()).await.unwrap();
+
+    let waiter = {
+        let half = half.clone();
+        tokio::spawn(async move {
+            let mut rx = half.lock().await;
+            rx.changed().await.unwrap();
+            let value = *rx.borrow();
+            value
+        })
+    };
+    wrapper
+        .interact(|(tx, _)| {
+            tx.send(42).unwrap();
+            Ok(())
+        })
+        .await
+        .unwrap();
+    assert_eq!(waiter.await.unwrap(), 42);
+}
+
+#[tokio::test]
+async fn object_interact_panic() {
+    let pool = Pool::<UnitManager>::builder(UnitManager)
+        .max_size(1)
+        .build()
+        .unwrap();
+
+    let obj = pool.get().await.unwrap();
+    let result = obj
+        .interact_bytes(Box::new(Vec::new()), |_, _| -> Result<(), _> {
+            panic!("boom")
+        })
+        .await;
+    assert!(matches!(result, Err(InteractError::Panic(_))));
+    drop(obj);
+    assert_eq!(pool.status().size, 0);
+
+    let obj = pool.get().await.unwrap();
+    let result = obj.split_async(|_| -> () { panic!("boom") }).await;
+    assert!(matches!(result, Err(InteractError::Panic(_))));
+    drop(obj);
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn self_test_interact() {
+    let pool = Pool::<UnitManager>::builder(UnitManager)
+        .max_size(1)
+        .build()
+        .unwrap();
+    let report = pool.self_test_interact().await;
+    assert!(report.is_ok());
+    assert!(matches!(report.interact, Some(Ok(_))));
+    assert_eq!(pool.status().size, 0);
+}
+
+#[test]
+fn shutdown_blocking() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let pool = Pool::<UnitManager>::builder(UnitManager)
+        .max_size(2)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let _obj0 = pool.get().await.unwrap();
+        let _obj1 = pool.get().await.unwrap();
+    });
+
+    // Dropping the idle objects outside of the runtime context must not
+    // require spawning blocking tasks.
+    let report = pool.shutdown_blocking(Duration::from_secs(5));
+    assert_eq!(report.closed, 2);
+    assert_eq!(report.remaining, 0);
+    assert_eq!(pool.status().size, 0);
+}
@@ -0,0 +1,68 @@
+#![cfg(all(feature = "managed", feature = "sync", feature = "rt_async-std_1"))]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::{
+    managed::sync::{AbortReason, InteractError, SyncWrapper},
+    Runtime,
+};
+
+#[async_std::test]
+async fn interact() {
+    let wrapper = SyncWrapper::<_, Infallible>::new(Runtime::AsyncStd1, || Ok(0))
+        .await
+        .unwrap();
+    let result = wrapper
+        .interact(|n| {
+            *n += 1;
+            Ok(*n)
+        })
+        .await;
+    assert_eq!(result.unwrap(), 1);
+}
+
+#[async_std::test]
+async fn interact_timeout() {
+    let wrapper = SyncWrapper::<_, Infallible>::new(Runtime::AsyncStd1, || Ok(0))
+        .await
+        .unwrap();
+    let result = wrapper
+        .interact_timeout(Duration::from_millis(10), |n| {
+            std::thread::sleep(Duration::from_millis(50));
+            *n += 1;
+            Ok(*n)
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(InteractError::Aborted(AbortReason::Timeout))
+    ));
+    assert!(wrapper.is_aborted());
+    assert_eq!(wrapper.interact(|n| Ok(*n)).await.unwrap(), 1);
+}
+
+#[async_std::test]
+async fn interact_panic() {
+    let wrapper = SyncWrapper::<_, Infallible>::new(Runtime::AsyncStd1, || Ok(0))
+        .await
+        .unwrap();
+    let result = wrapper
+        .interact(|_| -> Result<(), _> { panic!("boom") })
+        .await;
+    match result {
+        Err(InteractError::Panic(p)) => assert_eq!(p.downcast_ref::<&str>(), Some(&"boom")),
+        _ => panic!("expected InteractError::Panic"),
+    }
+}
+
+#[async_std::test]
+async fn new_panic() {
+    let result =
+        SyncWrapper::<(), Infallible>::new(Runtime::AsyncStd1, || panic!("connect failed")).await;
+    match result {
+        Err(InteractError::Panic(p)) => {
+            assert_eq!(p.downcast_ref::<&str>(), Some(&"connect failed"))
+        }
+        _ => panic!("expected InteractError::Panic"),
+    }
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "test-utils")]
+
+use async_trait::async_trait;
+
+use deadpool::managed::{self, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(0)
+    }
+
+    async fn recycle(&self, _obj: &mut usize) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn assertions() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    pool.assert_size(0);
+    pool.assert_idle(0);
+    pool.assert_no_waiters();
+
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    pool.assert_size(2);
+    pool.assert_idle(0);
+
+    drop(obj0);
+    pool.assert_idle(1);
+    drop(obj1);
+    pool.assert_idle(2);
+    pool.assert_size(2);
+}
+
+#[tokio::test]
+#[should_panic(expected = "expected 1 idle objects, found 0")]
+async fn assert_idle_fails() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    pool.assert_idle(1);
+}
@@ -3,12 +3,21 @@
     any(feature = "rt_tokio_1", feature = "rt_async-std_1")
 ))]
 
-use std::{convert::Infallible, future::Future, pin::Pin, task, time::Duration};
+use std::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    task,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
 
 use deadpool::{
-    managed::{self, Object, PoolConfig, PoolError, RecycleResult, Timeouts},
+    managed::{
+        self, CreateRetry, Object, PoolConfig, PoolError, RecycleResult, TimeoutCooldown,
+        TimeoutType, Timeouts, WaitTimeoutScaling,
+    },
     Runtime,
 };
 
@@ -51,6 +60,7 @@ async fn test_managed_timeout(runtime: Runtime) {
             wait: Some(Duration::from_millis(0)),
             recycle: Some(Duration::from_millis(0)),
         },
+        ..PoolConfig::default()
     };
     let pool = Pool::builder(mgr)
         .config(cfg)
@@ -72,3 +82,246 @@ async fn rt_tokio_1() {
 async fn rt_async_std_1() {
     test_managed_timeout(Runtime::AsyncStd1).await;
 }
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn wait_timeout_scaling() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(0)
+        .wait_timeout(Some(Duration::from_secs(60)))
+        .wait_timeout_scaling(WaitTimeoutScaling::Step {
+            depth: 1,
+            timeout: Duration::from_millis(10),
+        })
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+
+    let waiter = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    tokio::task::yield_now().await;
+    assert_eq!(pool.status().available, -1);
+
+    let result = tokio::time::timeout(Duration::from_secs(1), pool.get()).await;
+    assert!(matches!(
+        result,
+        Ok(Err(PoolError::Timeout(TimeoutType::Wait)))
+    ));
+    waiter.abort();
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn deadline() {
+    tokio::task_local! {
+        static DEADLINE: Instant;
+    }
+
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(0)
+        .wait_timeout(Some(Duration::from_secs(60)))
+        .deadline(|| DEADLINE.try_with(|deadline| *deadline).ok())
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_millis(10);
+    let result =
+        tokio::time::timeout(Duration::from_secs(1), DEADLINE.scope(deadline, pool.get())).await;
+    assert!(matches!(
+        result,
+        Ok(Err(PoolError::Timeout(TimeoutType::Wait)))
+    ));
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn timeout_cooldown() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(0)
+        .wait_timeout(Some(Duration::from_millis(0)))
+        .timeout_cooldown(Some(TimeoutCooldown {
+            window: Duration::from_secs(60),
+            max_rate: 0.5,
+            min_timeouts: 2,
+            cooldown: Duration::from_millis(50),
+        }))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+
+    for _ in 0..2 {
+        assert!(matches!(
+            pool.get().await,
+            Err(PoolError::Timeout(TimeoutType::Wait))
+        ));
+    }
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::CoolingDown { .. })
+    ));
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::Timeout(TimeoutType::Wait))
+    ));
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn timeout_cooldown_overflow() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(0)
+        .wait_timeout(Some(Duration::from_millis(0)))
+        .timeout_cooldown(Some(TimeoutCooldown {
+            window: Duration::from_secs(60),
+            max_rate: 0.5,
+            min_timeouts: 1,
+            cooldown: Duration::MAX,
+        }))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::Timeout(TimeoutType::Wait))
+    ));
+    // A cooldown which can't be represented never ends.
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::CoolingDown { retry_after }) if retry_after == Duration::MAX
+    ));
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn create_retry() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FlakyManager {
+        attempts: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl managed::Manager for FlakyManager {
+        type Type = ();
+        type Error = usize;
+
+        async fn create(&self) -> Result<(), usize> {
+            let attempt = self.attempts.fetch_add(1, Ordering::Relaxed);
+            if attempt < 2 {
+                Err(attempt)
+            } else {
+                Ok(())
+            }
+        }
+
+        async fn recycle(&self, _obj: &mut ()) -> RecycleResult<usize> {
+            Ok(())
+        }
+    }
+
+    let build = |max_attempts| {
+        managed::Pool::<FlakyManager>::builder(FlakyManager {
+            attempts: AtomicUsize::new(0),
+        })
+        .max_size(1)
+        .runtime(Runtime::Tokio1)
+        .create_retry(Some(CreateRetry {
+            max_attempts,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(15),
+            jitter: false,
+        }))
+        .build()
+        .unwrap()
+    };
+
+    let pool = build(2);
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(1))));
+
+    let pool = build(3);
+    let start = Instant::now();
+    assert!(pool.get().await.is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(25));
+    assert_eq!(pool.manager().attempts.load(Ordering::Relaxed), 3);
+
+    let result = managed::Pool::<FlakyManager>::builder(FlakyManager {
+        attempts: AtomicUsize::new(0),
+    })
+    .create_retry(Some(CreateRetry {
+        max_attempts: 3,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(10),
+        jitter: true,
+    }))
+    .build();
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "rt_tokio_1")]
+#[tokio::test]
+async fn create_retry_random() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use deadpool::managed::random::{RandomSource, SeededRandom};
+
+    struct FailingManager {}
+
+    #[async_trait]
+    impl managed::Manager for FailingManager {
+        type Type = ();
+        type Error = ();
+
+        async fn create(&self) -> Result<(), ()> {
+            Err(())
+        }
+
+        async fn recycle(&self, _obj: &mut ()) -> RecycleResult<()> {
+            Ok(())
+        }
+    }
+
+    let a = SeededRandom::new(42);
+    let b = SeededRandom::new(42);
+    let sequence: Vec<u64> = (0..4).map(|_| a.next_u64()).collect();
+    assert_eq!(sequence, (0..4).map(|_| b.next_u64()).collect::<Vec<_>>());
+    assert_ne!(sequence[0], SeededRandom::new(43).next_u64());
+
+    let draws = Arc::new(AtomicUsize::new(0));
+    let random = {
+        let draws = draws.clone();
+        move || {
+            let _ = draws.fetch_add(1, Ordering::Relaxed);
+            0
+        }
+    };
+    let pool = managed::Pool::<FailingManager>::builder(FailingManager {})
+        .max_size(1)
+        .runtime(Runtime::Tokio1)
+        .create_retry(Some(CreateRetry {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(10),
+            jitter: true,
+        }))
+        .random(random)
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    // A zero draw means no jitter at all.
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    assert_eq!(draws.load(Ordering::Relaxed), 2);
+}
@@ -1,11 +1,19 @@
 #![cfg(feature = "managed")]
 
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use tokio::time;
 
-use deadpool::managed::{self, RecycleError, RecycleResult};
+use deadpool::managed::{
+    self, DiscardReason, RecycleError, RecycleErrorKind, RecycleResult, TimeoutCooldown,
+};
 
 type Pool = managed::Pool<Manager>;
 
@@ -91,3 +99,137 @@ async fn recycle() {
     assert_eq!(status.available, 1);
     assert_eq!(status.size, 1);
 }
+
+struct TransientManager {
+    kind: RecycleErrorKind,
+    recycle_calls: AtomicUsize,
+}
+
+#[async_trait]
+impl managed::Manager for TransientManager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+    async fn recycle(&self, _conn: &mut ()) -> RecycleResult<()> {
+        // Only the first recycle attempt fails.
+        if self.recycle_calls.fetch_add(1, Ordering::Relaxed) == 0 {
+            Err(RecycleError::Backend(()))
+        } else {
+            Ok(())
+        }
+    }
+    fn classify_recycle_error(&self, _error: &RecycleError<()>) -> RecycleErrorKind {
+        self.kind
+    }
+}
+
+#[tokio::test]
+async fn recycle_transient() {
+    let manager = TransientManager {
+        kind: RecycleErrorKind::Transient,
+        recycle_calls: AtomicUsize::new(0),
+    };
+    let pool = managed::Pool::<TransientManager>::builder(manager)
+        .max_size(1)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.manager().recycle_calls.load(Ordering::Relaxed), 2);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+}
+
+#[tokio::test]
+async fn recycle_fatal() {
+    let manager = TransientManager {
+        kind: RecycleErrorKind::Fatal,
+        recycle_calls: AtomicUsize::new(0),
+    };
+    let pool = managed::Pool::<TransientManager>::builder(manager)
+        .max_size(1)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.manager().recycle_calls.load(Ordering::Relaxed), 1);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+}
+
+#[tokio::test]
+async fn recycle_fatal_cooldown() {
+    let manager = Manager {
+        create_fail: false,
+        recycle_fail: true,
+    };
+    let pool = Pool::builder(manager)
+        .max_size(1)
+        .timeout_cooldown(Some(TimeoutCooldown {
+            window: Duration::from_secs(60),
+            max_rate: 0.4,
+            min_timeouts: 2,
+            cooldown: Duration::from_secs(60),
+        }))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert!(matches!(
+        pool.get().await,
+        Err(managed::PoolError::CoolingDown { .. })
+    ));
+}
+
+#[tokio::test]
+async fn recycle_now() {
+    let manager = Manager {
+        create_fail: false,
+        recycle_fail: false,
+    };
+    let pool = Pool::builder(manager).max_size(1).build().unwrap();
+
+    let obj = pool.get().await.unwrap();
+    let obj = managed::Object::recycle_now(obj).await.unwrap();
+    assert_eq!(managed::Object::metrics(&obj).recycle_count, 1);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn recycle_now_replace() {
+    let manager = Manager {
+        create_fail: false,
+        recycle_fail: true,
+    };
+    let discarded = Arc::new(AtomicUsize::new(0));
+    let pool = Pool::builder(manager)
+        .max_size(1)
+        .capacity_limiter(managed::CapacityLimiter::new(1))
+        .on_discard({
+            let discarded = discarded.clone();
+            move |_, reason| {
+                assert_eq!(reason, DiscardReason::RecycleFailed);
+                let _ = discarded.fetch_add(1, Ordering::Relaxed);
+            }
+        })
+        .build()
+        .unwrap();
+
+    let obj = pool.get().await.unwrap();
+    // The replacement needs the only slot of the limiter held by `obj`.
+    let obj = time::timeout(Duration::from_secs(1), managed::Object::recycle_now(obj))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(managed::Object::metrics(&obj).recycle_count, 0);
+    assert_eq!(discarded.load(Ordering::Relaxed), 1);
+    assert_eq!(pool.status().size, 1);
+    drop(obj);
+    assert_eq!(pool.status().available, 1);
+}